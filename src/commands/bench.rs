@@ -0,0 +1,302 @@
+use crate::history::{BenchBaseline, git_short_hash, unix_timestamp};
+use crate::utils::{is_rust_project, output_json, output_text, run_command};
+use crate::{BaselineAction, BenchAction};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Changes within this many percent are treated as noise in the comparison.
+const NOISE_PCT: f64 = 5.0;
+
+pub async fn run(action: BenchAction, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    match action {
+        BenchAction::Baseline {
+            action: BaselineAction::Save { name },
+        } => save_baseline(&name, json_output).await,
+        BenchAction::Compare { name } => compare_baseline(&name, json_output).await,
+    }
+}
+
+async fn save_baseline(name: &str, json_output: bool) -> Result<()> {
+    check_baseline_name(name, json_output)?;
+    let benchmarks = run_benchmarks(json_output)?;
+
+    let baseline = BenchBaseline {
+        name: name.to_string(),
+        timestamp: unix_timestamp(),
+        git_hash: git_short_hash(),
+        benchmarks,
+    };
+    baseline.save()?;
+
+    let path = BenchBaseline::path(name);
+    if json_output {
+        output_json(&json!({
+            "baseline_name": name,
+            "path": path,
+            "git_hash": baseline.git_hash,
+            "benchmarks": baseline.benchmarks
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Saved {} benchmark(s) as baseline '{}' to {}",
+            baseline.benchmarks.len(),
+            name,
+            path.display()
+        ));
+        output_text(&format!(
+            "💡 Compare later changes with: oxy bench compare {}",
+            name
+        ));
+    }
+
+    Ok(())
+}
+
+async fn compare_baseline(name: &str, json_output: bool) -> Result<()> {
+    check_baseline_name(name, json_output)?;
+    let Some(baseline) = BenchBaseline::load(name)? else {
+        if json_output {
+            output_json(&json!({
+                "error": format!("No baseline named '{}'", name),
+                "suggestion": format!("Save one with: oxy bench baseline save {}", name)
+            }));
+        } else {
+            output_text(&format!("❌ No baseline named '{}'", name));
+            output_text(&format!(
+                "💡 Save one with: oxy bench baseline save {}",
+                name
+            ));
+        }
+        return Err(anyhow!("No baseline named '{}'", name));
+    };
+
+    let current = run_benchmarks(json_output)?;
+    let comparisons = compare_benchmarks(&baseline.benchmarks, &current);
+    let missing: Vec<&String> = baseline
+        .benchmarks
+        .keys()
+        .filter(|name| !current.contains_key(*name))
+        .collect();
+
+    if json_output {
+        output_json(&json!({
+            "baseline_name": name,
+            "baseline_git_hash": baseline.git_hash,
+            "comparisons": comparisons,
+            "missing_from_current": missing
+        }));
+        return Ok(());
+    }
+
+    output_text(&format!(
+        "📊 Benchmarks vs baseline '{}'{}",
+        name,
+        baseline
+            .git_hash
+            .as_ref()
+            .map(|hash| format!(" ({})", hash))
+            .unwrap_or_default()
+    ));
+    output_text("==========================================");
+    let width = comparisons
+        .iter()
+        .filter_map(|c| c["name"].as_str())
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    for comparison in &comparisons {
+        let name = comparison["name"].as_str().unwrap_or("");
+        let current_ns = comparison["current_ns"].as_f64().unwrap_or(0.0);
+        let Some(delta) = comparison["delta_pct"].as_f64() else {
+            output_text(&format!(
+                "  🆕 {:<width$}  {:>12}  (new)",
+                name,
+                format_ns(current_ns)
+            ));
+            continue;
+        };
+        let icon = if delta > NOISE_PCT {
+            "🐢"
+        } else if delta < -NOISE_PCT {
+            "⚡"
+        } else {
+            "  "
+        };
+        output_text(&format!(
+            "  {} {:<width$}  {:>12} → {:>12}  {:+.1}%",
+            icon,
+            name,
+            format_ns(comparison["baseline_ns"].as_f64().unwrap_or(0.0)),
+            format_ns(current_ns),
+            delta
+        ));
+    }
+    for name in &missing {
+        output_text(&format!("  ❓ {} is in the baseline but didn't run", name));
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c["delta_pct"].as_f64().is_some_and(|d| d > NOISE_PCT))
+        .count();
+    output_text("");
+    output_text(&format!(
+        "📈 {} benchmark(s) compared, {} slower by more than {}%",
+        comparisons.len(),
+        regressions,
+        NOISE_PCT
+    ));
+
+    Ok(())
+}
+
+/// Baselines are files under .oxygen/baselines, so names can't contain paths.
+fn check_baseline_name(name: &str, json_output: bool) -> Result<()> {
+    if !name.is_empty() && !name.contains(['/', '\\']) && name != "." && name != ".." {
+        return Ok(());
+    }
+    if json_output {
+        output_json(&json!({
+            "error": format!("Invalid baseline name '{}'", name),
+            "suggestion": "Use a plain name like main or before-refactor"
+        }));
+    } else {
+        output_text(&format!("❌ Invalid baseline name '{}'", name));
+        output_text("💡 Use a plain name like main or before-refactor");
+    }
+    Err(anyhow!("Invalid baseline name '{}'", name))
+}
+
+/// Run `cargo bench` and collect nanoseconds per iteration for each benchmark.
+fn run_benchmarks(json_output: bool) -> Result<BTreeMap<String, f64>> {
+    info!("Running cargo bench...");
+    let output = run_command("cargo", &["bench"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let benchmarks = parse_bench_output(&stdout);
+
+    if !output.status.success() || benchmarks.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = if output.status.success() {
+            "No benchmark results found in the cargo bench output"
+        } else {
+            "cargo bench failed"
+        };
+        if json_output {
+            output_json(&json!({
+                "error": error,
+                "details": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ {}", error));
+            output_text(stderr.trim());
+            if output.status.success() {
+                output_text("💡 Benchmarks need libtest #[bench] functions or criterion");
+            }
+        }
+        return Err(anyhow!("{}", error));
+    }
+
+    Ok(benchmarks)
+}
+
+/// Nanoseconds per iteration for each benchmark in `cargo bench` output, from libtest's
+/// `test name ... bench: 1,234 ns/iter (+/- 56)` lines and criterion's
+/// `name time: [low estimate high]` lines, using criterion's middle estimate.
+pub fn parse_bench_output(stdout: &str) -> BTreeMap<String, f64> {
+    let mut benchmarks = BTreeMap::new();
+    let mut previous_line = "";
+
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("test ")
+            && let Some((name, result)) = rest.split_once(" ... bench:")
+            && let Some(ns) = result.split_whitespace().next()
+            && let Ok(ns) = ns.replace(',', "").parse::<f64>()
+        {
+            benchmarks.insert(name.trim().to_string(), ns);
+        } else if let Some((name, estimates)) = trimmed.split_once("time:")
+            && let Some(estimates) = estimates.trim().strip_prefix('[')
+        {
+            // Criterion puts long names on a line of their own
+            let name = if name.trim().is_empty() {
+                previous_line
+            } else {
+                name.trim()
+            };
+            let parts: Vec<&str> = estimates.trim_end_matches(']').split_whitespace().collect();
+            if let [_, _, value, unit, ..] = parts.as_slice()
+                && let (Ok(value), Some(scale)) = (value.parse::<f64>(), unit_ns(unit))
+                && !name.is_empty()
+            {
+                benchmarks.insert(name.to_string(), value * scale);
+            }
+        }
+        if !trimmed.is_empty() {
+            previous_line = trimmed;
+        }
+    }
+    benchmarks
+}
+
+fn unit_ns(unit: &str) -> Option<f64> {
+    match unit {
+        "ps" => Some(0.001),
+        "ns" => Some(1.0),
+        "µs" | "us" => Some(1_000.0),
+        "ms" => Some(1_000_000.0),
+        "s" => Some(1_000_000_000.0),
+        _ => None,
+    }
+}
+
+/// `{name, baseline_ns, current_ns, delta_pct}` for each current benchmark, where a
+/// positive delta means slower. Benchmarks missing from the baseline have no delta.
+pub fn compare_benchmarks(
+    baseline: &BTreeMap<String, f64>,
+    current: &BTreeMap<String, f64>,
+) -> Vec<Value> {
+    current
+        .iter()
+        .map(|(name, &current_ns)| {
+            let baseline_ns = baseline.get(name).copied();
+            let delta_pct =
+                baseline_ns
+                    .filter(|baseline_ns| *baseline_ns > 0.0)
+                    .map(|baseline_ns| {
+                        ((current_ns - baseline_ns) * 1000.0 / baseline_ns).round() / 10.0
+                    });
+            json!({
+                "name": name,
+                "baseline_ns": baseline_ns,
+                "current_ns": current_ns,
+                "delta_pct": delta_pct
+            })
+        })
+        .collect()
+}
+
+fn format_ns(ns: f64) -> String {
+    if ns >= 1_000_000_000.0 {
+        format!("{:.2} s", ns / 1_000_000_000.0)
+    } else if ns >= 1_000_000.0 {
+        format!("{:.2} ms", ns / 1_000_000.0)
+    } else if ns >= 1_000.0 {
+        format!("{:.2} µs", ns / 1_000.0)
+    } else {
+        format!("{:.1} ns", ns)
+    }
+}
@@ -1,14 +1,119 @@
+use crate::{EmitFormat, LtoMode};
+use crate::commands::perf::{TIMING_REPORT, TOP_SLOW_CRATES, parse_unit_data};
+use crate::history::{BuildHistory, BuildRun, git_short_hash, unix_timestamp};
 use crate::utils::{
-    format_bytes, format_duration, get_binary_size, is_rust_project, output_json, output_text,
-    run_command_with_timing,
+    cargo_config_files, cargo_messages, format_bytes, format_duration, get_binary_size,
+    is_rust_project, output_json, output_text, read_manifest, run_command, run_command_in,
 };
 use anyhow::Result;
-use serde_json::json;
-use std::path::Path;
-use tracing::{error, info};
+use serde_json::{Value, json};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{error, info, warn};
 
-pub async fn run(json_output: bool) -> Result<()> {
-    if !is_rust_project() {
+/// Which cargo target `oxy build` should compile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BuildTarget {
+    #[default]
+    Default,
+    Bin(String),
+    Example(String),
+    Lib,
+    AllBins,
+    AllExamples,
+    Workspace,
+}
+
+impl BuildTarget {
+    /// Pick a target from the `oxy build` selection flags; clap keeps them mutually exclusive.
+    pub fn from_flags(
+        bin: Option<String>,
+        example: Option<String>,
+        lib: bool,
+        bins: bool,
+        examples: bool,
+        workspace: bool,
+    ) -> Self {
+        if let Some(name) = bin {
+            BuildTarget::Bin(name)
+        } else if let Some(name) = example {
+            BuildTarget::Example(name)
+        } else if lib {
+            BuildTarget::Lib
+        } else if bins {
+            BuildTarget::AllBins
+        } else if examples {
+            BuildTarget::AllExamples
+        } else if workspace {
+            BuildTarget::Workspace
+        } else {
+            BuildTarget::Default
+        }
+    }
+
+    fn cargo_args(&self) -> Vec<&str> {
+        match self {
+            BuildTarget::Default => vec![],
+            BuildTarget::Bin(name) => vec!["--bin", name],
+            BuildTarget::Example(name) => vec!["--example", name],
+            BuildTarget::Lib => vec!["--lib"],
+            BuildTarget::AllBins => vec!["--bins"],
+            BuildTarget::AllExamples => vec!["--examples"],
+            BuildTarget::Workspace => vec!["--workspace"],
+        }
+    }
+
+    /// `{kind, name}` for JSON output; `name` is only set for a single bin or example.
+    fn to_json(&self) -> Value {
+        let (kind, name) = match self {
+            BuildTarget::Default => ("default", None),
+            BuildTarget::Bin(name) => ("bin", Some(name)),
+            BuildTarget::Example(name) => ("example", Some(name)),
+            BuildTarget::Lib => ("lib", None),
+            BuildTarget::AllBins => ("bins", None),
+            BuildTarget::AllExamples => ("examples", None),
+            BuildTarget::Workspace => ("workspace", None),
+        };
+        json!({
+            "kind": kind,
+            "name": name
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BuildOptions {
+    pub target: BuildTarget,
+    pub strip: bool,
+    /// Force the release profile's LTO setting
+    pub lto: Option<LtoMode>,
+    /// Intermediate output to write next to each crate's artifacts
+    pub emit: Option<EmitFormat>,
+    /// Also write cargo's `--timings` report and rank the slowest units from it
+    pub timings: bool,
+    pub verbose: bool,
+    /// Build the package at this Cargo.toml instead of the one in the current directory
+    pub manifest_path: Option<PathBuf>,
+}
+
+pub async fn run(options: BuildOptions, json_output: bool) -> Result<()> {
+    if let Some(path) = &options.manifest_path
+        && !path.is_file()
+    {
+        let msg = format!("Manifest not found: {}", path.display());
+        if json_output {
+            output_json(&json!({
+                "error": msg,
+                "manifest_path": path,
+                "success": false
+            }));
+        } else {
+            error!("{}", msg);
+        }
+        return Ok(());
+    }
+    if options.manifest_path.is_none() && !is_rust_project() {
         let msg = "Not a Rust project (no Cargo.toml found)";
         if json_output {
             output_json(&json!({
@@ -23,51 +128,158 @@ pub async fn run(json_output: bool) -> Result<()> {
 
     info!("Building Rust project...");
 
-    match run_command_with_timing("cargo", &["build", "--release"]) {
+    // JSON messages let us show which crates took longest to compile;
+    // json-render-diagnostics keeps the human-readable warnings on stderr
+    let mut args = vec!["build", "--release", "--message-format=json-render-diagnostics"];
+    if options.timings {
+        // Replaces the report `oxy perf` reads, so only on request
+        args.push("--timings");
+    }
+    args.extend(options.target.cargo_args());
+    let manifest_path = options.manifest_path.as_deref().map(|path| path.to_string_lossy());
+    if let Some(path) = &manifest_path {
+        args.extend(["--manifest-path", path]);
+    }
+    if options.verbose {
+        // -vv also forwards build script output, where the cc crate logs each compiler and
+        // linker invocation as `running: ...`
+        args.push("-vv");
+    }
+
+    let mut envs: Vec<(&str, &OsStr)> = Vec::new();
+    if options.verbose {
+        // Modern cc versions only log their commands with CC_ENABLE_DEBUG_OUTPUT set
+        envs.push(("CC_ENABLE_DEBUG_OUTPUT", OsStr::new("1")));
+    }
+    if let Some(lto) = options.lto {
+        envs.push(("CARGO_PROFILE_RELEASE_LTO", OsStr::new(lto_profile_value(lto))));
+    }
+    let emit_arg = options.emit.map(|emit| format!("--emit={}", emit_name(emit)));
+    let mut rustc_args = Vec::new();
+    if let Some(arg) = &emit_arg {
+        rustc_args.push(arg.as_str());
+    }
+    if options.verbose {
+        // rustc prints each link command on stdout
+        rustc_args.push("--print=link-args");
+    }
+    let manifest = options.manifest_path.as_deref().unwrap_or(Path::new("Cargo.toml"));
+    // History and reports belong to the built project, not the current directory
+    let project_root = match manifest.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let lto_mode = effective_lto_mode(options.lto, manifest);
+    let sccache = sccache_wrapper();
+    let sccache_before = sccache.as_deref().and_then(sccache_snapshot);
+
+    let start = Instant::now();
+    let result = run_command_in("cargo", &args, Path::new("."), &envs);
+    match result.map(|output| (output, start.elapsed())) {
         Ok((output, duration)) => {
             let success = output.status.success();
-            let stdout = String::from_utf8_lossy(&output.stdout);
+            // Anything on stdout that isn't one of cargo's JSON messages
+            let stdout: String = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| serde_json::from_str::<Value>(line).is_err())
+                .map(|line| format!("{}\n", line))
+                .collect();
             let stderr = String::from_utf8_lossy(&output.stderr);
+            let rustc_start = Instant::now();
+            let rustc_outputs = if success && !rustc_args.is_empty() {
+                rustc_local_targets(
+                    &cargo_messages(&output, "compiler-artifact"),
+                    manifest_path.as_deref(),
+                    &rustc_args,
+                    &envs,
+                )
+            } else {
+                Vec::new()
+            };
+            let rustc_output: String = rustc_outputs
+                .iter()
+                .map(|output| {
+                    format!(
+                        "{}\n{}",
+                        String::from_utf8_lossy(&output.stdout),
+                        String::from_utf8_lossy(&output.stderr)
+                    )
+                })
+                .collect();
 
-            // Try to find the built binary
-            let mut binary_info = None;
-            
-            if let Ok(cargo_toml) = std::fs::read_to_string("Cargo.toml") {
-                if let Ok(manifest) = cargo_toml.parse::<toml::Value>() {
-                    if let Some(package_name) = manifest
-                        .get("package")
-                        .and_then(|p| p.get("name"))
-                        .and_then(|n| n.as_str())
-                    {
-                        // Try multiple possible paths for the binary
-                        let possible_paths = [
-                            format!("target/release/{}", package_name),
-                            format!("target/x86_64-unknown-linux-gnu/release/{}", package_name),
-                            format!("target/aarch64-unknown-linux-gnu/release/{}", package_name),
-                        ];
-                        
-                        for path_str in &possible_paths {
-                            let binary_path = Path::new(path_str);
-                            if binary_path.exists() {
-                                if let Ok(size) = get_binary_size(path_str) {
-                                    binary_info = Some(json!({
-                                        "path": path_str,
-                                        "size_bytes": size,
-                                        "size_formatted": format_bytes(size)
-                                    }));
-                                    break;
-                                }
-                            }
-                        }
-                    }
+            let mut artifacts = if !success {
+                Vec::new()
+            } else if options.target == BuildTarget::Workspace {
+                executable_artifacts(&cargo_messages(&output, "compiler-artifact"))
+            } else if let Some(path) = &options.manifest_path {
+                // The package may build into a workspace target dir elsewhere, so use the
+                // paths cargo reports
+                let lib = options.target == BuildTarget::Lib;
+                package_artifacts(&cargo_messages(&output, "compiler-artifact"), path, lib)
+            } else {
+                find_artifacts(&options.target)
+            };
+            let strip_available = !options.strip || strip_artifacts(&mut artifacts);
+            let (binary_size, lto_reduction) = if success {
+                record_build_size(
+                    project_root,
+                    &artifacts,
+                    lto_mode,
+                    options.strip && strip_available,
+                )
+            } else {
+                (None, None)
+            };
+            // With JSON messages, build script output is on stdout
+            let linker_commands = if options.verbose {
+                linker_commands(&format!("{}\n{}\n{}", stdout, stderr, rustc_output))
+            } else {
+                Vec::new()
+            };
+            let slow_crates = if success {
+                let timing_units = if options.timings {
+                    std::fs::read_to_string(timing_report(manifest, project_root))
+                        .map(|report| parse_unit_data(&report))
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                slowest_units(&cargo_messages(&output, "compiler-artifact"), &timing_units)
+            } else {
+                Vec::new()
+            };
+            let emitted = match options.emit {
+                Some(emit) if success => {
+                    let messages: Vec<Value> = rustc_outputs
+                        .iter()
+                        .flat_map(|output| cargo_messages(output, "compiler-artifact"))
+                        .collect();
+                    emitted_files(&messages, emit, rustc_start)
                 }
-            }
+                _ => Vec::new(),
+            };
+            let sccache_stats = sccache
+                .as_deref()
+                .and_then(sccache_snapshot)
+                .and_then(|after| sccache_build_stats(sccache_before.as_ref(), &after));
 
             if json_output {
                 output_json(&json!({
                     "success": success,
                     "duration": format_duration(duration),
-                    "binary": binary_info,
+                    "target": options.target.to_json(),
+                    "manifest_path": options.manifest_path,
+                    "binary": artifacts.first(),
+                    "artifacts": artifacts,
+                    "stripped": options.strip && strip_available,
+                    "lto_mode": lto_mode,
+                    "binary_size_bytes": binary_size,
+                    "lto_size_reduction_bytes": lto_reduction,
+                    "slow_crates": slow_crates,
+                    "sccache_stats": sccache_stats,
+                    "emit_format": options.emit.map(emit_name),
+                    "output_files": emitted,
+                    "linker_commands": linker_commands,
                     "stdout": stdout,
                     "stderr": stderr
                 }));
@@ -77,11 +289,81 @@ pub async fn run(json_output: bool) -> Result<()> {
                     format_duration(duration)
                 ));
 
-                if let Some(binary) = binary_info {
+                let label = if options.target == BuildTarget::Lib {
+                    "Library"
+                } else {
+                    "Binary"
+                };
+                for artifact in &artifacts {
                     if let (Some(path), Some(size)) =
-                        (binary["path"].as_str(), binary["size_formatted"].as_str())
+                        (artifact["path"].as_str(), artifact["size_formatted"].as_str())
                     {
-                        output_text(&format!("📦 Binary: {} ({})", path, size));
+                        output_text(&format!("📦 {}: {} ({})", label, path, size));
+                    }
+                    if let Some(before) = artifact["size_before_strip_bytes"].as_u64() {
+                        output_text(&format!("   ✂️  Stripped from {}", format_bytes(before)));
+                    }
+                }
+                if let (Some(size), Some(reduction)) = (binary_size, lto_reduction) {
+                    let baseline = size as i64 + reduction;
+                    output_text(&format!(
+                        "⚡ LTO ({}): {} {} than the last non-LTO build ({:+.1}%)",
+                        lto_mode,
+                        format_bytes(reduction.unsigned_abs()),
+                        if reduction >= 0 { "smaller" } else { "larger" },
+                        -(reduction as f64) * 100.0 / baseline.max(1) as f64
+                    ));
+                } else if options.lto.is_some() && lto_mode != "off" && binary_size.is_some() {
+                    output_text("💡 Run `oxy build --lto off` once to record a size to compare LTO builds against");
+                }
+                if let Some(stats) = &sccache_stats {
+                    let rate = stats["hit_rate_pct"]
+                        .as_f64()
+                        .map(|rate| format!("{:.0}%", rate))
+                        .unwrap_or_else(|| "n/a".to_string());
+                    output_text(&format!(
+                        "🗄️  sccache: {} hit rate ({} hits, {} misses)",
+                        rate,
+                        stats["hits"].as_u64().unwrap_or(0),
+                        stats["misses"].as_u64().unwrap_or(0)
+                    ));
+                }
+                if let Some(emit) = options.emit {
+                    output_text("");
+                    output_text(&format!("🔬 Emitted {}:", emit_name(emit)));
+                    for file in &emitted {
+                        output_text(&format!(
+                            "  {}  {}",
+                            file["crate_name"].as_str().unwrap_or(""),
+                            file["path"].as_str().unwrap_or("")
+                        ));
+                    }
+                    if let Some(path) = emitted.first().and_then(|file| file["path"].as_str()) {
+                        let pager = if run_command("bat", &["--version"]).is_ok() {
+                            "bat"
+                        } else {
+                            "less"
+                        };
+                        output_text(&format!("💡 View with: {} {}", pager, path));
+                    } else {
+                        output_text("⚠️  No emitted files found for the project's crates");
+                    }
+                }
+                if !strip_available {
+                    output_text("⚠️  strip is not available on this platform");
+                    output_text("💡 Add `strip = true` to [profile.release] in Cargo.toml instead");
+                }
+
+                if !slow_crates.is_empty() {
+                    output_text("");
+                    output_text("🐢 Slowest compilation units:");
+                    for unit in &slow_crates {
+                        output_text(&format!(
+                            "  {:>8}ms  {} {}",
+                            unit["duration_ms"].as_u64().unwrap_or(0),
+                            unit["name"].as_str().unwrap_or("unknown"),
+                            unit["version"].as_str().unwrap_or("")
+                        ));
                     }
                 }
 
@@ -90,17 +372,20 @@ pub async fn run(json_output: bool) -> Result<()> {
                     output_text("\n⚠️  Warnings:");
                     output_text(&stderr);
                 }
+                print_linker_commands(&linker_commands);
             } else {
                 output_text(&format!(
                     "❌ Build failed after {}",
                     format_duration(duration)
                 ));
+                // Diagnostics are rendered to stderr
                 if !stderr.is_empty() {
                     output_text(&stderr);
                 }
                 if !stdout.is_empty() {
                     output_text(&stdout);
                 }
+                print_linker_commands(&linker_commands);
             }
         }
         Err(e) => {
@@ -117,3 +402,641 @@ pub async fn run(json_output: bool) -> Result<()> {
 
     Ok(())
 }
+
+fn emit_name(emit: EmitFormat) -> &'static str {
+    match emit {
+        EmitFormat::Asm => "asm",
+        EmitFormat::LlvmIr => "llvm-ir",
+        EmitFormat::LlvmBc => "llvm-bc",
+        EmitFormat::Mir => "mir",
+    }
+}
+
+/// `{crate_name, path}` for the `--emit` output of each local crate cargo built since
+/// `since`. rustc puts it in the `deps` directory next to the crate's artifacts.
+fn emitted_files(messages: &[Value], emit: EmitFormat, since: Instant) -> Vec<Value> {
+    let extension = match emit {
+        EmitFormat::Asm => "s",
+        EmitFormat::LlvmIr => "ll",
+        EmitFormat::LlvmBc => "bc",
+        EmitFormat::Mir => "mir",
+    };
+    let started = std::time::SystemTime::now() - since.elapsed();
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    let mut files = Vec::new();
+    let mut seen = Vec::new();
+    for message in messages {
+        // cargo reports the dependencies too, but only the project's own crates are of interest
+        let local = message["package_id"]
+            .as_str()
+            .is_some_and(|id| id.contains("path+file://"));
+        let Some(name) = message["target"]["name"].as_str() else {
+            continue;
+        };
+        let Some(artifact) = message["filenames"][0].as_str().map(Path::new) else {
+            continue;
+        };
+        let Some(dir) = artifact.parent() else {
+            continue;
+        };
+        let deps = if dir.ends_with("deps") {
+            dir.to_path_buf()
+        } else {
+            dir.join("deps")
+        };
+        let crate_name = name.replace('-', "_");
+        if !local || seen.contains(&(crate_name.clone(), deps.clone())) {
+            continue;
+        }
+
+        let Ok(entries) = std::fs::read_dir(&deps) else {
+            continue;
+        };
+        let candidates: Vec<(PathBuf, std::time::SystemTime)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == extension)
+                    && path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(|stem| stem.rsplit_once('-'))
+                        .is_some_and(|(stem, _)| stem == crate_name)
+            })
+            .filter_map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect();
+        // Files from this build, or the latest one if cargo found the crate fresh
+        let mut found: Vec<PathBuf> = candidates
+            .iter()
+            .filter(|(_, modified)| *modified >= started)
+            .map(|(path, _)| path.clone())
+            .collect();
+        if found.is_empty()
+            && let Some((path, _)) = candidates.iter().max_by_key(|(_, modified)| *modified)
+        {
+            found.push(path.clone());
+        }
+        found.sort();
+        for path in found {
+            let path = path.strip_prefix(&cwd).unwrap_or(&path).display().to_string();
+            files.push(json!({
+                "crate_name": crate_name,
+                "path": path
+            }));
+        }
+        seen.push((crate_name, deps));
+    }
+    files
+}
+
+/// The sccache executable rustc runs through, if `RUSTC_WRAPPER` or else
+/// `build.rustc-wrapper` in a cargo config file is sccache.
+fn sccache_wrapper() -> Option<String> {
+    let wrapper = match std::env::var("RUSTC_WRAPPER") {
+        Ok(wrapper) => Some(wrapper),
+        Err(_) => cargo_config_files().iter().rev().find_map(|path| {
+            let config = read_manifest(path).ok()?;
+            config.get("build")?.get("rustc-wrapper")?.as_str().map(String::from)
+        }),
+    };
+    wrapper.filter(|wrapper| wrapper.contains("sccache"))
+}
+
+fn sccache_snapshot(sccache: &str) -> Option<Value> {
+    let output = run_command(sccache, &["--show-stats", "--stats-format", "json"]).ok()?;
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// `{hit_rate_pct, hits, misses, cache_size_bytes}` for the compilations between two
+/// `sccache --show-stats --stats-format json` snapshots, as sccache's counters are totals
+/// since its server started.
+pub fn sccache_build_stats(before: Option<&Value>, after: &Value) -> Option<Value> {
+    let (mut hits, mut misses) = sccache_counts(after)?;
+    if let Some((hits_before, misses_before)) = before.and_then(sccache_counts)
+        // A restarted server starts counting from zero again
+        && hits >= hits_before
+        && misses >= misses_before
+    {
+        hits -= hits_before;
+        misses -= misses_before;
+    }
+    let total = hits + misses;
+    Some(json!({
+        "hit_rate_pct": (total > 0).then(|| (hits as f64 * 1000.0 / total as f64).round() / 10.0),
+        "hits": hits,
+        "misses": misses,
+        "cache_size_bytes": after["cache_size"].as_u64()
+    }))
+}
+
+/// Cache hits and misses; newer sccache versions count them per language.
+fn sccache_counts(snapshot: &Value) -> Option<(u64, u64)> {
+    let count = |value: &Value| match value {
+        Value::Number(count) => count.as_u64(),
+        value => Some(value.get("counts")?.as_object()?.values().filter_map(Value::as_u64).sum()),
+    };
+    let stats = &snapshot["stats"];
+    Some((count(&stats["cache_hits"])?, count(&stats["cache_misses"])?))
+}
+
+/// Value of `CARGO_PROFILE_RELEASE_LTO` for an `--lto` mode.
+fn lto_profile_value(mode: LtoMode) -> &'static str {
+    match mode {
+        LtoMode::Thin => "thin",
+        LtoMode::Fat => "fat",
+        LtoMode::Off => "false",
+    }
+}
+
+/// The release LTO setting the build uses as `thin`, `fat` or `off`: the `--lto` flag, else
+/// `CARGO_PROFILE_RELEASE_LTO`, else `profile.release.lto` in `manifest`.
+fn effective_lto_mode(flag: Option<LtoMode>, manifest: &Path) -> &'static str {
+    let setting = match flag {
+        Some(mode) => lto_profile_value(mode).to_string(),
+        None => std::env::var("CARGO_PROFILE_RELEASE_LTO").ok().unwrap_or_else(|| {
+            read_manifest(manifest)
+                .ok()
+                .and_then(|m| m.get("profile")?.get("release")?.get("lto").cloned())
+                .map(|lto| match lto {
+                    toml::Value::String(lto) => lto,
+                    other => other.to_string(),
+                })
+                .unwrap_or_default()
+        }),
+    };
+    // `lto = true` means fat LTO; `false` and unset only do crate-local thin LTO
+    match setting.as_str() {
+        "thin" => "thin",
+        "fat" | "true" => "fat",
+        _ => "off",
+    }
+}
+
+/// Record the size of the build's main artifact in the build history. Returns the size and,
+/// for LTO builds, how much smaller it is than the last non-LTO build of the same binary.
+fn record_build_size(
+    project_root: &Path,
+    artifacts: &[Value],
+    lto_mode: &str,
+    stripped: bool,
+) -> (Option<u64>, Option<i64>) {
+    let Some((binary, size)) = artifacts
+        .first()
+        .and_then(|a| Some((a["path"].as_str()?.to_string(), a["size_bytes"].as_u64()?)))
+    else {
+        return (None, None);
+    };
+
+    let history = BuildHistory::load(project_root).unwrap_or_else(|e| {
+        warn!("Ignoring unreadable build history: {:#}", e);
+        BuildHistory::default()
+    });
+    let reduction = (lto_mode != "off")
+        .then(|| history.latest(&binary, "off", stripped))
+        .flatten()
+        .map(|baseline| baseline.size_bytes as i64 - size as i64);
+
+    let run = BuildRun {
+        timestamp: unix_timestamp(),
+        git_hash: git_short_hash(),
+        lto: lto_mode.to_string(),
+        stripped,
+        binary,
+        size_bytes: size,
+    };
+    if let Err(e) = BuildHistory::append(project_root, run) {
+        warn!("Failed to record build history: {:#}", e);
+    }
+    (Some(size), reduction)
+}
+
+/// The `--timings` report of the package at `manifest`, which cargo writes into the
+/// package's target directory. That may be a workspace's, so ask cargo where it is.
+fn timing_report(manifest: &Path, project_root: &Path) -> PathBuf {
+    let manifest_str = manifest.to_string_lossy();
+    let args = ["metadata", "--no-deps", "--format-version", "1", "--manifest-path", &manifest_str];
+    run_command("cargo", &args)
+        .ok()
+        .and_then(|output| serde_json::from_slice::<Value>(&output.stdout).ok())
+        .and_then(|metadata| metadata["target_directory"].as_str().map(PathBuf::from))
+        .map(|target_dir| target_dir.join("cargo-timings").join("cargo-timing.html"))
+        .unwrap_or_else(|| project_root.join(TIMING_REPORT))
+}
+
+/// Rerun each of the project's own targets from a finished build through `cargo rustc`,
+/// which passes `rustc_args` to that target's final compiler invocation only. Flags set
+/// through RUSTFLAGS would change every dependency's fingerprint and rebuild it. The
+/// project's packages are cleaned first, since cargo skips fresh targets even when the
+/// extra arguments differ.
+fn rustc_local_targets(
+    messages: &[Value],
+    manifest_path: Option<&str>,
+    rustc_args: &[&str],
+    envs: &[(&str, &OsStr)],
+) -> Vec<std::process::Output> {
+    let mut targets: Vec<(&str, Vec<&str>)> = Vec::new();
+    for message in messages {
+        let Some(package_id) = message["package_id"]
+            .as_str()
+            .filter(|id| id.contains("path+file://"))
+        else {
+            continue;
+        };
+        let Some(name) = message["target"]["name"].as_str() else {
+            continue;
+        };
+        let kinds: Vec<&str> = message["target"]["kind"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|kind| kind.as_str())
+            .collect();
+        let selector = if kinds.contains(&"bin") {
+            vec!["--bin", name]
+        } else if kinds.contains(&"example") {
+            vec!["--example", name]
+        } else if kinds.iter().any(|kind| kind.contains("lib") || *kind == "proc-macro") {
+            vec!["--lib"]
+        } else {
+            // Build scripts
+            continue;
+        };
+        if !targets.contains(&(package_id, selector.clone())) {
+            targets.push((package_id, selector));
+        }
+    }
+
+    let mut packages: Vec<&str> = targets.iter().map(|(package_id, _)| *package_id).collect();
+    packages.dedup();
+    for package_id in packages {
+        let mut args = vec!["clean", "--release", "--package", package_id];
+        if let Some(path) = manifest_path {
+            args.extend(["--manifest-path", path]);
+        }
+        if let Err(e) = run_command_in("cargo", &args, Path::new("."), envs) {
+            warn!("{:#}", e);
+        }
+    }
+
+    let mut outputs = Vec::new();
+    for (package_id, selector) in &targets {
+        let mut args = vec![
+            "rustc",
+            "--release",
+            "--message-format=json-render-diagnostics",
+            "--package",
+            package_id,
+        ];
+        args.extend(selector);
+        if let Some(path) = manifest_path {
+            args.extend(["--manifest-path", path]);
+        }
+        args.push("--");
+        args.extend(rustc_args);
+        match run_command_in("cargo", &args, Path::new("."), envs) {
+            Ok(output) => {
+                if !output.status.success() {
+                    warn!(
+                        "cargo rustc failed for {}: {}",
+                        selector.join(" "),
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                outputs.push(output);
+            }
+            Err(e) => warn!("{:#}", e),
+        }
+    }
+    outputs
+}
+
+/// Linker and C compiler invocations in cargo output: the link commands rustc prints for
+/// `--print=link-args`, and the `running: ...` lines build scripts print through the cc
+/// crate.
+pub fn linker_commands(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if let Some(command) = rustc_link_command(line) {
+                return Some(command.to_string());
+            }
+            // Build script output is prefixed with `[package version] `
+            let line = match line.strip_prefix('[') {
+                Some(rest) => rest.split_once("] ").map_or(line, |(_, rest)| rest),
+                None => line,
+            };
+            let (keyword, command) = line.split_once([':', ' '])?;
+            if !keyword.eq_ignore_ascii_case("running") {
+                return None;
+            }
+            let command = command.trim().trim_matches('`');
+            let program = command
+                .split_whitespace()
+                .next()?
+                .trim_matches('"')
+                .rsplit(['/', '\\'])
+                .next()?;
+            is_linker(program).then(|| command.to_string())
+        })
+        .collect()
+}
+
+/// The command in a `--print=link-args` line, which rustc prefixes with the environment
+/// it runs the linker in, e.g. `LC_ALL="C" PATH="..." "cc" "-m64" ...`.
+fn rustc_link_command(line: &str) -> Option<&str> {
+    let mut rest = line;
+    while let Some((name, value)) = rest.split_once('=') {
+        if name.is_empty() || !name.bytes().all(|b| b.is_ascii_uppercase() || b == b'_') {
+            break;
+        }
+        // Values are quoted and may contain spaces
+        let value = value.strip_prefix('"')?;
+        let end = value.find('"')?;
+        rest = value[end + 1..].trim_start();
+    }
+    let program = rest.strip_prefix('"')?.split('"').next()?;
+    let file_name = program.rsplit(['/', '\\']).next()?;
+    (line != rest && is_linker(file_name)).then_some(rest)
+}
+
+/// Whether `program` (a file name) is a C compiler driver or linker.
+fn is_linker(program: &str) -> bool {
+    const LINKERS: &[&str] = &[
+        "cc", "c++", "gcc", "g++", "clang", "clang++", "cl", "ld", "lld", "ld.lld", "ld.gold",
+        "mold", "link", "rust-lld",
+    ];
+    // Cross toolchains prefix the triple, e.g. aarch64-linux-gnu-gcc
+    const SUFFIXES: &[&str] = &["-gcc", "-cc", "-clang", "-ld"];
+
+    let program = program.strip_suffix(".exe").unwrap_or(program);
+    LINKERS.contains(&program) || SUFFIXES.iter().any(|suffix| program.ends_with(suffix))
+}
+
+fn print_linker_commands(commands: &[String]) {
+    if commands.is_empty() {
+        return;
+    }
+    output_text("");
+    output_text("🔗 Linker and C compiler invocations:");
+    for command in commands {
+        output_text(&format!("  {}", console::style(command).cyan()));
+    }
+}
+
+/// The slowest units compiled by this build, at most `TOP_SLOW_CRATES`. Uses the
+/// `duration_ms` of `compiler-artifact` messages where cargo provides it, and the
+/// `--timings` report otherwise. Units that were already fresh are left out.
+pub fn slowest_units(artifacts: &[Value], timing_units: &[Value]) -> Vec<Value> {
+    let mut units: Vec<Value> = artifacts
+        .iter()
+        .filter(|artifact| artifact["fresh"] != true)
+        .filter_map(|artifact| {
+            let duration_ms = artifact["duration_ms"].as_u64()?;
+            // Package IDs end in `#name@version`, or just `#version` for path packages
+            // whose directory matches the name
+            let fragment = artifact["package_id"].as_str()?.rsplit('#').next()?;
+            let (name, version) = match fragment.rsplit_once('@') {
+                Some((name, version)) => (name, version),
+                None => (artifact["target"]["name"].as_str()?, fragment),
+            };
+            Some(json!({
+                "name": name,
+                "version": version,
+                "duration_ms": duration_ms
+            }))
+        })
+        .collect();
+    if units.is_empty() {
+        units = timing_units
+            .iter()
+            .map(|unit| {
+                json!({
+                    "name": unit["name"],
+                    "version": unit["version"],
+                    "duration_ms": unit["total_ms"]
+                })
+            })
+            .collect();
+    }
+
+    units.sort_by(|a, b| b["duration_ms"].as_u64().cmp(&a["duration_ms"].as_u64()));
+    units.truncate(TOP_SLOW_CRATES);
+    units
+}
+
+/// Strip debug symbols from each artifact in place, recording the size beforehand.
+/// Returns false when no `strip` tool could be run.
+fn strip_artifacts(artifacts: &mut [Value]) -> bool {
+    if cfg!(target_os = "windows") {
+        return false;
+    }
+
+    for artifact in artifacts.iter_mut() {
+        let Some(path) = artifact["path"].as_str().map(String::from) else {
+            continue;
+        };
+        // Stripping an rlib destroys the metadata rustc needs to link against it
+        if path.ends_with(".rlib") {
+            continue;
+        }
+
+        let args = if cfg!(target_os = "macos") {
+            vec![path.as_str()]
+        } else {
+            vec!["--strip-all", path.as_str()]
+        };
+        match run_command("strip", &args) {
+            Ok(output) if output.status.success() => {
+                let Ok(size) = get_binary_size(&path) else {
+                    continue;
+                };
+                artifact["size_before_strip_bytes"] = artifact["size_bytes"].clone();
+                artifact["size_bytes"] = json!(size);
+                artifact["size_formatted"] = json!(format_bytes(size));
+            }
+            Ok(output) => {
+                warn!(
+                    "strip failed for {}: {}",
+                    path,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Err(_) => return false,
+        }
+    }
+    true
+}
+
+/// Where cargo may place release output, with or without an explicit `--target` triple.
+const RELEASE_DIRS: [&str; 3] = [
+    "target/release",
+    "target/x86_64-unknown-linux-gnu/release",
+    "target/aarch64-unknown-linux-gnu/release",
+];
+
+/// Locate the artifacts produced for `target` in the release output directories.
+pub fn find_artifacts(target: &BuildTarget) -> Vec<Value> {
+    let Ok(manifest) = read_manifest(Path::new("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Some(package_name) = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+    else {
+        return Vec::new();
+    };
+
+    let exe = |name: &str| format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+    let candidates: Vec<String> = match target {
+        // Member binaries aren't known from the root manifest; see `executable_artifacts`
+        BuildTarget::Workspace => vec![],
+        BuildTarget::Default => vec![exe(package_name)],
+        BuildTarget::Bin(name) => vec![exe(name)],
+        BuildTarget::AllBins => target_names(&manifest, "bin", package_name)
+            .iter()
+            .map(|name| exe(name))
+            .collect(),
+        BuildTarget::Example(name) => vec![format!("examples/{}", exe(name))],
+        BuildTarget::AllExamples => target_names(&manifest, "example", package_name)
+            .iter()
+            .map(|name| format!("examples/{}", exe(name)))
+            .collect(),
+        BuildTarget::Lib => {
+            let lib_name = manifest
+                .get("lib")
+                .and_then(|l| l.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or(package_name)
+                .replace('-', "_");
+            vec![
+                format!("lib{}.rlib", lib_name),
+                format!(
+                    "{}{}{}",
+                    std::env::consts::DLL_PREFIX,
+                    lib_name,
+                    std::env::consts::DLL_SUFFIX
+                ),
+                format!("lib{}.a", lib_name),
+            ]
+        }
+    };
+
+    let mut artifacts = Vec::new();
+    for candidate in &candidates {
+        let found = RELEASE_DIRS
+            .iter()
+            .map(|dir| format!("{}/{}", dir, candidate))
+            .find(|path| Path::new(path).is_file());
+
+        if let Some(path) = found
+            && let Ok(size) = get_binary_size(&path)
+        {
+            artifacts.push(json!({
+                "path": path,
+                "size_bytes": size,
+                "size_formatted": format_bytes(size)
+            }));
+        }
+    }
+    artifacts
+}
+
+/// Executables cargo reported building, for builds whose outputs the root manifest
+/// doesn't describe.
+fn executable_artifacts(messages: &[Value]) -> Vec<Value> {
+    artifact_entries(messages.iter().filter_map(|m| m["executable"].as_str()))
+}
+
+/// Files cargo reported building for the package at `manifest_path`: its executables, or
+/// with `lib` its library files.
+fn package_artifacts(messages: &[Value], manifest_path: &Path, lib: bool) -> Vec<Value> {
+    let manifest_path = manifest_path
+        .canonicalize()
+        .unwrap_or_else(|_| manifest_path.to_path_buf());
+    let package_messages = messages
+        .iter()
+        .filter(|m| m["manifest_path"].as_str().map(Path::new) == Some(manifest_path.as_path()));
+    if !lib {
+        return artifact_entries(package_messages.filter_map(|m| m["executable"].as_str()));
+    }
+    artifact_entries(
+        package_messages
+            .filter(|m| {
+                // `lib`, `rlib`, `cdylib`, `staticlib`, ...
+                m["target"]["kind"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|kind| kind.as_str().is_some_and(|kind| kind.contains("lib")))
+            })
+            .filter_map(|m| m["filenames"].as_array())
+            .flatten()
+            .filter_map(|f| f.as_str())
+            .filter(|f| !f.ends_with(".rmeta")),
+    )
+}
+
+/// Artifact entries with sizes for the given output files, relative to the current directory
+/// where possible and without duplicates.
+fn artifact_entries<'a>(files: impl Iterator<Item = &'a str>) -> Vec<Value> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut artifacts: Vec<Value> = Vec::new();
+    for file in files {
+        let path = Path::new(file);
+        let path = path.strip_prefix(&cwd).unwrap_or(path).display().to_string();
+        if artifacts.iter().any(|artifact| artifact["path"] == path.as_str()) {
+            continue;
+        }
+        if let Ok(size) = get_binary_size(&path) {
+            artifacts.push(json!({
+                "path": path,
+                "size_bytes": size,
+                "size_formatted": format_bytes(size)
+            }));
+        }
+    }
+    artifacts
+}
+
+/// Names of the `bin` or `example` targets declared in the manifest or discovered by cargo's
+/// auto-detection (`src/main.rs`, `src/bin/`, `examples/`).
+fn target_names(manifest: &toml::Value, kind: &str, package_name: &str) -> Vec<String> {
+    let mut names: Vec<String> = manifest
+        .get(kind)
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t.get("name").and_then(|n| n.as_str()))
+        .map(String::from)
+        .collect();
+
+    let auto_dir = if kind == "bin" { "src/bin" } else { "examples" };
+    if kind == "bin" && Path::new("src/main.rs").exists() {
+        names.push(package_name.to_string());
+    }
+    if let Ok(entries) = std::fs::read_dir(auto_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let discovered = if path.is_dir() && path.join("main.rs").exists() {
+                path.file_name()
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                path.file_stem()
+            } else {
+                None
+            };
+            if let Some(name) = discovered {
+                names.push(name.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
@@ -1,11 +1,47 @@
+use crate::commands::workspace::{WorkspaceMember, workspace_members};
+use crate::config::{Config, ScoringConfig};
+use crate::history::{
+    CheckHistory, CheckRun, HISTORY_DIR, git_short_hash, sparkline, unix_timestamp,
+};
 use crate::utils::{
-    format_duration, is_rust_project, output_json, output_text, run_command_with_timing,
+    DEPENDENCY_TABLES, cargo_config_files, cargo_messages, cargo_subcommand_installed, confirm,
+    find_files, format_duration, is_rust_project, output_json, output_text, read_manifest,
+    run_command, run_command_in, run_command_interactive, run_command_with_timing,
 };
-use anyhow::Result;
-use serde_json::json;
-use tracing::{error, info};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+/// Which steps `oxy check` should run. Flags are OR-ed with the `[check]` config.
+#[derive(Debug, Default)]
+pub struct CheckOptions {
+    pub no_fmt: bool,
+    pub no_clippy: bool,
+    pub no_check: bool,
+    /// Reorganize `use` declarations before the other checks run
+    pub fix_imports: bool,
+    /// Also run `cargo +nightly check`, reported separately from the overall result
+    pub check_nightly: bool,
+    /// Install the nightly toolchain without prompting if it's missing
+    pub yes: bool,
+    /// Don't warn about uncommitted changes before checking
+    pub allow_dirty: bool,
+    /// File listing packages and `--target <triple>` entries to check every combination of
+    pub targets_file: Option<PathBuf>,
+    /// Check every workspace member instead of only the default members
+    pub workspace: bool,
+}
+
+/// Nightly builds go to their own target dir so they don't invalidate the stable artifacts.
+pub const NIGHTLY_TARGET_DIR: &str = "target/nightly";
 
-pub async fn run(json_output: bool) -> Result<()> {
+/// rustfmt options that merge imports per crate and group them std / external / crate.
+const IMPORT_CONFIG: &str = "imports_granularity=Crate,group_imports=StdExternalCrate";
+
+pub async fn run(options: CheckOptions, json_output: bool) -> Result<()> {
     if !is_rust_project() {
         let msg = "Not a Rust project (no Cargo.toml found)";
         if json_output {
@@ -21,136 +57,1177 @@ pub async fn run(json_output: bool) -> Result<()> {
 
     info!("Running Rust project checks...");
 
+    let config = Config::load().unwrap_or_else(|e| {
+        warn!("Ignoring invalid oxygen config: {:#}", e);
+        Config::default()
+    });
+    let no_fmt = options.no_fmt || config.check.no_fmt;
+    let no_clippy = options.no_clippy || config.check.no_clippy;
+    let no_check = options.no_check || config.check.no_check;
+    let (fmt_args, clippy_args, check_args): (&[&str], &[&str], &[&str]) = if options.workspace {
+        (
+            &["fmt", "--all", "--check"],
+            &[
+                "clippy",
+                "--workspace",
+                "--message-format=json",
+                "--",
+                "-D",
+                "warnings",
+            ],
+            &["check", "--workspace"],
+        )
+    } else {
+        (
+            &["fmt", "--check"],
+            &["clippy", "--message-format=json", "--", "-D", "warnings"],
+            &["check"],
+        )
+    };
+
+    let started = Instant::now();
     let mut results = Vec::new();
+    let mut skipped_checks = Vec::new();
     let mut all_passed = true;
+    let mut fmt_passed = None;
+    let mut clippy_warnings = None;
+    let mut clippy_passed = None;
+    let mut clippy_lints = Vec::new();
+    let mut check_passed = None;
 
-    // Run cargo fmt --check
-    info!("Running cargo fmt --check...");
-    match run_command_with_timing("cargo", &["fmt", "--check"]) {
-        Ok((output, duration)) => {
-            let success = output.status.success();
-            all_passed &= success;
-            results.push(json!({
-                "command": "cargo fmt --check",
-                "success": success,
-                "duration": format_duration(duration),
-                "stdout": String::from_utf8_lossy(&output.stdout),
-                "stderr": String::from_utf8_lossy(&output.stderr)
-            }));
+    // Warn (but carry on) when the tree has uncommitted changes, since the results then
+    // don't describe any commit
+    let dirty_files_count = if options.allow_dirty {
+        None
+    } else {
+        count_dirty_files()
+    };
+    if !json_output && let Some(count) = dirty_files_count.filter(|count| *count > 0) {
+        output_text(&format!(
+            "⚠️  {} uncommitted {} - results may not match what you commit (--allow-dirty to hide)",
+            count,
+            if count == 1 { "file" } else { "files" }
+        ));
+    }
 
-            if !json_output {
-                if success {
-                    output_text(&format!(
-                        "✅ Format check passed ({})",
-                        format_duration(duration)
-                    ));
-                } else {
-                    output_text(&format!(
-                        "❌ Format check failed ({})",
-                        format_duration(duration)
-                    ));
-                    output_text(&String::from_utf8_lossy(&output.stderr));
+    if let Some(path) = &options.targets_file {
+        return run_matrix(path, no_fmt, no_clippy, no_check, json_output);
+    }
+
+    if options.fix_imports {
+        info!("Reorganizing imports...");
+        let result = fix_imports();
+        let success = result["success"] == true;
+        all_passed &= success;
+
+        if !json_output {
+            let files = result["files_changed"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if !success {
+                output_text(&format!(
+                    "❌ Import reorganization failed: {}",
+                    result["error"].as_str().unwrap_or("unknown error")
+                ));
+                if let Some(suggestion) = result["suggestion"].as_str() {
+                    output_text(&format!("💡 {}", suggestion));
+                }
+            } else if files.is_empty() {
+                output_text("✅ Imports already organized");
+            } else {
+                output_text(&format!("✅ Reorganized imports in {} files:", files.len()));
+                for file in &files {
+                    output_text(&format!("   {}", file.as_str().unwrap_or("")));
                 }
             }
         }
-        Err(e) => {
-            all_passed = false;
-            results.push(json!({
-                "command": "cargo fmt --check",
-                "success": false,
-                "error": e.to_string()
-            }));
+        results.push(result);
+    }
+
+    // Run cargo fmt --check
+    if no_fmt {
+        skipped_checks.push("cargo fmt --check");
+    } else {
+        info!("Running cargo fmt --check...");
+        match run_command_with_timing("cargo", fmt_args) {
+            Ok((output, duration)) => {
+                let success = output.status.success();
+                all_passed &= success;
+                fmt_passed = Some(success);
+                results.push(json!({
+                    "command": "cargo fmt --check",
+                    "success": success,
+                    "duration": format_duration(duration),
+                    "stdout": String::from_utf8_lossy(&output.stdout),
+                    "stderr": String::from_utf8_lossy(&output.stderr)
+                }));
+
+                if !json_output {
+                    if success {
+                        output_text(&format!(
+                            "✅ Format check passed ({})",
+                            format_duration(duration)
+                        ));
+                    } else {
+                        output_text(&format!(
+                            "❌ Format check failed ({})",
+                            format_duration(duration)
+                        ));
+                        output_text(&String::from_utf8_lossy(&output.stderr));
+                    }
+                }
+            }
+            Err(e) => {
+                all_passed = false;
+                results.push(json!({
+                    "command": "cargo fmt --check",
+                    "success": false,
+                    "error": e.to_string()
+                }));
 
-            if !json_output {
-                error!("❌ Failed to run cargo fmt: {}", e);
+                if !json_output {
+                    error!("❌ Failed to run cargo fmt: {}", e);
+                }
             }
         }
     }
 
     // Run cargo clippy
-    info!("Running cargo clippy...");
-    match run_command_with_timing("cargo", &["clippy", "--", "-D", "warnings"]) {
-        Ok((output, duration)) => {
-            let success = output.status.success();
-            all_passed &= success;
-            results.push(json!({
-                "command": "cargo clippy",
-                "success": success,
-                "duration": format_duration(duration),
-                "stdout": String::from_utf8_lossy(&output.stdout),
-                "stderr": String::from_utf8_lossy(&output.stderr)
-            }));
+    if no_clippy {
+        skipped_checks.push("cargo clippy");
+    } else {
+        info!("Running cargo clippy...");
+        match run_command_with_timing("cargo", clippy_args) {
+            Ok((output, duration)) => {
+                let success = output.status.success();
+                all_passed &= success;
+                clippy_passed = Some(success);
+                let messages = cargo_messages(&output, "compiler-message");
+                clippy_lints = clippy_lint_warnings(&messages);
+                clippy_warnings = Some(clippy_lints.len());
+                let rendered: String = messages
+                    .iter()
+                    .filter_map(|message| message["message"]["rendered"].as_str())
+                    .collect();
+                results.push(json!({
+                    "command": "cargo clippy",
+                    "success": success,
+                    "duration": format_duration(duration),
+                    "stdout": rendered,
+                    "stderr": String::from_utf8_lossy(&output.stderr)
+                }));
 
-            if !json_output {
-                if success {
-                    output_text(&format!("✅ Clippy passed ({})", format_duration(duration)));
-                } else {
-                    output_text(&format!("❌ Clippy failed ({})", format_duration(duration)));
-                    output_text(&String::from_utf8_lossy(&output.stdout));
+                if !json_output {
+                    if success {
+                        output_text(&format!("✅ Clippy passed ({})", format_duration(duration)));
+                    } else {
+                        output_text(&format!("❌ Clippy failed ({})", format_duration(duration)));
+                        for lint in &clippy_lints {
+                            output_text(&format!(
+                                "   [{}] {}:{} {}",
+                                lint["lint"].as_str().unwrap_or(""),
+                                lint["file"].as_str().unwrap_or("?"),
+                                lint["line"].as_u64().unwrap_or(0),
+                                lint["message"].as_str().unwrap_or("")
+                            ));
+                        }
+                        // Errors that aren't lints, like a failed build, still need the full
+                        // diagnostic
+                        for message in messages.iter().filter(|message| {
+                            message["message"]["level"] == "error" && lint_name(message).is_none()
+                        }) {
+                            output_text(message["message"]["rendered"].as_str().unwrap_or(""));
+                        }
+                        if messages.is_empty() {
+                            output_text(&String::from_utf8_lossy(&output.stderr));
+                        }
+                    }
                 }
             }
-        }
-        Err(e) => {
-            all_passed = false;
-            results.push(json!({
-                "command": "cargo clippy",
-                "success": false,
-                "error": e.to_string()
-            }));
+            Err(e) => {
+                all_passed = false;
+                results.push(json!({
+                    "command": "cargo clippy",
+                    "success": false,
+                    "error": e.to_string()
+                }));
 
-            if !json_output {
-                error!("❌ Failed to run cargo clippy: {}", e);
+                if !json_output {
+                    error!("❌ Failed to run cargo clippy: {}", e);
+                }
             }
         }
     }
 
     // Run cargo check
-    info!("Running cargo check...");
-    match run_command_with_timing("cargo", &["check"]) {
-        Ok((output, duration)) => {
-            let success = output.status.success();
-            all_passed &= success;
-            results.push(json!({
-                "command": "cargo check",
-                "success": success,
-                "duration": format_duration(duration),
-                "stdout": String::from_utf8_lossy(&output.stdout),
-                "stderr": String::from_utf8_lossy(&output.stderr)
-            }));
+    if no_check {
+        skipped_checks.push("cargo check");
+    } else {
+        info!("Running cargo check...");
+        match run_command_with_timing("cargo", check_args) {
+            Ok((output, duration)) => {
+                let success = output.status.success();
+                all_passed &= success;
+                check_passed = Some(success);
+                results.push(json!({
+                    "command": "cargo check",
+                    "success": success,
+                    "duration": format_duration(duration),
+                    "stdout": String::from_utf8_lossy(&output.stdout),
+                    "stderr": String::from_utf8_lossy(&output.stderr)
+                }));
 
-            if !json_output {
-                if success {
-                    output_text(&format!("✅ Check passed ({})", format_duration(duration)));
+                if !json_output {
+                    if success {
+                        output_text(&format!("✅ Check passed ({})", format_duration(duration)));
+                    } else {
+                        output_text(&format!("❌ Check failed ({})", format_duration(duration)));
+                        output_text(&String::from_utf8_lossy(&output.stderr));
+                    }
+                }
+            }
+            Err(e) => {
+                all_passed = false;
+                results.push(json!({
+                    "command": "cargo check",
+                    "success": false,
+                    "error": e.to_string()
+                }));
+
+                if !json_output {
+                    error!("❌ Failed to run cargo check: {}", e);
+                }
+            }
+        }
+    }
+
+    // Run cargo +nightly check, kept out of `all_passed` so upcoming breakage is only a heads-up
+    let nightly = if options.check_nightly {
+        let result = check_nightly(options.yes, json_output);
+        if !json_output {
+            let duration = result["duration"].as_str().unwrap_or("");
+            if result["skipped"] == true {
+                output_text(&format!(
+                    "⏭️  Nightly check skipped: {}",
+                    result["reason"].as_str().unwrap_or("nightly not installed")
+                ));
+            } else if result["success"] == true {
+                let warnings = result["warnings"].as_u64().unwrap_or(0);
+                if warnings == 0 {
+                    output_text(&format!("🌙 Nightly check passed ({})", duration));
                 } else {
-                    output_text(&format!("❌ Check failed ({})", format_duration(duration)));
-                    output_text(&String::from_utf8_lossy(&output.stderr));
+                    output_text(&format!(
+                        "🌙 Nightly check passed with {} warnings ({})",
+                        warnings, duration
+                    ));
+                }
+            } else {
+                output_text(&format!(
+                    "🌙 Nightly check failed ({}) - not counted towards the result",
+                    duration
+                ));
+                output_text(result["stderr"].as_str().unwrap_or(""));
+            }
+        }
+        Some(result)
+    } else {
+        None
+    };
+
+    // Run cargo machete, but only when it's available - the install hint lives in doctor
+    if config.check.run_machete && cargo_subcommand_installed("machete") {
+        info!("Running cargo machete...");
+        match run_command_with_timing("cargo", &["machete"]) {
+            Ok((output, duration)) => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let unused = parse_machete_output(&stdout);
+                let success = unused.is_empty();
+                all_passed &= success;
+                results.push(json!({
+                    "command": "cargo machete",
+                    "success": success,
+                    "duration": format_duration(duration),
+                    "unused_dependencies": unused,
+                    "stderr": String::from_utf8_lossy(&output.stderr)
+                }));
+
+                if !json_output {
+                    if success {
+                        output_text(&format!(
+                            "✅ No unused dependencies ({})",
+                            format_duration(duration)
+                        ));
+                    } else {
+                        output_text(&format!(
+                            "❌ Unused dependencies found ({})",
+                            format_duration(duration)
+                        ));
+                        for dep in &unused {
+                            output_text(&format!(
+                                "   {} ({})",
+                                dep["dependency"].as_str().unwrap_or("unknown"),
+                                dep["manifest"].as_str().unwrap_or("Cargo.toml")
+                            ));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                all_passed = false;
+                results.push(json!({
+                    "command": "cargo machete",
+                    "success": false,
+                    "error": e.to_string()
+                }));
+
+                if !json_output {
+                    error!("❌ Failed to run cargo machete: {}", e);
+                }
+            }
+        }
+    }
+
+    // Members pinning their own version of a `[workspace.dependencies]` entry
+    let inconsistencies = read_manifest(Path::new("Cargo.toml"))
+        .ok()
+        .filter(|root| {
+            root.get("workspace")
+                .and_then(|w| w.get("dependencies"))
+                .is_some()
+        })
+        .map(|root| {
+            info!("Checking workspace dependency consistency...");
+            find_workspace_inconsistencies(&root, &workspace_members(&root))
+        });
+    if let Some(inconsistencies) = &inconsistencies {
+        all_passed &= inconsistencies.is_empty();
+        if !json_output {
+            if inconsistencies.is_empty() {
+                output_text("✅ Workspace dependency versions are consistent");
+            } else {
+                output_text(&format!(
+                    "❌ {} workspace dependency inconsistency(s):",
+                    inconsistencies.len()
+                ));
+                for inconsistency in inconsistencies {
+                    output_text(&format!(
+                        "   {}: {} = \"{}\" (workspace has \"{}\")",
+                        inconsistency["member"].as_str().unwrap_or(""),
+                        inconsistency["dep"].as_str().unwrap_or(""),
+                        inconsistency["member_version"].as_str().unwrap_or(""),
+                        inconsistency["workspace_version"].as_str().unwrap_or("")
+                    ));
+                }
+                output_text("💡 Use `<dep>.workspace = true` to inherit the workspace version");
+            }
+        }
+    }
+
+    // A warning rather than a failure: the build itself reports the linker error
+    let configs: Vec<toml::Value> = cargo_config_files()
+        .iter()
+        .filter_map(|path| read_manifest(path).ok())
+        .collect();
+    if let Ok(manifest) = read_manifest(Path::new("Cargo.toml"))
+        && let Some(warning) = panic_strategy_warning(&manifest, &configs)
+    {
+        if !json_output {
+            output_text(&format!(
+                "⚠️  {}",
+                warning["message"].as_str().unwrap_or("")
+            ));
+            output_text(&format!("💡 {}", warning["fix"].as_str().unwrap_or("")));
+        }
+        results.push(warning);
+    }
+
+    let clippy = clippy_passed.map(|passed| (passed, clippy_warnings.unwrap_or(0)));
+    let score = quality_score(fmt_passed, clippy, check_passed, &config.check.scoring);
+
+    let run = CheckRun {
+        timestamp: unix_timestamp(),
+        git_hash: git_short_hash(),
+        fmt_passed,
+        clippy_passed,
+        clippy_warnings,
+        check_passed,
+        total_duration_ms: started.elapsed().as_millis() as u64,
+    };
+    if let Err(e) = CheckHistory::append(run) {
+        warn!("Failed to record check history: {:#}", e);
+    }
+
+    if json_output {
+        output_json(&json!({
+            "success": all_passed,
+            "results": results,
+            "skipped_checks": skipped_checks,
+            "nightly": nightly,
+            "dirty_files_count": dirty_files_count,
+            "allow_dirty": options.allow_dirty,
+            "inconsistencies": inconsistencies,
+            "warnings": clippy_lints,
+            "score": score
+        }));
+    } else {
+        if !skipped_checks.is_empty() {
+            output_text(&format!("⏭️  Skipped: {}", skipped_checks.join(", ")));
+        }
+        if let Some(score) = score {
+            output_text(&format!("📈 Quality Score: {}/100", score));
+        }
+
+        if all_passed {
+            output_text("\n🎉 All checks passed!");
+        } else {
+            output_text("\n💥 Some checks failed!");
+        }
+    }
+
+    // A non-zero exit lets CI jobs and git hooks stop on failures
+    if !all_passed {
+        return Err(anyhow!("Some checks failed"));
+    }
+
+    Ok(())
+}
+
+/// A `"warning"` status item when the release profile sets `panic = "unwind"` but the
+/// active build target (`CARGO_BUILD_TARGET`, else `build.target` from the cargo config
+/// files, given lowest precedence first) is bare-metal. Those targets have no unwinder, so
+/// the release build fails to link.
+pub fn panic_strategy_warning(manifest: &toml::Value, configs: &[toml::Value]) -> Option<Value> {
+    let build_target = |config: &toml::Value| match config.get("build")?.get("target")? {
+        toml::Value::String(target) => Some(vec![target.clone()]),
+        toml::Value::Array(list) => Some(
+            list.iter()
+                .filter_map(|t| t.as_str().map(String::from))
+                .collect(),
+        ),
+        _ => None,
+    };
+    let targets: Vec<String> = std::env::var("CARGO_BUILD_TARGET")
+        .ok()
+        .map(|target| vec![target])
+        .or_else(|| configs.iter().rev().find_map(build_target))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|target| is_bare_metal_triple(target))
+        .collect();
+    if targets.is_empty() {
+        return None;
+    }
+
+    // Profiles in the cargo config override the manifest's
+    let release_panic = |value: &toml::Value| {
+        value
+            .get("profile")?
+            .get("release")?
+            .get("panic")?
+            .as_str()
+            .map(String::from)
+    };
+    let panic = configs
+        .iter()
+        .rev()
+        .find_map(release_panic)
+        .or_else(|| release_panic(manifest));
+    // Left unset, bare-metal targets already default to aborting
+    if panic.as_deref() != Some("unwind") {
+        return None;
+    }
+
+    let message = format!(
+        "[profile.release] uses panic = \"unwind\" but {} {} bare-metal; without an unwinder \
+         the release build will fail to link",
+        targets.join(", "),
+        if targets.len() == 1 { "is" } else { "are" }
+    );
+
+    Some(json!({
+        "command": "release panic strategy",
+        "status": "warning",
+        "success": true,
+        "targets": targets,
+        "panic": panic,
+        "message": message,
+        "fix": "Set `panic = \"abort\"` under [profile.release] in Cargo.toml"
+    }))
+}
+
+/// Whether two version requirements match the same versions, so `1` and `1.0.0` or `1.2`
+/// and `^1.2.0` count as the same. Requirements semver can't parse are compared as text.
+fn same_requirement(a: &str, b: &str) -> bool {
+    match (semver::VersionReq::parse(a), semver::VersionReq::parse(b)) {
+        (Ok(a), Ok(b)) => {
+            let normalized = |req: semver::VersionReq| {
+                let mut comparators: Vec<_> = req.comparators.into_iter().map(normalize_comparator).collect();
+                comparators.sort_by_key(|c| format!("{}", c));
+                comparators
+            };
+            normalized(a) == normalized(b)
+        }
+        _ => a == b,
+    }
+}
+
+/// Fill in the version parts a comparator leaves out where that doesn't change what it
+/// matches: `^1` is `^1.0.0` and `~1.2` is `~1.2.0`, but `^0.0` is not `^0.0.0`.
+fn normalize_comparator(mut comparator: semver::Comparator) -> semver::Comparator {
+    use semver::Op;
+    match comparator.op {
+        Op::Caret if comparator.major > 0 => {
+            comparator.minor.get_or_insert(0);
+            comparator.patch.get_or_insert(0);
+        }
+        Op::Caret if comparator.minor.is_some_and(|minor| minor > 0) => {
+            comparator.patch.get_or_insert(0);
+        }
+        Op::Tilde if comparator.minor.is_some() => {
+            comparator.patch.get_or_insert(0);
+        }
+        _ => {}
+    }
+    comparator
+}
+
+/// Whether a target triple has no operating system, like `thumbv7em-none-eabihf` or
+/// `riscv32imac-unknown-none-elf`. `cfg(...)` keys of `[target]` tables don't count.
+fn is_bare_metal_triple(triple: &str) -> bool {
+    !triple.starts_with("cfg(") && triple.split('-').skip(1).any(|part| part == "none")
+}
+
+/// Dependencies of workspace members that also appear in the root's `[workspace.dependencies]`
+/// but with a different version requirement, as `{member, dep, workspace_version,
+/// member_version}`. Dependencies inheriting with `workspace = true` are consistent.
+pub fn find_workspace_inconsistencies(
+    root: &toml::Value,
+    members: &[WorkspaceMember],
+) -> Vec<Value> {
+    let version = |spec: &toml::Value| -> Option<String> {
+        let version = match spec {
+            toml::Value::String(version) => version.as_str(),
+            spec => spec.get("version")?.as_str()?,
+        };
+        Some(version.trim_start_matches('^').to_string())
+    };
+    let Some(workspace_deps) = root
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table())
+    else {
+        return Vec::new();
+    };
+
+    let mut inconsistencies = Vec::new();
+    for member in members {
+        let targets = member
+            .manifest
+            .get("target")
+            .and_then(|t| t.as_table())
+            .into_iter()
+            .flat_map(|targets| targets.values());
+        for scope in std::iter::once(&member.manifest).chain(targets) {
+            for table_name in DEPENDENCY_TABLES {
+                let Some(table) = scope.get(*table_name).and_then(|t| t.as_table()) else {
+                    continue;
+                };
+                for (dep, spec) in table {
+                    let Some(workspace_version) = workspace_deps.get(dep).and_then(version) else {
+                        continue;
+                    };
+                    if spec.get("workspace").is_some() {
+                        continue;
+                    }
+                    let Some(member_version) = version(spec) else {
+                        continue;
+                    };
+                    let inconsistency = json!({
+                        "member": member.name,
+                        "dep": dep,
+                        "workspace_version": workspace_version,
+                        "member_version": member_version
+                    });
+                    if !same_requirement(&member_version, &workspace_version)
+                        && !inconsistencies.contains(&inconsistency)
+                    {
+                        inconsistencies.push(inconsistency);
+                    }
                 }
             }
         }
+    }
+    inconsistencies
+}
+
+/// Check every package × target combination listed in a targets file. fmt doesn't depend on the
+/// target, so it runs once per package and is repeated in that package's rows.
+fn run_matrix(
+    path: &Path,
+    no_fmt: bool,
+    no_clippy: bool,
+    no_check: bool,
+    json_output: bool,
+) -> Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
         Err(e) => {
-            all_passed = false;
-            results.push(json!({
-                "command": "cargo check",
-                "success": false,
-                "error": e.to_string()
-            }));
+            if json_output {
+                output_json(&json!({
+                    "error": format!("Failed to read {}", path.display()),
+                    "details": e.to_string()
+                }));
+            } else {
+                output_text(&format!("❌ Failed to read {}: {}", path.display(), e));
+            }
+            return Err(anyhow!("Failed to read {}: {}", path.display(), e));
+        }
+    };
+    let (packages, targets) = parse_targets_file(&content);
+
+    // No packages means the whole workspace, no targets means the host
+    let packages: Vec<Option<&str>> = if packages.is_empty() {
+        vec![None]
+    } else {
+        packages.iter().map(|p| Some(p.as_str())).collect()
+    };
+    let targets: Vec<Option<&str>> = if targets.is_empty() {
+        vec![None]
+    } else {
+        targets.iter().map(|t| Some(t.as_str())).collect()
+    };
+
+    let mut matrix = Vec::new();
+    for package in &packages {
+        let mut package_args = Vec::new();
+        if let Some(package) = package {
+            package_args.extend(["-p", package]);
+        }
+        let fmt = (!no_fmt).then(|| {
+            info!(
+                "Running cargo fmt --check for {}...",
+                package.unwrap_or("workspace")
+            );
+            let args = [["fmt", "--check"].as_slice(), &package_args].concat();
+            let success = run_command("cargo", &args).is_ok_and(|o| o.status.success());
+            json!({ "success": success })
+        });
 
-            if !json_output {
-                error!("❌ Failed to run cargo check: {}", e);
+        for target in &targets {
+            let mut args = package_args.clone();
+            if let Some(target) = target {
+                args.extend(["--target", target]);
             }
+            info!(
+                "Checking {} for {}...",
+                package.unwrap_or("workspace"),
+                target.unwrap_or("host")
+            );
+
+            let mut checks = serde_json::Map::new();
+            if let Some(fmt) = &fmt {
+                checks.insert("fmt".to_string(), fmt.clone());
+            }
+            if !no_clippy {
+                let args = [["clippy"].as_slice(), &args, &["--", "-D", "warnings"]].concat();
+                let output = run_command("cargo", &args);
+                checks.insert(
+                    "clippy".to_string(),
+                    json!({
+                        "success": output.as_ref().is_ok_and(|o| o.status.success()),
+                        "warnings": output
+                            .as_ref()
+                            .map(|o| count_clippy_warnings(&String::from_utf8_lossy(&o.stderr)))
+                            .unwrap_or(0)
+                    }),
+                );
+            }
+            if !no_check {
+                let args = [["check"].as_slice(), &args].concat();
+                let success = run_command("cargo", &args).is_ok_and(|o| o.status.success());
+                checks.insert("check".to_string(), json!({ "success": success }));
+            }
+
+            matrix.push(json!({
+                "package": package,
+                "target": target,
+                "checks": checks
+            }));
         }
     }
 
+    let all_passed = matrix.iter().all(|row| {
+        row["checks"]
+            .as_object()
+            .is_some_and(|checks| checks.values().all(|check| check["success"] == true))
+    });
+
     if json_output {
         output_json(&json!({
             "success": all_passed,
-            "results": results
+            "matrix": matrix
         }));
-    } else if all_passed {
+    } else {
+        print_matrix(&matrix, all_passed);
+    }
+
+    if !all_passed {
+        return Err(anyhow!("Some checks failed"));
+    }
+
+    Ok(())
+}
+
+fn print_matrix(matrix: &[Value], all_passed: bool) {
+    let package_width = matrix
+        .iter()
+        .filter_map(|row| row["package"].as_str())
+        .map(str::len)
+        .chain(["workspace".len()])
+        .max()
+        .unwrap_or(0);
+    let target_width = matrix
+        .iter()
+        .filter_map(|row| row["target"].as_str())
+        .map(str::len)
+        .chain(["host".len()])
+        .max()
+        .unwrap_or(0);
+
+    output_text("🧮 Check Matrix");
+    output_text("===============");
+    output_text(&format!(
+        "{:<package_width$}  {:<target_width$}  fmt  clippy  check",
+        "package", "target"
+    ));
+    for row in matrix {
+        let status = |name: &str| match row["checks"][name]["success"].as_bool() {
+            Some(true) => "✅",
+            Some(false) => "❌",
+            None => "➖",
+        };
+        output_text(&format!(
+            "{:<package_width$}  {:<target_width$}  {}   {}      {}",
+            row["package"].as_str().unwrap_or("workspace"),
+            row["target"].as_str().unwrap_or("host"),
+            status("fmt"),
+            status("clippy"),
+            status("check")
+        ));
+    }
+
+    if all_passed {
         output_text("\n🎉 All checks passed!");
     } else {
         output_text("\n💥 Some checks failed!");
     }
+}
+
+/// Packages and target triples from a targets file: one package name or `--target <triple>`
+/// per line, with blank lines and `#` comments ignored.
+pub fn parse_targets_file(content: &str) -> (Vec<String>, Vec<String>) {
+    let mut packages = Vec::new();
+    let mut targets = Vec::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let target = line
+            .strip_prefix("--target")
+            .map(|rest| rest.trim_start_matches('=').trim())
+            .filter(|triple| !triple.is_empty());
+        match target {
+            Some(triple) => targets.push(triple.to_string()),
+            None => packages.push(line.to_string()),
+        }
+    }
+    (packages, targets)
+}
+
+/// Number of changed or untracked files in the working tree, or `None` outside a git repo.
+fn count_dirty_files() -> Option<usize> {
+    let output = run_command("git", &["status", "--porcelain"]).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            // Our own run history shouldn't make every later run look dirty
+            .filter(|line| {
+                !line.get(3..).is_some_and(|path| {
+                    path.strip_prefix(HISTORY_DIR)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+            })
+            .count(),
+    )
+}
+
+/// Run `cargo +nightly check`, installing nightly first if the user agrees (or passed
+/// `--yes`). JSON mode never prompts, since stdout is reserved for the report.
+fn check_nightly(yes: bool, json_output: bool) -> Value {
+    let installed = run_command("rustup", &["toolchain", "list"])
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line.starts_with("nightly"))
+        })
+        .unwrap_or(false);
+
+    if !installed {
+        let install = yes
+            || (!json_output
+                && confirm("The nightly toolchain is not installed. Install it with rustup?"));
+        if !install {
+            return json!({
+                "command": "cargo +nightly check",
+                "skipped": true,
+                "reason": "nightly toolchain not installed (pass --yes to install it)"
+            });
+        }
+
+        info!("Installing the nightly toolchain...");
+        let args = ["toolchain", "install", "nightly", "--profile", "minimal"];
+        let installed = if json_output {
+            run_command("rustup", &args).map(|output| output.status.success())
+        } else {
+            run_command_interactive("rustup", &args).map(|status| status.success())
+        };
+        if !installed.unwrap_or(false) {
+            return json!({
+                "command": "cargo +nightly check",
+                "skipped": true,
+                "reason": "rustup toolchain install nightly failed"
+            });
+        }
+    }
+
+    info!("Running cargo +nightly check...");
+    let started = Instant::now();
+    match run_command_in(
+        "cargo",
+        &["+nightly", "check"],
+        Path::new("."),
+        &[("CARGO_TARGET_DIR", OsStr::new(NIGHTLY_TARGET_DIR))],
+    ) {
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            json!({
+                "command": "cargo +nightly check",
+                "success": output.status.success(),
+                "warnings": count_clippy_warnings(&stderr),
+                "duration": format_duration(started.elapsed()),
+                "stderr": stderr
+            })
+        }
+        Err(e) => json!({
+            "command": "cargo +nightly check",
+            "success": false,
+            "error": e.to_string()
+        }),
+    }
+}
+
+/// A 0-100 score from the fmt, clippy (`(passed, warnings)`) and check results, weighted
+/// by `[check.scoring]`. Each warning costs clippy some points, and a clippy failure without
+/// warnings (a compile error) costs all of them. Skipped steps are left out of the total, so
+/// `None` means nothing was scored.
+pub fn quality_score(
+    fmt_passed: Option<bool>,
+    clippy: Option<(bool, usize)>,
+    check_passed: Option<bool>,
+    weights: &ScoringConfig,
+) -> Option<u32> {
+    let mut earned = 0;
+    let mut possible = 0;
+    let mut add = |weight: u32, points: Option<u32>| {
+        if let Some(points) = points {
+            earned += points.min(weight);
+            possible += weight;
+        }
+    };
+
+    add(
+        weights.fmt_weight,
+        fmt_passed.map(|passed| if passed { weights.fmt_weight } else { 0 }),
+    );
+    add(
+        weights.clippy_weight,
+        clippy.map(|(passed, warnings)| {
+            if !passed && warnings == 0 {
+                return 0;
+            }
+            let penalty = weights
+                .clippy_warning_penalty
+                .saturating_mul(warnings as u32);
+            weights.clippy_weight.saturating_sub(penalty)
+        }),
+    );
+    add(
+        weights.check_weight,
+        check_passed.map(|passed| if passed { weights.check_weight } else { 0 }),
+    );
+
+    (possible > 0).then(|| ((earned as f64 * 100.0 / possible as f64).round()) as u32)
+}
+
+/// Lint diagnostics in clippy's `--message-format=json` output as `{lint, file, line,
+/// message}`, where the lint is the diagnostic's code, e.g. `clippy::needless_return`.
+/// Diagnostics reported once per target, like for a lib and bin sharing modules, are listed
+/// once.
+pub fn clippy_lint_warnings(messages: &[Value]) -> Vec<Value> {
+    let mut warnings = Vec::new();
+    for message in messages {
+        let Some(lint) = lint_name(message) else {
+            continue;
+        };
+        let diagnostic = &message["message"];
+        let span = diagnostic["spans"]
+            .as_array()
+            .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true));
+        let warning = json!({
+            "lint": lint,
+            "file": span.and_then(|span| span["file_name"].as_str()),
+            "line": span.and_then(|span| span["line_start"].as_u64()),
+            "message": diagnostic["message"].as_str().unwrap_or("")
+        });
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+    warnings
+}
+
+/// The lint a compiler message is for; rustc error codes like E0308 aren't lints.
+fn lint_name(message: &Value) -> Option<&str> {
+    let code = message["message"]["code"]["code"].as_str()?;
+    let is_error_code =
+        code.len() == 5 && code.starts_with('E') && code[1..].chars().all(|c| c.is_ascii_digit());
+    (!is_error_code).then_some(code)
+}
+
+/// Number of lint diagnostics in clippy's stderr, taken from cargo's per-crate summary lines
+/// (`generated N warnings`, or `due to N previous errors` when they were denied).
+pub fn count_clippy_warnings(stderr: &str) -> usize {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let count = if line.starts_with("warning: `") {
+                line.split(" generated ").nth(1)?
+            } else if line.starts_with("error: could not compile") {
+                line.split(" due to ").nth(1)?
+            } else {
+                return None;
+            };
+            count.split_whitespace().next()?.parse::<usize>().ok()
+        })
+        .sum()
+}
+
+pub async fn show_history(limit: Option<usize>, json_output: bool) -> Result<()> {
+    let history = CheckHistory::load()?;
+    let runs = &history.runs[history
+        .runs
+        .len()
+        .saturating_sub(limit.unwrap_or(usize::MAX))..];
+
+    if json_output {
+        output_json(&json!({
+            "path": CheckHistory::path(),
+            "runs": runs,
+            "pass_rate": pass_rate(runs)
+        }));
+        return Ok(());
+    }
+
+    output_text("📈 Check History");
+    output_text("================");
+
+    if runs.is_empty() {
+        output_text("No recorded runs yet - they are saved each time `oxy check` runs");
+        return Ok(());
+    }
+
+    let passes: String = runs
+        .iter()
+        .map(|run| if run.passed() { '✓' } else { '✗' })
+        .collect();
+    let warnings: Vec<u64> = runs
+        .iter()
+        .map(|run| run.clippy_warnings.unwrap_or(0) as u64)
+        .collect();
+    let durations: Vec<u64> = runs.iter().map(|run| run.total_duration_ms).collect();
+
+    output_text(&format!(
+        "Runs: {}  Pass rate: {:.0}%",
+        runs.len(),
+        pass_rate(runs).unwrap_or(0.0) * 100.0
+    ));
+    output_text("");
+    output_text(&format!("Result    {}", passes));
+    output_text(&format!(
+        "Warnings  {}  (latest {})",
+        sparkline(&warnings),
+        warnings.last().unwrap_or(&0)
+    ));
+    output_text(&format!(
+        "Duration  {}  (latest {})",
+        sparkline(&durations),
+        format_duration(Duration::from_millis(*durations.last().unwrap_or(&0)))
+    ));
+
+    if let Some(last) = runs.last() {
+        output_text("");
+        output_text(&format!(
+            "Last run: {} at commit {}",
+            if last.passed() { "passed" } else { "failed" },
+            last.git_hash.as_deref().unwrap_or("unknown")
+        ));
+    }
 
     Ok(())
 }
+
+fn pass_rate(runs: &[CheckRun]) -> Option<f64> {
+    if runs.is_empty() {
+        return None;
+    }
+    Some(runs.iter().filter(|run| run.passed()).count() as f64 / runs.len() as f64)
+}
+
+/// Extract unused dependencies from `cargo machete` output, which lists them as
+/// tab-indented lines under a `<crate> -- <manifest>:` header.
+pub fn parse_machete_output(stdout: &str) -> Vec<Value> {
+    let mut unused = Vec::new();
+    let mut current: Option<(&str, &str)> = None;
+
+    for line in stdout.lines() {
+        if let Some(dependency) = line.strip_prefix('\t') {
+            if let Some((crate_name, manifest)) = current {
+                unused.push(json!({
+                    "crate": crate_name,
+                    "manifest": manifest,
+                    "dependency": dependency.trim()
+                }));
+            }
+        } else if let Some((crate_name, manifest)) = line
+            .strip_suffix(':')
+            .and_then(|header| header.split_once(" -- "))
+        {
+            current = Some((crate_name.trim(), manifest.trim()));
+        } else {
+            current = None;
+        }
+    }
+
+    unused
+}
+
+/// The `use` declarations in Rust source, in order and with whitespace removed, so that
+/// rewrapping a declaration doesn't count as changing it.
+pub fn use_statements(source: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current: Option<String> = None;
+    for line in source.lines() {
+        let line = line.trim();
+        // Skip a visibility such as `pub` or `pub(crate)`
+        let item = match line.strip_prefix("pub") {
+            Some(rest) => match rest.strip_prefix('(') {
+                Some(rest) => rest.split_once(')').map_or("", |(_, rest)| rest),
+                None => rest,
+            }
+            .trim_start(),
+            None => line,
+        };
+        if current.is_none() && !item.starts_with("use ") {
+            continue;
+        }
+        let statement = current.get_or_insert_with(String::new);
+        statement.extend(line.split_whitespace());
+        if line.ends_with(';') {
+            statements.extend(current.take());
+        }
+    }
+    statements
+}
+
+/// Run `cargo fmt` with the import options and report which files had their imports rewritten.
+/// The options are unstable and stable rustfmt silently formats without them, so nothing runs
+/// unless a nightly rustfmt is available. rustfmt can't be limited to imports, so files where it
+/// only changed ordinary formatting are put back as they were.
+fn fix_imports() -> Value {
+    let rustup_nightly = run_command("rustup", &["run", "nightly", "rustfmt", "--version"])
+        .is_ok_and(|output| output.status.success());
+    let default_nightly = !rustup_nightly
+        && run_command("rustfmt", &["--version"]).is_ok_and(|output| {
+            output.status.success() && String::from_utf8_lossy(&output.stdout).contains("nightly")
+        });
+    if !rustup_nightly && !default_nightly {
+        return json!({
+            "command": "cargo fmt (imports)",
+            "success": false,
+            "error": "Import reorganization needs a nightly rustfmt",
+            "suggestion": "Install with: rustup toolchain install nightly --component rustfmt"
+        });
+    }
+
+    let mut args = Vec::new();
+    if rustup_nightly {
+        args.push("+nightly");
+    }
+    args.extend(["fmt", "--", "--config", IMPORT_CONFIG]);
+
+    let rust_files = find_files(Path::new("."), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    });
+    let before: Vec<Option<String>> = rust_files
+        .iter()
+        .map(|path| std::fs::read_to_string(path).ok())
+        .collect();
+
+    let (output, duration) = match run_command_with_timing("cargo", &args) {
+        Ok(result) => result,
+        Err(e) => {
+            return json!({
+                "command": "cargo fmt (imports)",
+                "success": false,
+                "error": e.to_string()
+            });
+        }
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        return json!({
+            "command": "cargo fmt (imports)",
+            "success": false,
+            "duration": format_duration(duration),
+            "error": stderr.trim()
+        });
+    }
+    let mut files_changed = Vec::new();
+    for (path, before) in rust_files.iter().zip(before) {
+        let (Some(before), Ok(after)) = (before, std::fs::read_to_string(path)) else {
+            continue;
+        };
+        if use_statements(&before) != use_statements(&after) {
+            files_changed.push(path.strip_prefix(".").unwrap_or(path).display().to_string());
+        } else if before != after
+            && let Err(e) = std::fs::write(path, &before)
+        {
+            warn!("Failed to restore {}: {}", path.display(), e);
+        }
+    }
+
+    json!({
+        "command": "cargo fmt (imports)",
+        "success": true,
+        "duration": format_duration(duration),
+        "files_changed": files_changed
+    })
+}
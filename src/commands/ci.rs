@@ -0,0 +1,153 @@
+use crate::utils::{confirm, is_rust_project, output_json, output_text};
+use crate::{CiAction, CiPlatform};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::path::Path;
+use tracing::info;
+
+const WORKFLOW_PATH: &str = ".github/workflows/oxy.yml";
+
+/// Caches the cargo home (registry, git checkouts and installed tools like oxy itself)
+/// and the target directory, keyed on the lockfile.
+const CACHE_STEP: &str = r#"      - uses: actions/cache@v4
+        with:
+          path: |
+            ~/.cargo/bin/
+            ~/.cargo/registry/index/
+            ~/.cargo/registry/cache/
+            ~/.cargo/git/db/
+            target/
+          key: ${{ runner.os }}-cargo-${{ hashFiles('**/Cargo.lock') }}
+          restore-keys: ${{ runner.os }}-cargo-
+"#;
+
+/// The crate's binary is called `oxygen`, so it is copied to `oxy` after installing.
+const INSTALL_OXY_STEP: &str = r#"      - name: Install oxy
+        shell: bash
+        run: |
+          cargo install --git https://github.com/ghostkellz/oxygen --locked
+          exe="${{ runner.os == 'Windows' && '.exe' || '' }}"
+          cp -f ~/.cargo/bin/oxygen$exe ~/.cargo/bin/oxy$exe
+"#;
+
+pub async fn run(action: CiAction, json_output: bool) -> Result<()> {
+    match action {
+        CiAction::Generate {
+            platform,
+            bench,
+            force,
+        } => generate_workflow(platform, bench, force, json_output).await,
+    }
+}
+
+async fn generate_workflow(
+    platform: CiPlatform,
+    bench: bool,
+    force: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let path = Path::new(WORKFLOW_PATH);
+    if path.exists() && !force {
+        let confirmed =
+            !json_output && confirm(&format!("{} already exists. Overwrite it?", WORKFLOW_PATH));
+        if !confirmed {
+            if json_output {
+                output_json(&json!({
+                    "error": format!("{} already exists", WORKFLOW_PATH),
+                    "suggestion": "Use --force to overwrite it"
+                }));
+            } else {
+                output_text(&format!("❌ Kept existing {}", WORKFLOW_PATH));
+                output_text("💡 Use --force to overwrite it");
+            }
+            return Err(anyhow!("{} already exists", WORKFLOW_PATH));
+        }
+    }
+
+    let runners = runners(platform);
+    info!("Writing {} for {}", WORKFLOW_PATH, runners.join(", "));
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    std::fs::write(path, ci_workflow(&runners, bench))
+        .with_context(|| format!("Failed to write {}", WORKFLOW_PATH))?;
+
+    if json_output {
+        output_json(&json!({
+            "written_to": WORKFLOW_PATH,
+            "runners": runners,
+            "bench": bench
+        }));
+    } else {
+        output_text(&format!("✅ Wrote {}", WORKFLOW_PATH));
+        output_text(&format!("   Platforms: {}", runners.join(", ")));
+        output_text("   Jobs: check + test on each platform, dependency audit");
+        if bench {
+            output_text("   Benchmarks run every Monday and on manual dispatch");
+        }
+        output_text("💡 Commit the workflow to enable it on GitHub");
+    }
+
+    Ok(())
+}
+
+fn runners(platform: CiPlatform) -> Vec<&'static str> {
+    match platform {
+        CiPlatform::Linux => vec!["ubuntu-latest"],
+        CiPlatform::Macos => vec!["macos-latest"],
+        CiPlatform::Windows => vec!["windows-latest"],
+        CiPlatform::All => vec!["ubuntu-latest", "macos-latest", "windows-latest"],
+    }
+}
+
+/// A GitHub Actions workflow that checks and tests on each runner and audits dependencies.
+/// With `bench`, benchmarks run on a weekly schedule, which skips the other jobs.
+pub fn ci_workflow(runners: &[&str], bench: bool) -> String {
+    let mut workflow =
+        String::from("name: oxy\n\non:\n  push:\n    branches: [main]\n  pull_request:\n");
+    if bench {
+        workflow.push_str("  schedule:\n    - cron: \"0 4 * * 1\"\n  workflow_dispatch:\n");
+    }
+    workflow.push_str("\nenv:\n  CARGO_TERM_COLOR: always\n\njobs:\n");
+
+    let on_push = if bench {
+        "    if: github.event_name != 'schedule'\n"
+    } else {
+        ""
+    };
+
+    workflow.push_str(&format!(
+        "  check:\n    name: Check and test (${{{{ matrix.os }}}})\n{}    runs-on: ${{{{ matrix.os }}}}\n    strategy:\n      fail-fast: false\n      matrix:\n        os: [{}]\n    steps:\n      - uses: actions/checkout@v4\n      - uses: dtolnay/rust-toolchain@stable\n        with:\n          components: clippy, rustfmt\n{}{}      - name: oxy check\n        run: oxy check\n      - name: Test\n        run: cargo test --workspace\n",
+        on_push,
+        runners.join(", "),
+        CACHE_STEP,
+        INSTALL_OXY_STEP
+    ));
+
+    workflow.push_str(&format!(
+        "\n  audit:\n    name: Dependency audit\n{}    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n      - uses: dtolnay/rust-toolchain@stable\n{}{}      - name: Install cargo-audit\n        run: cargo install cargo-audit --locked\n      - name: oxy deps audit\n        run: oxy deps audit\n",
+        on_push, CACHE_STEP, INSTALL_OXY_STEP
+    ));
+
+    if bench {
+        workflow.push_str(&format!(
+            "\n  bench:\n    name: Benchmarks\n    if: github.event_name == 'schedule' || github.event_name == 'workflow_dispatch'\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n      - uses: dtolnay/rust-toolchain@stable\n{}      - name: Benchmarks\n        run: cargo bench --workspace\n",
+            CACHE_STEP
+        ));
+    }
+
+    workflow
+}
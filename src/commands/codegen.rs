@@ -0,0 +1,158 @@
+use crate::commands::deps::package_name;
+use crate::utils::{
+    cargo_messages, cargo_subcommand_installed, count_code_lines, count_rust_loc, is_rust_project,
+    output_json, output_text, run_command,
+};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const EXPANDED_PATH: &str = "target/expanded.rs";
+
+pub async fn run(expand: bool, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let expand_installed = cargo_subcommand_installed("expand");
+    if expand && !expand_installed {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo-expand not installed",
+                "suggestion": "cargo install cargo-expand"
+            }));
+        } else {
+            output_text("❌ cargo-expand not installed");
+            output_text("💡 Install with: cargo install cargo-expand");
+        }
+        return Err(anyhow!("cargo-expand not installed"));
+    }
+
+    // `cargo check` runs every build script, and cargo reports where each one wrote its output
+    info!("Running build scripts with cargo check...");
+    let output = run_command("cargo", &["check", "--message-format=json"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo check failed",
+                "details": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo check failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo check failed"));
+    }
+
+    let mut build_scripts: Vec<(String, PathBuf, usize)> =
+        cargo_messages(&output, "build-script-executed")
+            .iter()
+            .filter_map(|message| {
+                let name = package_name(message["package_id"].as_str()?);
+                let out_dir = PathBuf::from(message["out_dir"].as_str()?);
+                let lines = count_rust_loc(&out_dir);
+                Some((name, out_dir, lines))
+            })
+            .collect();
+    build_scripts.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    let build_script_lines: usize = build_scripts.iter().map(|(_, _, lines)| lines).sum();
+
+    // Macro expansion of the local crate, compared with its hand-written source
+    let expanded = if expand_installed {
+        info!("Expanding macros with cargo expand...");
+        let mut args = vec!["expand"];
+        if Path::new("src/lib.rs").exists() {
+            args.push("--lib");
+        }
+        run_command("cargo", &args)
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    };
+    let expanded_lines = expanded.as_deref().map(count_code_lines);
+    let macro_generated_lines =
+        expanded_lines.map(|lines| lines.saturating_sub(count_rust_loc(Path::new("src"))));
+
+    if expand && let Some(expanded) = &expanded {
+        std::fs::create_dir_all("target").context("Failed to create target directory")?;
+        std::fs::write(EXPANDED_PATH, expanded)
+            .with_context(|| format!("Failed to write {}", EXPANDED_PATH))?;
+    }
+
+    let total_generated_lines = build_script_lines + macro_generated_lines.unwrap_or(0);
+
+    if json_output {
+        let scripts: Vec<_> = build_scripts
+            .iter()
+            .map(|(name, out_dir, lines)| {
+                json!({
+                    "crate": name,
+                    "out_dir": out_dir,
+                    "generated_lines": lines
+                })
+            })
+            .collect();
+        output_json(&json!({
+            "build_scripts": scripts,
+            "expanded_lines": expanded_lines,
+            "macro_generated_lines": macro_generated_lines,
+            "total_generated_lines": total_generated_lines,
+            "expanded_to": (expand && expanded.is_some()).then_some(EXPANDED_PATH)
+        }));
+        return Ok(());
+    }
+
+    output_text("⚙️  Generated Code");
+    output_text("=================");
+    if build_scripts.is_empty() {
+        output_text("No build scripts ran");
+    } else {
+        output_text(&format!("🔨 {} build script(s):", build_scripts.len()));
+        let width = build_scripts
+            .iter()
+            .map(|(name, _, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, _, lines) in &build_scripts {
+            output_text(&format!(
+                "  {:<width$}  {:>7} line(s)",
+                name,
+                lines,
+                width = width
+            ));
+        }
+    }
+
+    output_text("");
+    match (expanded_lines, macro_generated_lines) {
+        (Some(expanded_lines), Some(generated)) => output_text(&format!(
+            "🧩 Macros expand the crate to {} line(s), {} more than its source",
+            expanded_lines, generated
+        )),
+        _ if expand_installed => output_text("⚠️  cargo expand failed, macro output not counted"),
+        _ => output_text(
+            "💡 Install cargo-expand to count macro-generated code: cargo install cargo-expand",
+        ),
+    }
+    output_text(&format!(
+        "📊 {} generated line(s) in total",
+        total_generated_lines
+    ));
+    if expand && expanded.is_some() {
+        output_text(&format!("📄 Expanded source written to {}", EXPANDED_PATH));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use crate::CompletionsAction;
+use crate::utils::{output_json, output_text};
+use anyhow::{Context, Result, anyhow};
+use clap_complete::Shell;
+use serde_json::json;
+use std::hash::{DefaultHasher, Hasher};
+use std::path::PathBuf;
+use tracing::info;
+
+pub async fn run(
+    action: Option<CompletionsAction>,
+    shell: Option<Shell>,
+    mut cmd: clap::Command,
+    json_output: bool,
+) -> Result<()> {
+    match action {
+        Some(CompletionsAction::Install { shell }) => {
+            install_completions(shell, &mut cmd, json_output).await
+        }
+        Some(CompletionsAction::Check { shell }) => {
+            check_completions(shell, &mut cmd, json_output).await
+        }
+        None => {
+            let Some(shell) = shell.or_else(Shell::from_env) else {
+                return Err(anyhow!(
+                    "Could not detect your shell; pass one explicitly, e.g. `oxy completions bash`"
+                ));
+            };
+            let name = cmd.get_name().to_string();
+            clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+            Ok(())
+        }
+    }
+}
+
+async fn install_completions(
+    shell: Option<Shell>,
+    cmd: &mut clap::Command,
+    json_output: bool,
+) -> Result<()> {
+    let Some(shell) = shell.or_else(Shell::from_env) else {
+        if json_output {
+            output_json(&json!({
+                "error": "Could not detect shell from $SHELL",
+                "suggestion": "Pass one explicitly with --shell"
+            }));
+        } else {
+            output_text("❌ Could not detect your shell from $SHELL");
+            output_text("💡 Pass one explicitly, e.g. oxy completions install --shell zsh");
+        }
+        return Err(anyhow!("Could not detect shell"));
+    };
+
+    let Some((path, activation_command)) = install_location(shell) else {
+        if json_output {
+            output_json(&json!({
+                "error": "Automatic install is not supported for this shell",
+                "shell": shell.to_string(),
+                "suggestion": format!("Redirect `oxy completions {}` into your shell's config", shell)
+            }));
+        } else {
+            output_text(&format!(
+                "❌ Automatic install is not supported for {}",
+                shell
+            ));
+            output_text(&format!(
+                "💡 Redirect `oxy completions {}` into your shell's config instead",
+                shell
+            ));
+        }
+        return Err(anyhow!("Unsupported shell for install: {}", shell));
+    };
+
+    info!("Installing {} completions to {}", shell, path.display());
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&path, completion_script(shell, cmd))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    if json_output {
+        output_json(&json!({
+            "shell": shell.to_string(),
+            "installed_to": path,
+            "activation_command": activation_command
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Installed {} completions to {}",
+            shell,
+            path.display()
+        ));
+        output_text("💡 Activate them in this session with:");
+        output_text(&format!("   {}", activation_command));
+    }
+
+    Ok(())
+}
+
+async fn check_completions(
+    shell: Option<Shell>,
+    cmd: &mut clap::Command,
+    json_output: bool,
+) -> Result<()> {
+    let Some(shell) = shell.or_else(Shell::from_env) else {
+        if json_output {
+            output_json(&json!({
+                "error": "Could not detect shell from $SHELL",
+                "suggestion": "Pass one explicitly with --shell"
+            }));
+        } else {
+            output_text("❌ Could not detect your shell from $SHELL");
+            output_text("💡 Pass one explicitly, e.g. oxy completions check --shell zsh");
+        }
+        return Err(anyhow!("Could not detect shell"));
+    };
+    let Some((path, _)) = install_location(shell) else {
+        if json_output {
+            output_json(&json!({
+                "error": "oxy doesn't install completions for this shell",
+                "shell": shell.to_string()
+            }));
+        } else {
+            output_text(&format!(
+                "❌ oxy doesn't install completions for {}, so there is nothing to check",
+                shell
+            ));
+        }
+        return Err(anyhow!("Unsupported shell for check: {}", shell));
+    };
+
+    info!(
+        "Comparing {} with the current {} completions",
+        path.display(),
+        shell
+    );
+    let current = completion_script(shell, cmd);
+    let installed = std::fs::read(&path).ok();
+    let up_to_date = completions_up_to_date(installed.as_deref(), &current);
+    let current_hash = script_hash(&current);
+    let installed_hash = installed.as_deref().map(script_hash);
+
+    if json_output {
+        output_json(&json!({
+            "shell": shell.to_string(),
+            "up_to_date": up_to_date,
+            "installed_path": path,
+            "installed_hash": installed_hash,
+            "current_hash": current_hash
+        }));
+    } else if up_to_date {
+        output_text(&format!(
+            "✅ {} completions are up to date ({})",
+            shell,
+            path.display()
+        ));
+    } else if installed_hash.is_none() {
+        output_text(&format!(
+            "❌ No {} completions installed at {}",
+            shell,
+            path.display()
+        ));
+        output_text("💡 Install them with: oxy completions install");
+    } else {
+        output_text("❌ Completions out of date, run: oxy completions install");
+    }
+
+    if !up_to_date {
+        return Err(anyhow!("{} completions are not up to date", shell));
+    }
+
+    Ok(())
+}
+
+/// Whether the `installed` completion script, if any, matches the `current` one.
+pub fn completions_up_to_date(installed: Option<&[u8]>, current: &[u8]) -> bool {
+    installed.map(script_hash) == Some(script_hash(current))
+}
+
+/// The completion script for `shell`, as `oxy completions <shell>` prints it.
+pub fn completion_script(shell: Shell, cmd: &mut clap::Command) -> Vec<u8> {
+    let mut script = Vec::new();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, cmd, name, &mut script);
+    script
+}
+
+/// Hex digest used to compare scripts. `DefaultHasher` isn't stable across Rust releases,
+/// which is fine since both sides are hashed by the same binary.
+fn script_hash(script: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(script);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Where each shell looks for user completions, and how to load them right away.
+fn install_location(shell: Shell) -> Option<(PathBuf, String)> {
+    let home = dirs::home_dir()?;
+    match shell {
+        Shell::Bash => {
+            let path = home.join(".bash_completion.d").join("oxy");
+            let activation = format!("source {}", path.display());
+            Some((path, activation))
+        }
+        Shell::Zsh => {
+            let path = home.join(".zfunc").join("_oxy");
+            let activation =
+                "fpath=(~/.zfunc $fpath) && autoload -Uz compinit && compinit".to_string();
+            Some((path, activation))
+        }
+        Shell::Fish => {
+            // fish uses ~/.config on every platform, unlike `dirs::config_dir`
+            let path = home
+                .join(".config")
+                .join("fish")
+                .join("completions")
+                .join("oxy.fish");
+            let activation = format!("source {}", path.display());
+            Some((path, activation))
+        }
+        _ => None,
+    }
+}
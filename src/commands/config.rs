@@ -0,0 +1,145 @@
+use crate::ConfigAction;
+use crate::config::{Config, PROJECT_CONFIG_FILE, unknown_keys};
+use crate::utils::{output_json, output_text};
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+pub async fn run(action: ConfigAction, json_output: bool) -> Result<()> {
+    match action {
+        ConfigAction::Validate => validate_config(json_output).await,
+    }
+}
+
+async fn validate_config(json_output: bool) -> Result<()> {
+    info!("Validating oxygen configuration...");
+
+    let files: Vec<PathBuf> = [Config::config_path()?, PathBuf::from(PROJECT_CONFIG_FILE)]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+
+    let mut errors: Vec<Value> = Vec::new();
+    let mut warnings: Vec<Value> = Vec::new();
+    for path in &files {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: toml::Value = match toml::from_str(&content) {
+            Ok(value) => value,
+            Err(e) => {
+                errors.push(json!({
+                    "file": path,
+                    "message": e.to_string().trim()
+                }));
+                continue;
+            }
+        };
+        match unknown_keys(&value) {
+            Ok(keys) => warnings.extend(keys.into_iter().map(|key| {
+                json!({
+                    "field": key,
+                    "message": "Unknown key, ignored by oxygen",
+                    "file": path
+                })
+            })),
+            Err(e) => errors.push(json!({
+                "file": path,
+                "message": format!("{:#}", e).trim()
+            })),
+        }
+    }
+
+    // Check paths on the merged config, as that is what the other commands use
+    match Config::load() {
+        Ok(config) => warnings.extend(path_warnings(&config)),
+        Err(e) if errors.is_empty() => errors.push(json!({
+            "file": Value::Null,
+            "message": format!("{:#}", e)
+        })),
+        Err(_) => {}
+    }
+    let valid = errors.is_empty();
+
+    if json_output {
+        output_json(&json!({
+            "valid": valid,
+            "files": files,
+            "errors": errors,
+            "warnings": warnings
+        }));
+    } else {
+        output_text("⚙️  Configuration");
+        output_text("=================");
+        if files.is_empty() {
+            output_text("ℹ️  No config files found, using the defaults");
+        }
+        for path in &files {
+            output_text(&format!("📄 {}", path.display()));
+        }
+        output_text("");
+        for error in &errors {
+            output_text(&format!(
+                "❌ {}: {}",
+                error["file"].as_str().unwrap_or("merged config"),
+                error["message"].as_str().unwrap_or("")
+            ));
+        }
+        for warning in &warnings {
+            let file = warning["file"]
+                .as_str()
+                .map(|file| format!(" ({})", file))
+                .unwrap_or_default();
+            output_text(&format!(
+                "⚠️  {}: {}{}",
+                warning["field"].as_str().unwrap_or(""),
+                warning["message"].as_str().unwrap_or(""),
+                file
+            ));
+        }
+        if !valid {
+            output_text(&format!(
+                "❌ Configuration is invalid: {} error(s)",
+                errors.len()
+            ));
+        } else if warnings.is_empty() {
+            output_text("✅ Configuration is valid");
+        } else {
+            output_text(&format!(
+                "✅ Configuration is valid, with {} warning(s)",
+                warnings.len()
+            ));
+        }
+    }
+
+    if !valid {
+        return Err(anyhow!("Invalid oxygen configuration"));
+    }
+
+    Ok(())
+}
+
+/// Warnings for path settings that don't point to an existing directory.
+fn path_warnings(config: &Config) -> Vec<Value> {
+    let paths = config
+        .tools
+        .check_paths
+        .iter()
+        .map(|path| ("tools.check_paths", path.as_path()))
+        .chain(
+            config
+                .build
+                .target_dir
+                .as_deref()
+                .map(|path| ("build.target_dir", path)),
+        );
+    paths
+        .filter(|(_, path)| !Path::new(path).is_dir())
+        .map(|(field, path)| {
+            json!({
+                "field": field,
+                "message": format!("{} is not an existing directory", path.display())
+            })
+        })
+        .collect()
+}
@@ -1,9 +1,14 @@
-use crate::utils::{is_rust_project, output_json, output_text, run_command};
-use crate::DepsAction;
-use anyhow::{Result, anyhow};
+use crate::utils::{
+    DEPENDENCY_TABLES, LockedPackage, cargo_home, cargo_messages, cargo_subcommand_installed,
+    check_yanked, format_bytes, hyperlink, is_rust_project, output_json, output_text,
+    read_lockfile, read_manifest, run_command,
+};
+use crate::{DepsAction, PatchAction};
+use anyhow::{Context, Result, anyhow};
 use serde_json::json;
-use std::collections::HashMap;
-use tracing::info;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 pub async fn run(action: DepsAction, json_output: bool) -> Result<()> {
     if !is_rust_project() {
@@ -19,31 +24,115 @@ pub async fn run(action: DepsAction, json_output: bool) -> Result<()> {
     }
 
     match action {
-        DepsAction::Tree => show_dependency_tree(json_output).await,
-        DepsAction::Outdated => check_outdated_deps(json_output).await,
-        DepsAction::Audit => audit_dependencies(json_output).await,
+        DepsAction::Tree {
+            fail_on_yanked,
+            no_dev,
+            edges,
+            crate_types,
+        } => {
+            let edges = if no_dev {
+                Some("normal".to_string())
+            } else if !crate_types.is_empty() {
+                crate_type_edges(&crate_types)
+            } else {
+                edges
+            };
+            show_dependency_tree(fail_on_yanked, edges.as_deref(), &crate_types, json_output).await
+        }
+        DepsAction::Outdated { workspace: false } => check_outdated_deps(json_output).await,
+        DepsAction::Outdated { workspace: true } => {
+            check_outdated_workspace(json_output).await
+        }
+        DepsAction::Audit { fix: false, .. } => audit_dependencies(json_output).await,
+        DepsAction::Audit { fix: true, dry_run } => {
+            fix_vulnerabilities(dry_run, json_output).await
+        }
         DepsAction::Licenses => show_licenses(json_output).await,
         DepsAction::Size => analyze_dependency_sizes(json_output).await,
+        DepsAction::PinExact => pin_exact_versions(json_output).await,
+        DepsAction::Fetch { offline_after } => fetch_dependencies(offline_after, json_output).await,
+        DepsAction::FetchAllFeatures { feature_sets } => {
+            fetch_all_features(&feature_sets, json_output).await
+        }
+        DepsAction::FeatureMatrix { max_combinations } => {
+            check_feature_matrix(max_combinations, json_output).await
+        }
+        DepsAction::UnsafeMap => show_unsafe_map(json_output).await,
+        DepsAction::CompatCheck { msrv } => check_msrv_compat(msrv, json_output).await,
+        DepsAction::Patch {
+            action: PatchAction::Add { crate_name, path },
+        } => add_patch(&crate_name, &path, json_output).await,
+        DepsAction::Patch {
+            action: PatchAction::Remove { crate_name },
+        } => remove_patch(&crate_name, json_output).await,
+        DepsAction::Patch {
+            action: PatchAction::List,
+        } => list_patches(json_output).await,
+        DepsAction::SupplyChain {
+            crates,
+            publishers,
+            users,
+        } => {
+            // No flags shows every view
+            let all = !(crates || publishers || users);
+            show_supply_chain(all || crates, all || publishers, all || users, json_output).await
+        }
     }
 }
 
-async fn show_dependency_tree(json_output: bool) -> Result<()> {
+async fn show_dependency_tree(
+    fail_on_yanked: bool,
+    edges: Option<&str>,
+    crate_types: &[String],
+    json_output: bool,
+) -> Result<()> {
     info!("Showing dependency tree...");
 
-    match run_command("cargo", &["tree", "--format", "{p} {f}"]) {
+    let mut args = vec!["tree", "--format", "{p} {f}"];
+    if let Some(edges) = edges {
+        args.extend(["--edges", edges]);
+    }
+
+    match run_command("cargo", &args) {
         Ok(output) => {
-            let tree_output = String::from_utf8_lossy(&output.stdout);
+            let mut tree_output = String::from_utf8_lossy(&output.stdout).to_string();
+            if crate_types.iter().any(|t| t == "proc-macro") {
+                tree_output = proc_macro_subtrees(&tree_output);
+            }
+            let yanked = find_yanked_versions(&tree_output);
             
             if json_output {
-                let dependencies = parse_dependency_tree(&tree_output);
+                let dependencies = parse_dependency_tree(&tree_output, &yanked);
                 output_json(&json!({
                     "dependency_tree": dependencies,
+                    "edges": included_edge_kinds(edges),
+                    "edge_filter": (!crate_types.is_empty()).then_some(crate_types),
+                    "yanked_count": yanked.len(),
                     "raw_output": tree_output.trim()
                 }));
             } else {
                 output_text("📦 Dependency Tree");
                 output_text("==================");
-                output_text(&tree_output);
+                if tree_output.trim().is_empty() {
+                    output_text("No dependencies of the selected types");
+                }
+                for line in tree_output.lines() {
+                    if tree_line_package(line).is_some_and(|pkg| yanked.contains(&pkg)) {
+                        output_text(&format!("{} ⚠️ YANKED", line));
+                    } else {
+                        output_text(line);
+                    }
+                }
+
+                if !yanked.is_empty() {
+                    output_text("");
+                    output_text(&format!("⚠️  {} yanked crate version(s) in use", yanked.len()));
+                    output_text("💡 Move off yanked versions with: cargo update -p <crate>");
+                }
+            }
+
+            if fail_on_yanked && !yanked.is_empty() {
+                return Err(anyhow!("Found {} yanked crate version(s)", yanked.len()));
             }
         }
         Err(_) => {
@@ -63,6 +152,134 @@ async fn show_dependency_tree(json_output: bool) -> Result<()> {
     Ok(())
 }
 
+/// The edge kinds `cargo tree --edges <edges>` follows, with cargo's default of every
+/// dependency kind when no `--edges` is given or it only excludes kinds (`no-dev`).
+pub fn included_edge_kinds(edges: Option<&str>) -> Vec<&'static str> {
+    const DEPENDENCY_KINDS: [&str; 3] = ["normal", "build", "dev"];
+
+    let tokens: Vec<&str> = edges
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .collect();
+    let selected: Vec<&'static str> = DEPENDENCY_KINDS
+        .into_iter()
+        .filter(|kind| tokens.contains(kind) || tokens.contains(&"all"))
+        .collect();
+
+    let mut kinds: Vec<&'static str> = if selected.is_empty() {
+        DEPENDENCY_KINDS.to_vec()
+    } else {
+        selected
+    };
+    kinds.retain(|kind| !tokens.contains(&format!("no-{}", kind).as_str()));
+    if tokens.contains(&"features") {
+        kinds.push("features");
+    }
+    kinds
+}
+
+/// The `cargo tree --edges` value for `--crate-types`; `proc-macro` isn't an edge kind, so
+/// it is left to `proc_macro_subtrees` and only the kinds given with it are followed.
+pub fn crate_type_edges(crate_types: &[String]) -> Option<String> {
+    let kinds: Vec<&str> = crate_types
+        .iter()
+        .map(String::as_str)
+        .filter(|kind| *kind != "proc-macro")
+        .collect();
+    (!kinds.is_empty()).then(|| kinds.join(","))
+}
+
+/// The lines of `cargo tree` output for proc-macro crates and everything below them,
+/// plus the lines leading to them so the tree keeps its shape.
+pub fn proc_macro_subtrees(tree_output: &str) -> String {
+    let lines: Vec<&str> = tree_output.lines().collect();
+    let mut keep = vec![false; lines.len()];
+    // Indices of the lines above the current one, one per depth
+    let mut ancestors: Vec<usize> = Vec::new();
+    let mut proc_macro_depth: Option<usize> = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let (depth, entry) = split_tree_prefix(line);
+        if entry.is_empty() {
+            ancestors.clear();
+            proc_macro_depth = None;
+            continue;
+        }
+        ancestors.truncate(depth);
+        if proc_macro_depth.is_some_and(|pm_depth| depth <= pm_depth) {
+            proc_macro_depth = None;
+        }
+        if proc_macro_depth.is_none() && entry.contains(" (proc-macro)") {
+            proc_macro_depth = Some(depth);
+        }
+        if proc_macro_depth.is_some() {
+            keep[i] = true;
+            for &ancestor in &ancestors {
+                keep[ancestor] = true;
+            }
+        }
+        ancestors.push(i);
+    }
+
+    lines
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(line, _)| format!("{}\n", line))
+        .collect()
+}
+
+/// Look up every registry package in the tree on crates.io and return the yanked ones.
+fn find_yanked_versions(tree_output: &str) -> HashSet<(String, String)> {
+    let packages: HashSet<(String, String)> =
+        tree_output.lines().filter_map(tree_line_package).collect();
+    let mut yanked = HashSet::new();
+
+    for (name, version) in packages {
+        match check_yanked(&name, &version) {
+            Ok(true) => {
+                yanked.insert((name, version));
+            }
+            Ok(false) => {}
+            Err(e) => {
+                // Don't hammer an unreachable registry once per crate
+                warn!("Skipping yanked check, crates.io unavailable: {}", e);
+                break;
+            }
+        }
+    }
+
+    yanked
+}
+
+/// Split the tree-drawing prefix (`│   ├── `) off a `cargo tree` line.
+fn split_tree_prefix(line: &str) -> (usize, &str) {
+    let trimmed = line.trim_start_matches(['│', '├', '└', '─', ' ']);
+    let depth = line[..line.len() - trimmed.len()].chars().count() / 4;
+    (depth, trimmed.trim_end())
+}
+
+/// Extract `(name, version)` from a `cargo tree` line for crates that come from a registry.
+fn tree_line_package(line: &str) -> Option<(String, String)> {
+    let (_, entry) = split_tree_prefix(line);
+    let mut parts = entry.split_whitespace();
+    let name = parts.next()?;
+    let version = parts.next()?.strip_prefix('v')?;
+
+    // Path and git dependencies carry their source in parentheses after the version
+    if let Some(source) = parts.next()
+        && source.starts_with('(')
+        && source != "(*)"
+        && source != "(proc-macro)"
+    {
+        return None;
+    }
+
+    Some((name.to_string(), version.to_string()))
+}
+
 async fn check_outdated_deps(json_output: bool) -> Result<()> {
     info!("Checking for outdated dependencies...");
 
@@ -120,15 +337,137 @@ async fn check_outdated_deps(json_output: bool) -> Result<()> {
     Ok(())
 }
 
+async fn check_outdated_workspace(json_output: bool) -> Result<()> {
+    info!("Checking for outdated dependencies across the workspace...");
+
+    if !cargo_subcommand_installed("outdated") {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo outdated not available",
+                "suggestion": "Install with: cargo install cargo-outdated"
+            }));
+        } else {
+            output_text("❌ cargo-outdated not installed");
+            output_text("💡 Install with: cargo install cargo-outdated");
+        }
+        return Ok(());
+    }
+
+    let output = run_command("cargo", &["outdated", "--workspace", "--format", "json"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo outdated failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo outdated failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo outdated failed"));
+    }
+
+    let members = group_outdated_by_member(
+        &String::from_utf8_lossy(&output.stdout),
+        &workspace_member_paths(),
+    );
+
+    if json_output {
+        output_json(&json!({ "members": members }));
+        return Ok(());
+    }
+
+    output_text("📊 Outdated Dependencies");
+    output_text("========================");
+    for member in &members {
+        let name = member["name"].as_str().unwrap_or("unknown");
+        let path = member["path"].as_str().unwrap_or("");
+        output_text("");
+        output_text(&format!("📦 {} ({})", name, path));
+
+        let deps = member["outdated_deps"].as_array().cloned().unwrap_or_default();
+        if deps.is_empty() {
+            output_text("  ✅ All dependencies are up to date!");
+        }
+        for dep in &deps {
+            if let (Some(name), Some(project), Some(compat), Some(latest)) = (
+                dep["name"].as_str(),
+                dep["project"].as_str(),
+                dep["compat"].as_str(),
+                dep["latest"].as_str(),
+            ) {
+                output_text(&format!(
+                    "  {} {} → {} (latest: {})",
+                    name, project, compat, latest
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Workspace member directories relative to the workspace root, keyed by package name.
+fn workspace_member_paths() -> HashMap<String, String> {
+    let Ok(output) = run_command("cargo", &["metadata", "--no-deps", "--format-version", "1"])
+    else {
+        return HashMap::new();
+    };
+    let Ok(metadata) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return HashMap::new();
+    };
+    let root = Path::new(metadata["workspace_root"].as_str().unwrap_or(""));
+
+    metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            let name = package["name"].as_str()?;
+            let dir = Path::new(package["manifest_path"].as_str()?).parent()?;
+            let relative = dir.strip_prefix(root).unwrap_or(dir).display().to_string();
+            let relative = if relative.is_empty() { ".".to_string() } else { relative };
+            Some((name.to_string(), relative))
+        })
+        .collect()
+}
+
+/// `cargo outdated --workspace --format json` prints one JSON object per member; turn them
+/// into `{name, path, outdated_deps}` entries.
+pub fn group_outdated_by_member(
+    stdout: &str,
+    member_paths: &HashMap<String, String>,
+) -> Vec<serde_json::Value> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|report| {
+            let name = report["crate_name"].as_str()?.to_string();
+            Some(json!({
+                "path": member_paths.get(&name),
+                "name": name,
+                "outdated_deps": report["dependencies"].as_array().cloned().unwrap_or_default()
+            }))
+        })
+        .collect()
+}
+
 async fn audit_dependencies(json_output: bool) -> Result<()> {
     info!("Auditing dependencies for security issues...");
 
     match run_command("cargo", &["audit", "--format", "json"]) {
         Ok(output) => {
             let audit_output = String::from_utf8_lossy(&output.stdout);
-            
+            let parsed = serde_json::from_str::<serde_json::Value>(&audit_output)
+                .ok()
+                .map(|mut report| {
+                    annotate_advisory_urls(&mut report);
+                    report
+                });
+
             if json_output {
-                if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&audit_output) {
+                if let Some(parsed) = parsed {
                     output_json(&parsed);
                 } else {
                     output_json(&json!({
@@ -139,13 +478,13 @@ async fn audit_dependencies(json_output: bool) -> Result<()> {
                 output_text("🔒 Security Audit");
                 output_text("================");
                 
-                if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&audit_output) {
-                    if let Some(vulnerabilities) = parsed.get("vulnerabilities").and_then(|v| v.as_array()) {
+                if let Some(mut parsed) = parsed {
+                    if let Some(vulnerabilities) = vulnerability_list(&mut parsed) {
                         if vulnerabilities.is_empty() {
                             output_text("✅ No known security vulnerabilities found!");
                         } else {
                             output_text(&format!("⚠️  Found {} vulnerability(ies):", vulnerabilities.len()));
-                            for vuln in vulnerabilities {
+                            for vuln in vulnerabilities.iter() {
                                 if let (Some(package), Some(advisory)) = (
                                     vuln.get("package").and_then(|p| p.get("name")).and_then(|n| n.as_str()),
                                     vuln.get("advisory")
@@ -153,6 +492,12 @@ async fn audit_dependencies(json_output: bool) -> Result<()> {
                                     let title = advisory.get("title").and_then(|t| t.as_str()).unwrap_or("Unknown");
                                     let severity = advisory.get("severity").and_then(|s| s.as_str()).unwrap_or("Unknown");
                                     output_text(&format!("  {} - {} ({})", package, title, severity));
+                                    if let (Some(id), Some(url)) = (
+                                        advisory.get("id").and_then(|i| i.as_str()),
+                                        vuln.get("url").and_then(|u| u.as_str())
+                                    ) {
+                                        output_text(&format!("    🔗 {}", hyperlink(url, id)));
+                                    }
                                 }
                             }
                         }
@@ -178,6 +523,208 @@ async fn audit_dependencies(json_output: bool) -> Result<()> {
     Ok(())
 }
 
+async fn fix_vulnerabilities(dry_run: bool, json_output: bool) -> Result<()> {
+    info!("Auditing dependencies for fixable vulnerabilities...");
+
+    let audit = || -> Option<serde_json::Value> {
+        let output = run_command("cargo", &["audit", "--format", "json"]).ok()?;
+        serde_json::from_slice(&output.stdout).ok()
+    };
+    let Some(mut report) = audit() else {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo audit not available",
+                "suggestion": "Install with: cargo install cargo-audit --features fix"
+            }));
+        } else {
+            output_text("❌ cargo-audit not installed");
+            output_text("💡 Install with: cargo install cargo-audit --features fix");
+        }
+        return Err(anyhow!("cargo audit not available"));
+    };
+    let vulnerable = vulnerability_list(&mut report).map_or(0, |list| list.len());
+
+    let (fixed, remaining) = if dry_run {
+        let fixable = patchable_vulnerabilities(&mut report);
+        let remaining = vulnerable.saturating_sub(fixable.len());
+        (fixable, remaining)
+    } else {
+        let lock_path = Path::new("Cargo.lock");
+        let before = read_lockfile(lock_path).unwrap_or_default();
+        info!("Running cargo audit fix...");
+        let output = run_command("cargo", &["audit", "fix"])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if json_output {
+                output_json(&json!({
+                    "error": "cargo audit fix failed",
+                    "details": stderr.trim(),
+                    "suggestion": "Install with: cargo install cargo-audit --features fix"
+                }));
+            } else {
+                output_text("❌ cargo audit fix failed");
+                output_text(stderr.trim());
+                output_text("💡 It needs: cargo install cargo-audit --features fix");
+            }
+            return Err(anyhow!("cargo audit fix failed"));
+        }
+        // Re-resolve the lockfile against the raised requirements without updating anything else
+        run_command("cargo", &["update", "--workspace"])?;
+        let after = read_lockfile(lock_path).unwrap_or_default();
+
+        let remaining = audit()
+            .as_mut()
+            .and_then(vulnerability_list)
+            .map_or(vulnerable, |list| list.len());
+        (updated_packages(&before, &after), remaining)
+    };
+
+    if json_output {
+        output_json(&json!({
+            "fixed": fixed,
+            "remaining_vulnerabilities": remaining,
+            "dry_run": dry_run
+        }));
+    } else {
+        output_text("🔒 Security Audit Fix");
+        output_text("=====================");
+        if fixed.is_empty() {
+            output_text("No packages to upgrade");
+        }
+        for package in &fixed {
+            output_text(&format!(
+                "  {} {} {} → {}",
+                if dry_run { "⬆️ " } else { "✅" },
+                package["name"].as_str().unwrap_or(""),
+                package["from_version"].as_str().unwrap_or(""),
+                package["to_version"].as_str().unwrap_or("?")
+            ));
+        }
+        output_text("");
+        if dry_run {
+            output_text(&format!("📊 {} package(s) would be upgraded", fixed.len()));
+        }
+        if remaining == 0 {
+            output_text("✅ No known security vulnerabilities remain");
+        } else {
+            output_text(&format!(
+                "⚠️  {} vulnerability(ies) {}",
+                remaining,
+                if dry_run { "would remain" } else { "remain" }
+            ));
+            output_text("💡 Run `oxy deps audit` for details");
+        }
+    }
+
+    if !dry_run && remaining > 0 {
+        return Err(anyhow!("{} vulnerability(ies) remain after fixing", remaining));
+    }
+
+    Ok(())
+}
+
+/// Vulnerable packages whose advisory lists patched versions, as `{name, from_version,
+/// to_version}` with the patched version requirements as the target.
+fn patchable_vulnerabilities(report: &mut serde_json::Value) -> Vec<serde_json::Value> {
+    let Some(vulnerabilities) = vulnerability_list(report) else {
+        return Vec::new();
+    };
+    vulnerabilities
+        .iter()
+        .filter_map(|vuln| {
+            let patched: Vec<&str> = vuln
+                .get("versions")?
+                .get("patched")?
+                .as_array()?
+                .iter()
+                .filter_map(|v| v.as_str())
+                .collect();
+            if patched.is_empty() {
+                return None;
+            }
+            let package = vuln.get("package")?;
+            Some(json!({
+                "name": package.get("name")?.as_str()?,
+                "from_version": package.get("version")?.as_str()?,
+                "to_version": patched.join(" or ")
+            }))
+        })
+        .collect()
+}
+
+/// Packages whose locked version changed between two lockfiles, as `{name, from_version,
+/// to_version}`. Packages that were only added or removed aren't upgrades.
+pub fn updated_packages(
+    before: &[LockedPackage],
+    after: &[LockedPackage],
+) -> Vec<serde_json::Value> {
+    let versions = |packages: &[LockedPackage], name: &str| -> Vec<String> {
+        packages
+            .iter()
+            .filter(|package| package.name == name)
+            .map(|package| package.version.clone())
+            .collect()
+    };
+    let mut updated = Vec::new();
+    for package in before {
+        let now = versions(after, &package.name);
+        let was = versions(before, &package.name);
+        if now.contains(&package.version) {
+            continue;
+        }
+        if let Some(to_version) = now.iter().find(|version| !was.contains(version)) {
+            updated.push(json!({
+                "name": package.name,
+                "from_version": package.version,
+                "to_version": to_version
+            }));
+        }
+    }
+    updated
+}
+
+/// The vulnerability entries in a `cargo audit --format json` report. Current cargo-audit
+/// nests them under `vulnerabilities.list`; older versions used a bare array.
+fn vulnerability_list(report: &mut serde_json::Value) -> Option<&mut Vec<serde_json::Value>> {
+    let vulnerabilities = report.get_mut("vulnerabilities")?;
+    if vulnerabilities.is_array() {
+        return vulnerabilities.as_array_mut();
+    }
+    vulnerabilities.get_mut("list")?.as_array_mut()
+}
+
+/// Add a RustSec advisory `url` to every vulnerability with a `RUSTSEC-YYYY-NNNN` id.
+pub fn annotate_advisory_urls(report: &mut serde_json::Value) {
+    let Some(vulnerabilities) = vulnerability_list(report) else {
+        return;
+    };
+
+    for vuln in vulnerabilities {
+        let Some(id) = vuln
+            .get("advisory")
+            .and_then(|a| a.get("id"))
+            .and_then(|i| i.as_str())
+            .filter(|id| is_rustsec_id(id))
+            .map(String::from)
+        else {
+            continue;
+        };
+        vuln["url"] = json!(format!("https://rustsec.org/advisories/{}.html", id));
+    }
+}
+
+fn is_rustsec_id(id: &str) -> bool {
+    let mut parts = id.split('-');
+    matches!(
+        (parts.next(), parts.next(), parts.next(), parts.next()),
+        (Some("RUSTSEC"), Some(year), Some(number), None)
+            if year.len() == 4
+                && year.chars().all(|c| c.is_ascii_digit())
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+    )
+}
+
 async fn show_licenses(json_output: bool) -> Result<()> {
     info!("Analyzing dependency licenses...");
 
@@ -188,14 +735,15 @@ async fn show_licenses(json_output: bool) -> Result<()> {
             let mut dependencies = Vec::new();
 
             for line in tree_output.lines() {
-                if let Some((name_version, license)) = line.trim().split_once(' ') {
-                    if !license.is_empty() && license != "N/A" {
-                        *license_counts.entry(license.to_string()).or_insert(0) += 1;
-                        dependencies.push(json!({
-                            "name": name_version,
-                            "license": license
-                        }));
-                    }
+                if let Some((name_version, license)) = line.trim().split_once(' ')
+                    && !license.is_empty()
+                    && license != "N/A"
+                {
+                    *license_counts.entry(license.to_string()).or_insert(0) += 1;
+                    dependencies.push(json!({
+                        "name": name_version,
+                        "license": license
+                    }));
                 }
             }
 
@@ -278,27 +826,1389 @@ async fn analyze_dependency_sizes(json_output: bool) -> Result<()> {
     Ok(())
 }
 
-fn parse_dependency_tree(tree_output: &str) -> Vec<serde_json::Value> {
-    let mut dependencies = Vec::new();
-    
-    for line in tree_output.lines() {
-        let trimmed = line.trim();
-        if !trimmed.is_empty() {
-            let depth = line.len() - trimmed.len();
-            if let Some((name_version, features)) = trimmed.split_once(' ') {
-                dependencies.push(json!({
-                    "name": name_version,
-                    "features": features,
-                    "depth": depth / 4
+async fn show_unsafe_map(json_output: bool) -> Result<()> {
+    info!("Scanning dependencies for unsafe code...");
+
+    if !cargo_subcommand_installed("geiger") {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo geiger not available",
+                "suggestion": "Install with: cargo install cargo-geiger"
+            }));
+        } else {
+            output_text("❌ cargo-geiger not installed");
+            output_text("💡 Install with: cargo install cargo-geiger");
+        }
+        return Ok(());
+    }
+
+    let output = run_command(
+        "cargo",
+        &["geiger", "--all-features", "--output-format", "Json"],
+    )?;
+    // geiger exits non-zero when it finds unsafe code, so only a missing report is a failure
+    let Ok(report) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo geiger failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo geiger failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo geiger failed"));
+    };
+
+    let crates = parse_geiger_report(&report);
+
+    if json_output {
+        output_json(&json!({ "crates": crates }));
+        return Ok(());
+    }
+
+    output_text("☢️  Unsafe Code Map");
+    output_text("==================");
+
+    let by_key: HashMap<String, &serde_json::Value> = crates
+        .iter()
+        .map(|krate| (unsafe_map_key(krate), krate))
+        .collect();
+    let dependents: HashSet<&str> = crates
+        .iter()
+        .flat_map(|krate| krate["dependencies"].as_array().into_iter().flatten())
+        .filter_map(|dep| dep.as_str())
+        .collect();
+    let mut seen = HashSet::new();
+    for root in crates
+        .iter()
+        .filter(|krate| !dependents.contains(unsafe_map_key(krate).as_str()))
+    {
+        print_unsafe_tree(root, &by_key, "", "", &mut seen);
+    }
+
+    let with_unsafe = crates.iter().filter(|krate| krate["unsafe_calls"] != 0).count();
+    output_text("");
+    output_text(&format!(
+        "📊 {} of {} crates use unsafe code",
+        with_unsafe,
+        crates.len()
+    ));
+
+    Ok(())
+}
+
+async fn add_patch(crate_name: &str, path: &Path, json_output: bool) -> Result<()> {
+    if !path.join("Cargo.toml").is_file() {
+        let error = format!("No Cargo.toml found in {}", path.display());
+        if json_output {
+            output_json(&json!({
+                "error": error,
+                "suggestion": "Point the patch at the directory of the crate's checkout"
+            }));
+        } else {
+            output_text(&format!("❌ {}", error));
+            output_text("💡 Point the patch at the directory of the crate's checkout");
+        }
+        return Err(anyhow!(error));
+    }
+
+    let original = std::fs::read_to_string("Cargo.toml")?;
+    let mut manifest: toml_edit::DocumentMut =
+        original.parse().context("Failed to parse Cargo.toml")?;
+    // cargo check re-resolves the lockfile for the patch, so restore that too if it fails
+    let lockfile = workspace_lockfile();
+    let original_lock = std::fs::read(&lockfile).ok();
+    set_patch(&mut manifest, crate_name, &path.to_string_lossy());
+    std::fs::write("Cargo.toml", manifest.to_string())?;
+
+    info!("Checking that the patch applies...");
+    let output = run_command("cargo", &["check"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Cargo only warns about a patch that matches nothing in the dependency graph, and
+    // --quiet would hide that warning
+    let unused = stderr.contains("was not used in the crate graph");
+    let valid = output.status.success() && !unused;
+    if !valid {
+        std::fs::write("Cargo.toml", &original)?;
+        match &original_lock {
+            Some(lock) => std::fs::write(&lockfile, lock)?,
+            None => {
+                let _ = std::fs::remove_file(&lockfile);
+            }
+        }
+    }
+
+    let patches = manifest_patches(&read_manifest(Path::new("Cargo.toml"))?);
+    if json_output {
+        output_json(&json!({
+            "patches": patches,
+            "valid": valid,
+            "unused": unused,
+            "details": if valid { None } else { Some(stderr.trim()) }
+        }));
+    } else if valid {
+        output_text(&format!(
+            "✅ Patched {} with {} and cargo check passed",
+            crate_name,
+            path.display()
+        ));
+    } else {
+        if unused {
+            output_text(&format!(
+                "❌ The patch for {} isn't used by any dependency",
+                crate_name
+            ));
+            output_text("💡 The local crate's name and version must match the dependency");
+        } else {
+            output_text(&format!(
+                "❌ cargo check failed with {} patched",
+                crate_name
+            ));
+            output_text(stderr.trim());
+        }
+        output_text("↩️  Restored the original Cargo.toml and Cargo.lock");
+    }
+
+    if !valid {
+        return Err(anyhow!("Patch for {} doesn't apply", crate_name));
+    }
+    Ok(())
+}
+
+/// The Cargo.lock of the workspace the current directory belongs to.
+fn workspace_lockfile() -> PathBuf {
+    run_command(
+        "cargo",
+        &["locate-project", "--workspace", "--message-format", "plain"],
+    )
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+    .and_then(|manifest| manifest.parent().map(|dir| dir.join("Cargo.lock")))
+    .unwrap_or_else(|| PathBuf::from("Cargo.lock"))
+}
+
+async fn remove_patch(crate_name: &str, json_output: bool) -> Result<()> {
+    let original = std::fs::read_to_string("Cargo.toml")?;
+    let mut manifest: toml_edit::DocumentMut =
+        original.parse().context("Failed to parse Cargo.toml")?;
+    if !unset_patch(&mut manifest, crate_name) {
+        if json_output {
+            output_json(&json!({
+                "error": format!("No patch for {} in [patch.crates-io]", crate_name)
+            }));
+        } else {
+            output_text(&format!(
+                "❌ No patch for {} in [patch.crates-io]",
+                crate_name
+            ));
+        }
+        return Err(anyhow!("No patch for {}", crate_name));
+    }
+    std::fs::write("Cargo.toml", manifest.to_string())?;
+
+    let patches = manifest_patches(&read_manifest(Path::new("Cargo.toml"))?);
+    if json_output {
+        output_json(&json!({
+            "removed": crate_name,
+            "patches": patches
+        }));
+    } else {
+        output_text(&format!("✅ Removed the patch for {}", crate_name));
+    }
+    Ok(())
+}
+
+async fn list_patches(json_output: bool) -> Result<()> {
+    let patches = manifest_patches(&read_manifest(Path::new("Cargo.toml"))?);
+    // Without running cargo, the best check is that each local checkout is still there
+    let valid = patches.iter().all(|patch| {
+        patch["path"]
+            .as_str()
+            .is_none_or(|path| Path::new(path).join("Cargo.toml").is_file())
+    });
+
+    if json_output {
+        output_json(&json!({
+            "patches": patches,
+            "valid": valid
+        }));
+        return Ok(());
+    }
+
+    output_text("🩹 Dependency Patches");
+    output_text("=====================");
+    if patches.is_empty() {
+        output_text("No [patch] entries in Cargo.toml");
+        return Ok(());
+    }
+
+    let width = patches
+        .iter()
+        .filter_map(|p| p["crate_name"].as_str())
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    for patch in &patches {
+        let missing = patch["path"]
+            .as_str()
+            .is_some_and(|path| !Path::new(path).join("Cargo.toml").is_file());
+        output_text(&format!(
+            "  {} {:<width$}  [{}] {}",
+            if missing { "❌" } else { "✅" },
+            patch["crate_name"].as_str().unwrap_or(""),
+            patch["registry"].as_str().unwrap_or(""),
+            patch["source"].as_str().unwrap_or("")
+        ));
+    }
+    output_text("");
+    output_text(&format!("📊 {} patch(es)", patches.len()));
+    if !valid {
+        output_text("💡 Some patched paths no longer contain a Cargo.toml");
+    }
+    Ok(())
+}
+
+/// Every `[patch.<registry>]` entry as `{crate_name, registry, source}`, where `source` is
+/// the `path` or `git` URL it's patched with and `path` is also set for local patches.
+pub fn manifest_patches(manifest: &toml::Value) -> Vec<serde_json::Value> {
+    let mut patches = Vec::new();
+    for (registry, table) in manifest
+        .get("patch")
+        .and_then(|p| p.as_table())
+        .into_iter()
+        .flatten()
+    {
+        for (name, spec) in table.as_table().into_iter().flatten() {
+            let path = spec.get("path").and_then(|p| p.as_str());
+            let source = path.map(String::from).or_else(|| {
+                let git = spec.get("git")?.as_str()?;
+                let reference = ["branch", "tag", "rev"]
+                    .iter()
+                    .find_map(|key| Some(format!("{}={}", key, spec.get(*key)?.as_str()?)));
+                Some(match reference {
+                    Some(reference) => format!("{} ({})", git, reference),
+                    None => git.to_string(),
+                })
+            });
+            patches.push(json!({
+                // `package = "..."` patches a crate under another key
+                "crate_name": spec.get("package").and_then(|p| p.as_str()).unwrap_or(name),
+                "registry": registry,
+                "source": source,
+                "path": path
+            }));
+        }
+    }
+    patches
+}
+
+/// Set `<crate_name> = { path = "<path>" }` in `[patch.crates-io]`, replacing any
+/// existing patch for the crate.
+pub fn set_patch(manifest: &mut toml_edit::DocumentMut, crate_name: &str, path: &str) {
+    if !manifest.contains_table("patch") {
+        let mut patch = toml_edit::Table::new();
+        // Only the `[patch.crates-io]` header is written, not an empty `[patch]`
+        patch.set_implicit(true);
+        manifest["patch"] = toml_edit::Item::Table(patch);
+    }
+    let patch = &mut manifest["patch"];
+    if patch.get("crates-io").is_none_or(|t| !t.is_table_like()) {
+        patch["crates-io"] = toml_edit::table();
+    }
+    let mut spec = toml_edit::InlineTable::new();
+    spec.insert("path", path.into());
+    patch["crates-io"][crate_name] = toml_edit::value(spec);
+}
+
+/// Remove the crate's `[patch.crates-io]` entry, dropping the table once it's empty.
+/// Returns whether there was a patch to remove.
+pub fn unset_patch(manifest: &mut toml_edit::DocumentMut, crate_name: &str) -> bool {
+    let Some(patch) = manifest.get_mut("patch").and_then(|p| p.as_table_like_mut()) else {
+        return false;
+    };
+    let Some(crates_io) = patch
+        .get_mut("crates-io")
+        .and_then(|t| t.as_table_like_mut())
+    else {
+        return false;
+    };
+    if crates_io.remove(crate_name).is_none() {
+        return false;
+    }
+    if crates_io.is_empty() {
+        patch.remove("crates-io");
+    }
+    if patch.is_empty() {
+        manifest.remove("patch");
+    }
+    true
+}
+
+async fn show_supply_chain(
+    crates: bool,
+    publishers: bool,
+    users: bool,
+    json_output: bool,
+) -> Result<()> {
+    info!("Looking up dependency publishers...");
+
+    if !cargo_subcommand_installed("supply-chain") {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo supply-chain not available",
+                "suggestion": "Install with: cargo install cargo-supply-chain"
+            }));
+        } else {
+            output_text("❌ cargo-supply-chain not installed");
+            output_text("💡 Install with: cargo install cargo-supply-chain");
+        }
+        return Ok(());
+    }
+
+    // The `json` subcommand has what the `crates` and `publishers` views print, in one call
+    let output = run_command("cargo", &["supply-chain", "json"])?;
+    let report = match serde_json::from_slice::<serde_json::Value>(&output.stdout) {
+        Ok(report) if output.status.success() => report,
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if json_output {
+                output_json(&json!({
+                    "error": "cargo supply-chain failed",
+                    "details": stderr.trim()
                 }));
             } else {
-                dependencies.push(json!({
-                    "name": trimmed,
-                    "depth": depth / 4
+                output_text("❌ cargo supply-chain failed");
+                output_text(stderr.trim());
+            }
+            return Err(anyhow!("cargo supply-chain failed"));
+        }
+    };
+
+    let crate_publishers = supply_chain_publishers(&report);
+    let single_publisher: Vec<&str> = crate_publishers
+        .iter()
+        .filter(|(_, publishers)| publishers.len() == 1)
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    if json_output {
+        let mut report = report.clone();
+        report["high_risk_single_publisher"] = json!(single_publisher);
+        output_json(&report);
+        return Ok(());
+    }
+
+    output_text("🔗 Dependency Supply Chain");
+    output_text("==========================");
+
+    if crates {
+        output_text("");
+        output_text("📦 Crates:");
+        let width = crate_publishers.keys().map(|name| name.len()).max().unwrap_or(0);
+        for (name, publishers) in &crate_publishers {
+            let logins: Vec<&str> = publishers
+                .iter()
+                .filter_map(|p| p["login"].as_str())
+                .collect();
+            let marker = if publishers.len() == 1 { "⚠️ " } else { "  " };
+            output_text(&format!(
+                "  {} {:<width$}  {}",
+                marker,
+                name,
+                logins.join(", ")
+            ));
+        }
+    }
+
+    for (show, kinds, title) in [
+        (publishers, &["user", "team"][..], "👥 Publishers:"),
+        (users, &["user"][..], "👤 Users:"),
+    ] {
+        if !show {
+            continue;
+        }
+        let mut by_publisher: BTreeMap<&str, (&str, Vec<&str>)> = BTreeMap::new();
+        for (name, publishers) in &crate_publishers {
+            for publisher in publishers {
+                let kind = publisher["kind"].as_str().unwrap_or("user");
+                if let Some(login) = publisher["login"].as_str()
+                    && kinds.contains(&kind)
+                {
+                    let entry = by_publisher.entry(login).or_insert((kind, Vec::new()));
+                    entry.1.push(name);
+                }
+            }
+        }
+        let mut by_publisher: Vec<_> = by_publisher.into_iter().collect();
+        by_publisher.sort_by(|a, b| b.1.1.len().cmp(&a.1.1.len()).then_with(|| a.0.cmp(b.0)));
+
+        output_text("");
+        output_text(title);
+        let width = by_publisher.iter().map(|(login, _)| login.len()).max().unwrap_or(0);
+        for (login, (kind, crates)) in &by_publisher {
+            output_text(&format!(
+                "  {:<width$}  {:<4}  {} crate(s): {}",
+                login,
+                kind,
+                crates.len(),
+                crates.join(", ")
+            ));
+        }
+    }
+
+    output_text("");
+    output_text(&format!(
+        "📊 {} crate(s), {} with a single publisher",
+        crate_publishers.len(),
+        single_publisher.len()
+    ));
+    if !single_publisher.is_empty() {
+        output_text("⚠️  Crates with a single publisher depend on one account staying available");
+    }
+
+    Ok(())
+}
+
+/// Publishers of each crate in a `cargo supply-chain json` report, users and teams
+/// together, keyed by crate name.
+pub fn supply_chain_publishers(
+    report: &serde_json::Value,
+) -> BTreeMap<String, Vec<serde_json::Value>> {
+    report["crates_publishers"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, publishers)| {
+            // Either a plain list or split into `users` and `teams`, depending on the version
+            let publishers: Vec<serde_json::Value> = match publishers {
+                serde_json::Value::Array(publishers) => publishers.clone(),
+                _ => ["users", "teams"]
+                    .iter()
+                    .zip(["user", "team"])
+                    .flat_map(|(key, kind)| {
+                        publishers[*key]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .map(move |publisher| {
+                                let mut publisher = publisher.clone();
+                                if publisher.get("kind").is_none() {
+                                    publisher["kind"] = json!(kind);
+                                }
+                                publisher
+                            })
+                    })
+                    .collect(),
+            };
+            (name.clone(), publishers)
+        })
+        .collect()
+}
+
+async fn check_msrv_compat(msrv: Option<String>, json_output: bool) -> Result<()> {
+    let manifest = read_manifest(Path::new("Cargo.toml")).ok();
+    let msrv = msrv.or_else(|| {
+        let manifest = manifest.as_ref()?;
+        manifest
+            .get("package")
+            .and_then(|p| p.get("rust-version"))
+            .and_then(|v| v.as_str())
+            .or_else(|| {
+                manifest
+                    .get("workspace")?
+                    .get("package")?
+                    .get("rust-version")?
+                    .as_str()
+            })
+            .map(String::from)
+    });
+    let Some(msrv) = msrv else {
+        if json_output {
+            output_json(&json!({
+                "error": "No MSRV to check against",
+                "suggestion": "Set `rust-version` in [package] or pass --msrv"
+            }));
+        } else {
+            output_text("❌ No MSRV to check against");
+            output_text("💡 Set `rust-version` in [package] or pass --msrv");
+        }
+        return Err(anyhow!("No MSRV to check against"));
+    };
+
+    let installed = run_command("rustup", &["toolchain", "list"])
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line.starts_with(&format!("{}-", msrv)))
+        })
+        .unwrap_or(false);
+    if !installed {
+        info!("Installing Rust {}...", msrv);
+        let output = run_command(
+            "rustup",
+            &["toolchain", "install", &msrv, "--profile", "minimal"],
+        )?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if json_output {
+                output_json(&json!({
+                    "error": format!("Failed to install Rust {}", msrv),
+                    "stderr": stderr.trim()
+                }));
+            } else {
+                output_text(&format!("❌ Failed to install Rust {}", msrv));
+                output_text(stderr.trim());
+            }
+            return Err(anyhow!("rustup toolchain install {} failed", msrv));
+        }
+    }
+
+    info!("Running cargo +{} check...", msrv);
+    let toolchain = format!("+{}", msrv);
+    let output = run_command("cargo", &[&toolchain, "check", "--message-format=json"])?;
+    let success = output.status.success();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let mut failed_crates = msrv_failures(&stderr);
+    for message in cargo_messages(&output, "compiler-message") {
+        if message["message"]["level"] != "error" {
+            continue;
+        }
+        let Some(name) = message["package_id"].as_str().map(package_name) else {
+            continue;
+        };
+        if failed_crates.iter().any(|failed| failed["name"] == name) {
+            continue;
+        }
+        failed_crates.push(json!({
+            "name": name,
+            "error_message": message["message"]["message"]
+        }));
+    }
+    // Anything else cargo rejected before compiling, e.g. an unsupported lockfile version
+    let error = (!success && failed_crates.is_empty()).then(|| {
+        stderr
+            .lines()
+            .filter(|line| line.starts_with("error"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    if json_output {
+        output_json(&json!({
+            "msrv": msrv,
+            "success": success,
+            "failed_crates": failed_crates,
+            "error": error
+        }));
+    } else {
+        output_text(&format!("🦀 MSRV Compatibility: Rust {}", msrv));
+        output_text("==============================");
+        if success {
+            output_text(&format!("✅ The project and its dependencies build with Rust {}", msrv));
+        } else {
+            output_text(&format!("❌ cargo +{} check failed", msrv));
+            for failed in &failed_crates {
+                output_text(&format!(
+                    "  • {}: {}",
+                    failed["name"].as_str().unwrap_or("unknown"),
+                    failed["error_message"].as_str().unwrap_or("")
+                ));
+            }
+            if let Some(error) = &error {
+                output_text(error);
+            }
+            output_text(
+                "💡 Pin older dependency versions with `cargo update <crate> --precise <version>` or raise rust-version",
+            );
+        }
+    }
+
+    if !success {
+        return Err(anyhow!("Project does not build with Rust {}", msrv));
+    }
+    Ok(())
+}
+
+/// Crates cargo refuses to build because their `rust-version` is newer than the active
+/// toolchain. Handles both the older one-error-per-package form and the newer summary list.
+pub fn msrv_failures(stderr: &str) -> Vec<serde_json::Value> {
+    let mut failures = Vec::new();
+    let mut in_summary = false;
+    for line in stderr.lines() {
+        // error: package `clap_lex v0.7.4` cannot be built because it requires rustc 1.74 ...
+        if let Some(rest) = line.strip_prefix("error: package `")
+            && let Some((package, message)) = rest.split_once("` cannot be built because it ")
+        {
+            let name = package.split_whitespace().next().unwrap_or(package);
+            let message = message.split(", while").next().unwrap_or(message);
+            failures.push(json!({ "name": name, "error_message": message }));
+            in_summary = false;
+            continue;
+        }
+        // error: rustc 1.70.0 is not supported by the following packages:
+        //   clap@4.5.0 requires rustc 1.74
+        if line.starts_with("error:")
+            && line.contains("is not supported by the following packages")
+        {
+            in_summary = true;
+            continue;
+        }
+        if in_summary {
+            let Some((package, requirement)) = line.trim().split_once(" requires ") else {
+                in_summary = false;
+                continue;
+            };
+            let name = package.split('@').next().unwrap_or(package);
+            failures.push(json!({
+                "name": name,
+                "error_message": format!("requires {}", requirement)
+            }));
+        }
+    }
+    failures
+}
+
+/// Package name from a cargo package ID, in either the `registry+...#name@version` or
+/// the older `name version (source)` format.
+pub fn package_name(package_id: &str) -> String {
+    match package_id.rsplit_once('#') {
+        Some((source, fragment)) => match fragment.split_once('@') {
+            Some((name, _)) => name.to_string(),
+            // Path packages whose directory matches their name omit it: `path+file:///x/name#0.1.0`
+            None => source.rsplit('/').next().unwrap_or(source).to_string(),
+        },
+        None => package_id
+            .split_whitespace()
+            .next()
+            .unwrap_or(package_id)
+            .to_string(),
+    }
+}
+
+fn unsafe_map_key(krate: &serde_json::Value) -> String {
+    format!(
+        "{} {}",
+        krate["name"].as_str().unwrap_or(""),
+        krate["version"].as_str().unwrap_or("")
+    )
+}
+
+/// Print `krate` and its dependencies like `cargo tree`, marking repeats with `(*)`.
+fn print_unsafe_tree(
+    krate: &serde_json::Value,
+    by_key: &HashMap<String, &serde_json::Value>,
+    prefix: &str,
+    child_prefix: &str,
+    seen: &mut HashSet<String>,
+) {
+    let key = unsafe_map_key(krate);
+    let unsafe_calls = krate["unsafe_calls"].as_u64().unwrap_or(0);
+    let label = if unsafe_calls > 0 {
+        console::style(format!("{} ({} unsafe)", key, unsafe_calls)).red()
+    } else if krate["forbids_unsafe"] == true {
+        console::style(format!("{} (forbids unsafe)", key)).green()
+    } else {
+        console::style(key.clone()).green()
+    };
+
+    let repeated = !seen.insert(key);
+    if repeated {
+        output_text(&format!("{}{} (*)", prefix, label));
+        return;
+    }
+    output_text(&format!("{}{}", prefix, label));
+
+    // Dependencies were already sorted by unsafe count when the report was parsed
+    let children: Vec<&serde_json::Value> = krate["dependencies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|dep| by_key.get(dep.as_str()?).copied())
+        .collect();
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let branch = if last { "└── " } else { "├── " };
+        let indent = if last { "    " } else { "│   " };
+        print_unsafe_tree(
+            child,
+            by_key,
+            &format!("{}{}", child_prefix, branch),
+            &format!("{}{}", child_prefix, indent),
+            seen,
+        );
+    }
+}
+
+/// Turn a `cargo geiger --output-format Json` report into one entry per crate, sorted
+/// by used unsafe items (functions, expressions, impls, traits and methods) descending.
+/// `transitive_unsafe_calls` includes the crate itself and every crate it depends on.
+pub fn parse_geiger_report(report: &serde_json::Value) -> Vec<serde_json::Value> {
+    const CATEGORIES: [&str; 5] = ["functions", "exprs", "item_impls", "item_traits", "methods"];
+
+    let package_key = |id: &serde_json::Value| {
+        Some(format!("{} {}", id["name"].as_str()?, id["version"].as_str()?))
+    };
+
+    let mut unsafe_counts = HashMap::new();
+    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+    let mut entries = Vec::new();
+    for entry in report["packages"].as_array().into_iter().flatten() {
+        let Some(key) = package_key(&entry["package"]["id"]) else {
+            continue;
+        };
+        let used = &entry["unsafety"]["used"];
+        let unsafe_calls: u64 = CATEGORIES
+            .iter()
+            .filter_map(|category| {
+                let count = &used[*category];
+                count["unsafe_"].as_u64().or_else(|| count["unsafe"].as_u64())
+            })
+            .sum();
+        let dependencies: Vec<String> = entry["package"]["dependencies"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(package_key)
+            .collect();
+
+        unsafe_counts.insert(key.clone(), unsafe_calls);
+        graph.insert(key.clone(), dependencies);
+        entries.push((
+            key,
+            entry["package"]["id"].clone(),
+            unsafe_calls,
+            entry["unsafety"]["forbids_unsafe"].as_bool().unwrap_or(false),
+        ));
+    }
+
+    let transitive = |root: &str| -> u64 {
+        let mut visited = HashSet::new();
+        let mut stack = vec![root.to_string()];
+        while let Some(key) = stack.pop() {
+            if visited.insert(key.clone()) {
+                stack.extend(graph.get(&key).into_iter().flatten().cloned());
+            }
+        }
+        visited.iter().filter_map(|key| unsafe_counts.get(key)).sum()
+    };
+    let by_unsafe = |a: &String, b: &String| {
+        let count = |key: &String| unsafe_counts.get(key).copied().unwrap_or(0);
+        count(b).cmp(&count(a)).then_with(|| a.cmp(b))
+    };
+
+    let mut crates: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|(key, id, unsafe_calls, forbids_unsafe)| {
+            let mut dependencies = graph.get(key).cloned().unwrap_or_default();
+            dependencies.sort_by(by_unsafe);
+            json!({
+                "name": id["name"],
+                "version": id["version"],
+                "unsafe_calls": unsafe_calls,
+                "forbids_unsafe": forbids_unsafe,
+                "transitive_unsafe_calls": transitive(key),
+                "dependencies": dependencies
+            })
+        })
+        .collect();
+    crates.sort_by(|a, b| {
+        b["unsafe_calls"]
+            .as_u64()
+            .cmp(&a["unsafe_calls"].as_u64())
+            .then_with(|| unsafe_map_key(a).cmp(&unsafe_map_key(b)))
+    });
+    crates
+}
+
+async fn fetch_dependencies(offline_after: bool, json_output: bool) -> Result<()> {
+    info!("Fetching dependencies...");
+
+    let output = run_command("cargo", &["fetch"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo fetch failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo fetch failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo fetch failed"));
+    }
+
+    // Path dependencies aren't downloaded, so only count packages with a source
+    let packages: Vec<_> = read_lockfile(Path::new("Cargo.lock"))?
+        .into_iter()
+        .filter(|package| package.source.is_some())
+        .collect();
+
+    let cache_dirs = registry_cache_dirs();
+    let total_size_bytes: u64 = packages
+        .iter()
+        .filter_map(|package| cached_crate_size(&cache_dirs, &package.name, &package.version))
+        .sum();
+
+    let config_path = if offline_after {
+        Some(set_offline_mode()?)
+    } else {
+        None
+    };
+
+    if json_output {
+        output_json(&json!({
+            "packages_fetched": packages.len(),
+            "total_size_bytes": total_size_bytes,
+            "offline_mode_set": config_path.is_some(),
+            "config_path": config_path
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Fetched {} packages ({} in the registry cache)",
+            packages.len(),
+            format_bytes(total_size_bytes)
+        ));
+        if let Some(path) = &config_path {
+            output_text(&format!(
+                "📴 Set net.offline = true in {}",
+                path.display()
+            ));
+            output_text("💡 Pass `--config net.offline=false` to cargo (or remove the setting) to go online again");
+        }
+    }
+
+    Ok(())
+}
+
+/// Per-registry directories under `$CARGO_HOME/registry/cache` holding downloaded crates.
+fn registry_cache_dirs() -> Vec<std::path::PathBuf> {
+    cargo_home()
+        .and_then(|home| std::fs::read_dir(home.join("registry").join("cache")).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect()
+}
+
+/// Size of the downloaded `<name>-<version>.crate`, if any registry cache has it.
+fn cached_crate_size(cache_dirs: &[std::path::PathBuf], name: &str, version: &str) -> Option<u64> {
+    let file_name = format!("{}-{}.crate", name, version);
+    cache_dirs
+        .iter()
+        .find_map(|dir| std::fs::metadata(dir.join(&file_name)).ok())
+        .map(|metadata| metadata.len())
+}
+
+async fn fetch_all_features(requested: &[String], json_output: bool) -> Result<()> {
+    let manifest = read_manifest(Path::new("Cargo.toml"))?;
+    if !Path::new("Cargo.lock").exists() {
+        info!("Generating Cargo.lock...");
+        run_command("cargo", &["generate-lockfile"])?;
+    }
+    let registry_packages: HashSet<String> = read_lockfile(Path::new("Cargo.lock"))?
+        .into_iter()
+        .filter(|package| matches!(package.source_kind(), "crates.io" | "registry"))
+        .map(|package| format!("{}@{}", package.name, package.version))
+        .collect();
+    let cache_dirs = registry_cache_dirs();
+
+    // `cargo fetch` has no feature flags: it downloads everything in Cargo.lock, which covers
+    // all features. So resolve each feature set with `cargo tree` to find what it needs that
+    // isn't cached yet, then fetch once.
+    let mut feature_sets = Vec::new();
+    let mut additional_packages: Vec<String> = Vec::new();
+    for (label, feature_args) in feature_sets_to_fetch(&manifest, requested) {
+        info!("Resolving dependencies for {}...", label);
+        let mut args = vec!["tree", "--target", "all", "--prefix", "none"];
+        args.extend(feature_args.iter().map(String::as_str));
+        let output = run_command("cargo", &args)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if json_output {
+                output_json(&json!({
+                    "error": format!("cargo tree failed for {}", label),
+                    "stderr": stderr.trim()
+                }));
+            } else {
+                output_text(&format!("❌ cargo tree failed for {}", label));
+                output_text(stderr.trim());
+            }
+            return Err(anyhow!("cargo tree failed for {}", label));
+        }
+
+        let mut missing: Vec<String> = tree_packages(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .filter(|package| registry_packages.contains(package))
+            .filter(|package| {
+                let (name, version) = package.split_once('@').unwrap_or((package, ""));
+                cached_crate_size(&cache_dirs, name, version).is_none()
+            })
+            .collect();
+        missing.sort();
+        for package in &missing {
+            if !additional_packages.contains(package) {
+                additional_packages.push(package.clone());
+            }
+        }
+        feature_sets.push(json!({
+            "features": label,
+            "missing_packages": missing
+        }));
+    }
+    additional_packages.sort();
+
+    info!("Fetching dependencies...");
+    let output = run_command("cargo", &["fetch"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo fetch failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo fetch failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo fetch failed"));
+    }
+
+    if json_output {
+        output_json(&json!({
+            "feature_sets_fetched": feature_sets.len(),
+            "additional_packages": additional_packages,
+            "feature_sets": feature_sets
+        }));
+        return Ok(());
+    }
+
+    output_text("📥 Feature Set Downloads");
+    output_text("========================");
+    for set in &feature_sets {
+        let missing: Vec<&str> = set["missing_packages"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.as_str())
+            .collect();
+        let label = set["features"].as_str().unwrap_or("");
+        if missing.is_empty() {
+            output_text(&format!("✅ {}: already cached", label));
+        } else {
+            output_text(&format!("📦 {}: downloaded {}", label, missing.join(", ")));
+        }
+    }
+    output_text("");
+    output_text(&format!(
+        "✅ Fetched {} feature set(s), {} package(s) needed downloading",
+        feature_sets.len(),
+        additional_packages.len()
+    ));
+
+    Ok(())
+}
+
+/// The feature sets to resolve, as (label, `cargo` feature flags): the requested lists, or
+/// the default features, each feature on its own (including implicit features of optional
+/// dependencies) and all features together. Features only ever add dependencies, so these
+/// cover every combination short of `dep?/feature` interactions, which all features catch.
+pub fn feature_sets_to_fetch(
+    manifest: &toml::Value,
+    requested: &[String],
+) -> Vec<(String, Vec<String>)> {
+    if !requested.is_empty() {
+        return requested
+            .iter()
+            .map(|list| (list.clone(), vec!["--features".to_string(), list.clone()]))
+            .collect();
+    }
+
+    let mut sets = vec![("default features".to_string(), Vec::new())];
+    sets.extend(
+        manifest_features(manifest)
+            .into_iter()
+            .map(|feature| (feature.clone(), vec!["--features".to_string(), feature])),
+    );
+    sets.push(("all features".to_string(), vec!["--all-features".to_string()]));
+    sets
+}
+
+/// Features a package can be built with, sorted: the `[features]` table (without
+/// `default`) plus the implicit features of optional dependencies.
+fn manifest_features(manifest: &toml::Value) -> Vec<String> {
+    let declared = manifest.get("features").and_then(|f| f.as_table());
+    let mut features: Vec<String> = declared
+        .into_iter()
+        .flat_map(|table| table.keys())
+        .filter(|name| *name != "default")
+        .cloned()
+        .collect();
+
+    // Optional dependencies get a feature of their own unless a feature refers to `dep:name`
+    let explicit_deps: Vec<String> = declared
+        .into_iter()
+        .flat_map(|table| table.values())
+        .filter_map(|value| value.as_array())
+        .flatten()
+        .filter_map(|value| value.as_str()?.strip_prefix("dep:").map(String::from))
+        .collect();
+    let dependencies = manifest.get("dependencies").and_then(|d| d.as_table());
+    for (name, spec) in dependencies.into_iter().flatten() {
+        let optional = spec.get("optional").and_then(|o| o.as_bool()) == Some(true);
+        if optional && !explicit_deps.contains(name) && !features.contains(name) {
+            features.push(name.clone());
+        }
+    }
+    features.sort();
+    features
+}
+
+/// Subsets of `features`, smallest first and in order within each size, up to `max`.
+pub fn feature_combinations(features: &[String], max: usize) -> Vec<Vec<String>> {
+    fn extend(
+        features: &[String],
+        size: usize,
+        start: usize,
+        current: &mut Vec<String>,
+        combinations: &mut Vec<Vec<String>>,
+        max: usize,
+    ) {
+        if combinations.len() >= max {
+            return;
+        }
+        if current.len() == size {
+            combinations.push(current.clone());
+            return;
+        }
+        for i in start..features.len() {
+            current.push(features[i].clone());
+            extend(features, size, i + 1, current, combinations, max);
+            current.pop();
+        }
+    }
+
+    let mut combinations = Vec::new();
+    for size in 0..=features.len() {
+        extend(features, size, 0, &mut Vec::new(), &mut combinations, max);
+    }
+    combinations
+}
+
+async fn check_feature_matrix(max_combinations: usize, json_output: bool) -> Result<()> {
+    let manifest = read_manifest(Path::new("Cargo.toml"))?;
+    let features = manifest_features(&manifest);
+    let combinations = feature_combinations(&features, max_combinations);
+    // 2^n overflows quickly, and anything past the cap isn't tested anyway
+    let total = 1u64.checked_shl(features.len() as u32).unwrap_or(u64::MAX);
+
+    if !json_output {
+        output_text(&format!(
+            "🧮 Checking {} of {} feature combination(s)",
+            combinations.len(),
+            total
+        ));
+    }
+
+    let mut failures = Vec::new();
+    for combination in &combinations {
+        let list = combination.join(",");
+        let label = if list.is_empty() { "(no features)" } else { list.as_str() };
+        info!("Checking with features: {}", label);
+        let mut args = vec!["check", "--no-default-features"];
+        if !list.is_empty() {
+            args.extend(["--features", list.as_str()]);
+        }
+        let output = run_command("cargo", &args)?;
+        if output.status.success() {
+            if !json_output {
+                output_text(&format!("✅ {}", label));
+            }
+            continue;
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = stderr
+            .lines()
+            .find(|line| line.starts_with("error"))
+            .unwrap_or_else(|| stderr.trim())
+            .to_string();
+        if !json_output {
+            output_text(&format!("❌ {}: {}", label, error));
+        }
+        failures.push(json!({
+            "features": combination,
+            "error": error
+        }));
+    }
+
+    let all_passed = failures.is_empty();
+    if json_output {
+        output_json(&json!({
+            "combinations_tested": combinations.len(),
+            "total_combinations": total,
+            "failures": failures,
+            "all_passed": all_passed
+        }));
+    } else {
+        output_text("");
+        if all_passed {
+            output_text(&format!(
+                "🎉 All {} combination(s) passed",
+                combinations.len()
+            ));
+        } else {
+            output_text(&format!(
+                "💥 {} of {} combination(s) failed",
+                failures.len(),
+                combinations.len()
+            ));
+        }
+        if (combinations.len() as u64) < total {
+            output_text("💡 Raise --max-combinations to test more combinations");
+        }
+    }
+
+    if !all_passed {
+        return Err(anyhow!("{} feature combination(s) failed", failures.len()));
+    }
+
+    Ok(())
+}
+
+/// Packages listed by `cargo tree --prefix none`, as `name@version`.
+pub fn tree_packages(tree: &str) -> Vec<String> {
+    let mut packages: Vec<String> = Vec::new();
+    for line in tree.lines() {
+        let mut words = line.split_whitespace();
+        if let (Some(name), Some(version)) = (words.next(), words.next())
+            && let Some(version) = version.strip_prefix('v')
+        {
+            let package = format!("{}@{}", name, version);
+            if !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
+    }
+    packages
+}
+
+/// Turn on `net.offline` in the project's cargo config, keeping everything else in it.
+fn set_offline_mode() -> Result<std::path::PathBuf> {
+    // Older projects may still use the extension-less `.cargo/config`
+    let legacy = Path::new(".cargo/config");
+    let path = if legacy.is_file() && !Path::new(".cargo/config.toml").exists() {
+        legacy.to_path_buf()
+    } else {
+        Path::new(".cargo/config.toml").to_path_buf()
+    };
+
+    let mut config = match std::fs::read_to_string(&path) {
+        Ok(content) => content
+            .parse::<toml_edit::DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(_) => toml_edit::DocumentMut::new(),
+    };
+    if !config.contains_table("net") {
+        config["net"] = toml_edit::table();
+    }
+    config["net"]["offline"] = toml_edit::value(true);
+
+    std::fs::create_dir_all(".cargo")?;
+    std::fs::write(&path, config.to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+async fn pin_exact_versions(json_output: bool) -> Result<()> {
+    info!("Pinning dependencies to exact locked versions...");
+
+    let lock_path = Path::new("Cargo.lock");
+    if !lock_path.exists() {
+        if json_output {
+            output_json(&json!({
+                "error": "Cargo.lock not found",
+                "suggestion": "Run `cargo generate-lockfile` first"
+            }));
+        } else {
+            output_text("❌ Cargo.lock not found");
+            output_text("💡 Run `cargo generate-lockfile` first");
+        }
+        return Err(anyhow!("Cargo.lock not found"));
+    }
+
+    let mut locked: HashMap<String, Vec<String>> = HashMap::new();
+    for package in read_lockfile(lock_path)? {
+        locked.entry(package.name).or_default().push(package.version);
+    }
+
+    let original = std::fs::read_to_string("Cargo.toml")?;
+    let mut manifest: toml_edit::DocumentMut =
+        original.parse().context("Failed to parse Cargo.toml")?;
+    let mut changed = Vec::new();
+
+    pin_dependency_tables(manifest.as_table_mut(), &locked, &mut changed);
+    if let Some(targets) = manifest
+        .get_mut("target")
+        .and_then(|t| t.as_table_like_mut())
+    {
+        for (_, target) in targets.iter_mut() {
+            if let Some(target) = target.as_table_like_mut() {
+                pin_dependency_tables(target, &locked, &mut changed);
+            }
+        }
+    }
+
+    if !changed.is_empty() {
+        std::fs::copy("Cargo.toml", "Cargo.toml.bak")
+            .context("Failed to back up Cargo.toml")?;
+        std::fs::write("Cargo.toml", manifest.to_string())?;
+    }
+
+    if json_output {
+        output_json(&json!({
+            "changed": changed,
+            "backup": if changed.is_empty() { None } else { Some("Cargo.toml.bak") }
+        }));
+    } else {
+        output_text("📌 Pin Exact Versions");
+        output_text("=====================");
+
+        if changed.is_empty() {
+            output_text("✅ All dependencies are already pinned");
+        } else {
+            for change in &changed {
+                let name = change["crate"].as_str().unwrap_or("unknown");
+                output_text(&format!("- {} = \"{}\"", name, change["from"].as_str().unwrap_or("")));
+                output_text(&format!("+ {} = \"{}\"", name, change["to"].as_str().unwrap_or("")));
+            }
+            output_text("");
+            output_text(&format!("✅ Pinned {} dependencies", changed.len()));
+            output_text("💾 Original saved to Cargo.toml.bak");
+        }
+    }
+
+    Ok(())
+}
+
+fn pin_dependency_tables(
+    table: &mut dyn toml_edit::TableLike,
+    locked: &HashMap<String, Vec<String>>,
+    changed: &mut Vec<serde_json::Value>,
+) {
+    for key in DEPENDENCY_TABLES {
+        let Some(deps) = table.get_mut(key).and_then(|d| d.as_table_like_mut()) else {
+            continue;
+        };
+
+        for (name, dep) in deps.iter_mut() {
+            let name = name.get().to_string();
+            if let Some((from, to)) = pin_dependency(&name, dep, locked) {
+                changed.push(json!({
+                    "crate": name,
+                    "from": from,
+                    "to": to
                 }));
             }
         }
     }
+}
+
+/// Rewrite a single dependency's version requirement, returning `(from, to)` if it changed.
+fn pin_dependency(
+    name: &str,
+    dep: &mut toml_edit::Item,
+    locked: &HashMap<String, Vec<String>>,
+) -> Option<(String, String)> {
+    let (package, version_value) = if dep.is_str() {
+        (name.to_string(), dep.as_value_mut()?)
+    } else {
+        let table = dep.as_table_like_mut()?;
+        // Path, git and workspace-inherited dependencies aren't resolved from a registry
+        if table.contains_key("path") || table.contains_key("git") || table.contains_key("workspace") {
+            return None;
+        }
+        let package = table
+            .get("package")
+            .and_then(|p| p.as_str())
+            .unwrap_or(name)
+            .to_string();
+        (package, table.get_mut("version")?.as_value_mut()?)
+    };
+
+    let requirement = version_value.as_str()?.to_string();
+    let version = locked
+        .get(&package)?
+        .iter()
+        .find(|v| matches_requirement(&requirement, v))?;
+    let pinned = format!("={}", version);
+    if requirement == pinned {
+        return None;
+    }
+
+    let decor = version_value.decor().clone();
+    *version_value = pinned.as_str().into();
+    *version_value.decor_mut() = decor;
+
+    Some((requirement, pinned))
+}
+
+/// Loose caret-requirement match, enough to pick between multiple locked versions of a crate.
+fn matches_requirement(requirement: &str, version: &str) -> bool {
+    let requirement = requirement.trim_start_matches(|c: char| !c.is_ascii_digit());
+    if requirement.is_empty() {
+        return true;
+    }
+
+    let version_parts: Vec<&str> = version.split(['.', '-', '+']).collect();
+    for (i, part) in requirement.split('.').enumerate() {
+        if part == "*" {
+            break;
+        }
+        if version_parts.get(i) != Some(&part) {
+            return false;
+        }
+        // Everything up to and including the first non-zero component must match
+        if part != "0" {
+            break;
+        }
+    }
+
+    true
+}
+
+fn parse_dependency_tree(
+    tree_output: &str,
+    yanked: &HashSet<(String, String)>,
+) -> Vec<serde_json::Value> {
+    let mut dependencies = Vec::new();
+    
+    for line in tree_output.lines() {
+        let (depth, trimmed) = split_tree_prefix(line);
+        if !trimmed.is_empty() {
+            let is_yanked = tree_line_package(line).is_some_and(|pkg| yanked.contains(&pkg));
+            // Features are a single comma-separated token after the package id
+            let entry = trimmed.trim_end_matches(" (*)");
+            let (name_version, features) = match entry.rsplit_once(' ') {
+                Some((name_version, features))
+                    if name_version.contains(" v") && !features.ends_with(')') =>
+                {
+                    (name_version, Some(features))
+                }
+                _ => (entry, None),
+            };
+            dependencies.push(json!({
+                "name": name_version,
+                "features": features,
+                "depth": depth,
+                "yanked": is_yanked
+            }));
+        }
+    }
     
     dependencies
 }
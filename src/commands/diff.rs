@@ -0,0 +1,270 @@
+use crate::commands::info::collect_dependencies;
+use crate::utils::{
+    cargo_messages, count_rust_loc, create_worktree, format_bytes, is_rust_project, output_json,
+    output_text, read_manifest, remove_worktree, run_command, run_command_in,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use tracing::info;
+
+const METRICS: &[&str] = &["loc", "deps", "warnings", "binary_size", "test_count"];
+const DEFAULT_METRICS: &[&str] = &["loc", "deps", "warnings"];
+
+/// Target directory for builds of the base ref, kept between runs so they stay incremental.
+const BASE_TARGET_DIR: &str = "target/oxy-diff";
+
+pub async fn run(base_ref: &str, metrics: Vec<String>, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let metrics: Vec<String> = if metrics.is_empty() {
+        DEFAULT_METRICS.iter().map(|m| m.to_string()).collect()
+    } else {
+        metrics
+    };
+    if let Some(unknown) = metrics.iter().find(|m| !METRICS.contains(&m.as_str())) {
+        if json_output {
+            output_json(&json!({
+                "error": format!("Unknown metric: {}", unknown),
+                "supported_metrics": METRICS
+            }));
+        } else {
+            output_text(&format!("❌ Unknown metric: {}", unknown));
+            output_text(&format!("💡 Supported metrics: {}", METRICS.join(", ")));
+        }
+        return Err(anyhow!("Unknown metric: {}", unknown));
+    }
+
+    let resolved = run_command(
+        "git",
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", base_ref)],
+    )?;
+    if !resolved.status.success() {
+        if json_output {
+            output_json(&json!({
+                "error": format!("Unknown git ref: {}", base_ref)
+            }));
+        } else {
+            output_text(&format!("❌ Unknown git ref: {}", base_ref));
+        }
+        return Err(anyhow!("Unknown git ref: {}", base_ref));
+    }
+
+    // The project may live in a subdirectory of the repository
+    let prefix = run_command("git", &["rev-parse", "--show-prefix"])
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    // Check the base out into a separate worktree so the working tree is never touched
+    info!("Checking out {}...", base_ref);
+    let worktree = match create_worktree(base_ref, "diff") {
+        Ok(worktree) => worktree,
+        Err(e) => {
+            if json_output {
+                output_json(&json!({
+                    "error": format!("Failed to check out {}", base_ref),
+                    "details": e.to_string()
+                }));
+            } else {
+                output_text(&format!("❌ Failed to check out {}", base_ref));
+                output_text(&format!("Error: {}", e));
+            }
+            return Err(e);
+        }
+    };
+
+    let base_target_dir = std::env::current_dir()?.join(BASE_TARGET_DIR);
+    info!("Collecting metrics at {}...", base_ref);
+    let before = collect_metrics(&worktree.join(&prefix), Some(&base_target_dir), &metrics);
+    remove_worktree(&worktree);
+
+    info!("Collecting metrics for the working tree...");
+    let after = collect_metrics(Path::new("."), None, &metrics);
+
+    let rows: Vec<Value> = metrics
+        .iter()
+        .zip(before.iter().zip(&after))
+        .map(|(name, (before, after))| {
+            let delta = before
+                .zip(*after)
+                .map(|(before, after)| after as i64 - before as i64);
+            json!({
+                "name": name,
+                "before": before,
+                "after": after,
+                "delta": delta
+            })
+        })
+        .collect();
+
+    if json_output {
+        output_json(&json!({
+            "base_ref": base_ref,
+            "metrics": rows
+        }));
+        return Ok(());
+    }
+
+    output_text(&format!("📊 Metrics: {} → working tree", base_ref));
+    output_text("===============================");
+
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let name = row["name"].as_str().unwrap_or("");
+            let value = |key: &str| match row[key].as_u64() {
+                Some(value) if name == "binary_size" => format_bytes(value),
+                Some(value) => value.to_string(),
+                None => "n/a".to_string(),
+            };
+            let delta = match row["delta"].as_i64() {
+                Some(delta) if name == "binary_size" => {
+                    let sign = if delta < 0 { "-" } else { "+" };
+                    format!("{}{}", sign, format_bytes(delta.unsigned_abs()))
+                }
+                Some(delta) => format!("{:+}", delta),
+                None => "n/a".to_string(),
+            };
+            [name.to_string(), value("before"), value("after"), delta]
+        })
+        .collect();
+    let header = [
+        "Metric".to_string(),
+        "Before".to_string(),
+        "After".to_string(),
+        "Delta".to_string(),
+    ];
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            cells
+                .iter()
+                .chain(std::iter::once(&header))
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let render = |row: &[String; 4]| {
+        row.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                // Left-align the metric name, right-align the numbers
+                if i == 0 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    output_text(&render(&header));
+    output_text(&"-".repeat(widths.iter().sum::<usize>() + 6));
+    for row in &cells {
+        output_text(&render(row));
+    }
+
+    Ok(())
+}
+
+/// Measure each metric in `dir`; metrics that can't be collected there are `None`.
+fn collect_metrics(dir: &Path, target_dir: Option<&Path>, metrics: &[String]) -> Vec<Option<u64>> {
+    let has_manifest = dir.join("Cargo.toml").is_file();
+    metrics
+        .iter()
+        .map(|metric| {
+            if !has_manifest {
+                return None;
+            }
+            match metric.as_str() {
+                "loc" => Some(count_rust_loc(dir) as u64),
+                "deps" => read_manifest(&dir.join("Cargo.toml"))
+                    .ok()
+                    .map(|manifest| collect_dependencies(&manifest).len() as u64),
+                "warnings" => count_warnings(dir, target_dir),
+                "binary_size" => binary_size(dir, target_dir),
+                "test_count" => count_tests(dir, target_dir),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn cargo_in(dir: &Path, target_dir: Option<&Path>, args: &[&str]) -> Option<Output> {
+    let envs: Vec<(&str, &OsStr)> = target_dir
+        .map(|dir| ("CARGO_TARGET_DIR", dir.as_os_str()))
+        .into_iter()
+        .collect();
+    run_command_in("cargo", args, dir, &envs).ok()
+}
+
+pub fn count_warnings(dir: &Path, target_dir: Option<&Path>) -> Option<u64> {
+    let output = cargo_in(
+        dir,
+        target_dir,
+        &["clippy", "--all-targets", "--message-format=json"],
+    )?;
+    let count = cargo_messages(&output, "compiler-message")
+        .iter()
+        .filter(|message| message["message"]["level"] == "warning")
+        // Skip rustc's "N warnings emitted" summaries, which have no source location
+        .filter(|message| {
+            message["message"]["spans"]
+                .as_array()
+                .is_some_and(|spans| !spans.is_empty())
+        })
+        .count();
+    Some(count as u64)
+}
+
+/// Combined size of the release executables.
+pub fn binary_size(dir: &Path, target_dir: Option<&Path>) -> Option<u64> {
+    let output = cargo_in(
+        dir,
+        target_dir,
+        &["build", "--release", "--message-format=json"],
+    )?;
+    if !output.status.success() {
+        return None;
+    }
+    let executables: Vec<PathBuf> = cargo_messages(&output, "compiler-artifact")
+        .iter()
+        .filter_map(|artifact| artifact["executable"].as_str().map(PathBuf::from))
+        .collect();
+    if executables.is_empty() {
+        return None;
+    }
+    Some(
+        executables
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+    )
+}
+
+pub fn count_tests(dir: &Path, target_dir: Option<&Path>) -> Option<u64> {
+    let output = cargo_in(dir, target_dir, &["test", "--quiet", "--", "--list"])?;
+    if !output.status.success() {
+        return None;
+    }
+    let count = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.ends_with(": test"))
+        .count();
+    Some(count as u64)
+}
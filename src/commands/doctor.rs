@@ -1,10 +1,43 @@
-use crate::utils::{output_json, output_text, run_command};
-use anyhow::Result;
+use crate::utils::{
+    cargo_config_files, cargo_home, cargo_subcommand_installed, find_files, output_json,
+    output_text, read_lockfile, read_manifest, registry_source_dir, run_command, rustup_home,
+};
+use anyhow::{Result, anyhow};
 use serde_json::json;
 use std::env;
-use std::path::Path;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tracing::info;
 
+/// Hosts cargo talks to when fetching the index and downloading crates.
+const CRATES_IO_HOSTS: [&str; 3] = ["crates.io", "static.crates.io", "index.crates.io"];
+const LATENCY_WARNING: Duration = Duration::from_millis(500);
+const LATENCY_ERROR: Duration = Duration::from_secs(5);
+/// Thresholds for compiling a hello-world with plain rustc, which takes well under a
+/// second on a healthy machine.
+const COMPILE_WARNING: Duration = Duration::from_secs(2);
+const COMPILE_ERROR: Duration = Duration::from_secs(10);
+/// Filesystem types in /proc/mounts that are backed by the network, without any `fuse.`
+/// prefix.
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "lustre",
+    "davfs",
+    "sshfs",
+    "rclone",
+    "s3fs",
+    "gcsfuse",
+];
+
 pub async fn run(json_output: bool) -> Result<()> {
     info!("Running environment diagnostics...");
 
@@ -32,6 +65,44 @@ pub async fn run(json_output: bool) -> Result<()> {
         }
     }
 
+    // Benchmark rustc on a minimal program to spot slow compilers (AV scanning, NFS, ...)
+    match benchmark_rustc() {
+        Ok(elapsed) => {
+            let elapsed_ms = elapsed.as_millis() as u64;
+            let status = if elapsed > COMPILE_ERROR {
+                "error"
+            } else if elapsed > COMPILE_WARNING {
+                "warning"
+            } else {
+                "ok"
+            };
+            if status == "error" {
+                all_good = false;
+            }
+            let mut check = json!({
+                "name": "Compile Benchmark",
+                "status": status,
+                "value": format!("{}ms", elapsed_ms),
+                "compile_time_ms": elapsed_ms,
+                "message": "Compiled a hello-world program with rustc"
+            });
+            if status != "ok" {
+                check["suggestion"] = json!(
+                    "rustc is unusually slow - check for antivirus scanning, network filesystems or a slow temp directory"
+                );
+            }
+            checks.push(check);
+        }
+        Err(e) => {
+            all_good = false;
+            checks.push(json!({
+                "name": "Compile Benchmark",
+                "status": "error",
+                "message": format!("Could not compile a hello-world program: {}", e)
+            }));
+        }
+    }
+
     // Check if cargo is available
     match run_command("cargo", &["--version"]) {
         Ok(output) => {
@@ -79,6 +150,14 @@ pub async fn run(json_output: bool) -> Result<()> {
         }
     }
 
+    // Check that every installed toolchain's sysroot is intact
+    for check in check_toolchain_sysroots() {
+        if check["status"] == "error" {
+            all_good = false;
+        }
+        checks.push(check);
+    }
+
     // Check essential tools
     let tools = [
         ("clippy", "cargo clippy --version"),
@@ -108,6 +187,22 @@ pub async fn run(json_output: bool) -> Result<()> {
         }
     }
 
+    // Optional tools used by `oxy check`
+    if cargo_subcommand_installed("machete") {
+        checks.push(json!({
+            "name": "Tool: cargo-machete",
+            "status": "ok",
+            "message": "cargo-machete is available for unused dependency checks"
+        }));
+    } else {
+        checks.push(json!({
+            "name": "Tool: cargo-machete",
+            "status": "info",
+            "message": "cargo-machete not installed - `oxy check` skips unused dependency detection",
+            "suggestion": "Install with: cargo install cargo-machete"
+        }));
+    }
+
     // Check environment variables
     let env_vars = ["CARGO_HOME", "RUSTUP_HOME", "PATH"];
     for var in &env_vars {
@@ -141,6 +236,90 @@ pub async fn run(json_output: bool) -> Result<()> {
         }
     }
 
+    // The registry cache is thousands of small files, which network filesystems are slow at
+    if let Some((mount_point, fs_type)) = cargo_home_mount() {
+        if is_network_filesystem(&fs_type) {
+            checks.push(json!({
+                "name": "Cargo Home Filesystem",
+                "status": "warning",
+                "kind": "performance_warning",
+                "value": fs_type,
+                "message": format!(
+                    "CARGO_HOME is on a network filesystem ({} mounted at {})",
+                    fs_type, mount_point
+                ),
+                "suggestion": "Move CARGO_HOME to a local disk, e.g. export CARGO_HOME=/var/tmp/cargo"
+            }));
+        } else {
+            checks.push(json!({
+                "name": "Cargo Home Filesystem",
+                "status": "ok",
+                "value": fs_type,
+                "message": "CARGO_HOME is on a local filesystem"
+            }));
+        }
+    }
+
+    // Check network latency to crates.io
+    let proxy = ["HTTPS_PROXY", "https_proxy"]
+        .iter()
+        .find_map(|var| env::var(var).ok())
+        .filter(|value| !value.is_empty());
+    if let Some(proxy) = &proxy {
+        checks.push(json!({
+            "name": "Network: Proxy",
+            "status": "info",
+            "value": proxy,
+            "message": "HTTPS_PROXY is set - direct connect times may not reflect cargo's downloads"
+        }));
+    }
+
+    for host in CRATES_IO_HOSTS {
+        match measure_latency(host) {
+            Ok(latency) => {
+                let latency_ms = latency.as_millis() as u64;
+                // Through a proxy, direct connects say little about cargo's downloads
+                let status = if latency > LATENCY_ERROR && proxy.is_none() {
+                    "error"
+                } else if latency > LATENCY_WARNING {
+                    "warning"
+                } else {
+                    "ok"
+                };
+                if status == "error" {
+                    all_good = false;
+                }
+                checks.push(json!({
+                    "name": format!("Network: {}", host),
+                    "status": status,
+                    "value": format!("{}ms", latency_ms),
+                    "latency_ms": latency_ms,
+                    "message": format!("Connected to {}:443", host)
+                }));
+            }
+            Err(e) if proxy.is_some() => {
+                checks.push(json!({
+                    "name": format!("Network: {}", host),
+                    "status": "warning",
+                    "message": format!(
+                        "Could not connect to {}:443 directly: {} (cargo goes through HTTPS_PROXY)",
+                        host, e
+                    ),
+                    "suggestion": "If cargo can't download crates either, check your proxy settings"
+                }));
+            }
+            Err(e) => {
+                all_good = false;
+                checks.push(json!({
+                    "name": format!("Network: {}", host),
+                    "status": "error",
+                    "message": format!("Could not connect to {}:443: {}", host, e),
+                    "suggestion": "Check your network connection, DNS, or proxy settings"
+                }));
+            }
+        }
+    }
+
     // Check current directory
     if Path::new("Cargo.toml").exists() {
         checks.push(json!({
@@ -156,10 +335,175 @@ pub async fn run(json_output: bool) -> Result<()> {
         }));
     }
 
+    // Check for outdated editions
+    if let Ok(manifest) = read_manifest(Path::new("Cargo.toml"))
+        && let Some(edition) = manifest
+            .get("package")
+            .and_then(|p| p.get("edition"))
+            .and_then(|e| e.as_str())
+    {
+        if edition == "2015" || edition == "2018" {
+            checks.push(json!({
+                "name": "Rust Edition",
+                "status": "warning",
+                "value": edition,
+                "message": format!("Project uses the {} edition", edition),
+                "suggestion": "Upgrade with `cargo fix --edition` and bump `edition` in Cargo.toml (guide: https://doc.rust-lang.org/edition-guide/editions/transitioning-an-existing-project-to-a-new-edition.html)"
+            }));
+        } else {
+            checks.push(json!({
+                "name": "Rust Edition",
+                "status": "ok",
+                "value": edition,
+                "message": "Project uses a current edition"
+            }));
+        }
+    }
+
+    // Check for `extern crate`, which 2018 and later editions don't need
+    let modern_edition = is_edition_2018_or_later();
+    let extern_crates = if modern_edition {
+        find_extern_crates()
+    } else {
+        Vec::new()
+    };
+    if modern_edition {
+        if extern_crates.is_empty() {
+            checks.push(json!({
+                "name": "Extern Crate",
+                "status": "ok",
+                "message": "No unnecessary extern crate declarations",
+                "action_needed": false
+            }));
+        } else {
+            let locations: Vec<String> = extern_crates
+                .iter()
+                .map(|occurrence| {
+                    format!(
+                        "{}:{}",
+                        occurrence["file"].as_str().unwrap_or(""),
+                        occurrence["line"]
+                    )
+                })
+                .collect();
+            checks.push(json!({
+                "name": "Extern Crate",
+                "status": "warning",
+                "value": locations.join(", "),
+                "message": format!("{} extern crate declaration(s) are unnecessary since the 2018 edition", extern_crates.len()),
+                "suggestion": "Remove them; dependencies are in scope without `extern crate`, and `#[macro_use]` macros can be imported with `use`",
+                "action_needed": true
+            }));
+        }
+    }
+
+    // Check for incremental compilation in release builds
+    if Path::new("Cargo.toml").exists() {
+        let sources = release_incremental_sources();
+        if sources.is_empty() {
+            checks.push(json!({
+                "name": "Release Incremental",
+                "status": "ok",
+                "message": "Release builds don't use incremental compilation"
+            }));
+        } else {
+            checks.push(json!({
+                "name": "Release Incremental",
+                "status": "warning",
+                "value": sources.join(", "),
+                "message": "Incremental compilation is enabled for release builds",
+                "explanation": "Incremental builds split each crate into many small codegen units so they can be reused between builds, which limits inlining and optimization and makes release binaries slower",
+                "suggestion": "Remove `incremental = true` from [profile.release] and unset CARGO_INCREMENTAL for release builds"
+            }));
+        }
+    }
+
+    // Check for cargo-vet audits
+    if Path::new("Cargo.toml").exists() {
+        if Path::new("supply-chain").is_dir() {
+            checks.push(json!({
+                "name": "Supply Chain",
+                "status": "ok",
+                "message": "cargo-vet audits found in supply-chain/"
+            }));
+        } else {
+            checks.push(json!({
+                "name": "Supply Chain",
+                "status": "info",
+                "message": "No supply-chain/ directory - run `oxy vet init` to start auditing dependencies"
+            }));
+        }
+    }
+
+    // Check whether proc-macro dependencies opt out of unsafe code
+    let proc_macro_crates = if Path::new("Cargo.lock").exists() {
+        find_proc_macro_crates()
+    } else {
+        Vec::new()
+    };
+    if !proc_macro_crates.is_empty() {
+        let allowing_unsafe: Vec<String> = proc_macro_crates
+            .iter()
+            .filter(|krate| krate["denies_unsafe"] == false)
+            .filter_map(|krate| {
+                Some(format!(
+                    "{} {}",
+                    krate["name"].as_str()?,
+                    krate["version"].as_str()?
+                ))
+            })
+            .collect();
+        if allowing_unsafe.is_empty() {
+            checks.push(json!({
+                "name": "Proc Macro Unsafe",
+                "status": "ok",
+                "message": format!("All {} proc-macro crates deny unsafe code", proc_macro_crates.len())
+            }));
+        } else {
+            checks.push(json!({
+                "name": "Proc Macro Unsafe",
+                "status": "info",
+                "value": allowing_unsafe.join(", "),
+                "message": format!(
+                    "{} of {} proc-macro crates don't declare #![deny(unsafe_code)]",
+                    allowing_unsafe.len(),
+                    proc_macro_crates.len()
+                ),
+                "explanation": "Proc macros run inside the compiler with full access to the build machine, so crates that rule out unsafe code are easier to trust"
+            }));
+        }
+    }
+
+    // Check that cross-compilation targets have a linker configured
+    let targets_without_linker = find_targets_without_linker();
+    for target in &targets_without_linker {
+        let triple = target["triple"].as_str().unwrap_or("unknown");
+        let mut suggestion = format!(
+            "Add `[target.{}] linker = \"...\"` to .cargo/config.toml or set CARGO_TARGET_{}_LINKER",
+            triple,
+            triple.to_uppercase().replace(['-', '.'], "_")
+        );
+        if let Some(linker) = target["suggested_linker"].as_str() {
+            suggestion = format!("Set the linker to `{}` in .cargo/config.toml", linker);
+            if let Some(package) = target["distro_package"].as_str() {
+                suggestion.push_str(&format!(" (install the `{}` package)", package));
+            }
+        }
+        checks.push(json!({
+            "name": format!("Cross Linker: {}", triple),
+            "status": "warning",
+            "message": "No linker configured for this target",
+            "suggestion": suggestion
+        }));
+    }
+
     if json_output {
         output_json(&json!({
             "overall_status": if all_good { "healthy" } else { "issues_found" },
-            "checks": checks
+            "checks": checks,
+            "targets_without_linker": targets_without_linker,
+            "proc_macro_crates": proc_macro_crates,
+            "extern_crate_occurrences": extern_crates
         }));
     } else {
         if all_good {
@@ -188,6 +532,13 @@ pub async fn run(json_output: bool) -> Result<()> {
             } else {
                 output_text(&format!("{} {}: {} ({})", icon, name, message, value));
             }
+
+            if let Some(explanation) = check.get("explanation").and_then(|e| e.as_str()) {
+                output_text(&format!("   {}", explanation));
+            }
+            if let Some(suggestion) = check.get("suggestion").and_then(|s| s.as_str()) {
+                output_text(&format!("   💡 {}", suggestion));
+            }
         }
 
         if !all_good {
@@ -201,3 +552,485 @@ pub async fn run(json_output: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Mount point and filesystem type holding the cargo registry, from /proc/mounts. Only
+/// Linux has that file, so this is `None` elsewhere.
+fn cargo_home_mount() -> Option<(String, String)> {
+    let cargo_home = cargo_home()?;
+    let registry = cargo_home.join("registry");
+    let path = if registry.exists() {
+        registry
+    } else {
+        cargo_home
+    };
+    let path = path.canonicalize().ok()?;
+    let mounts = std::fs::read_to_string("/proc/mounts").ok()?;
+    mount_for_path(&mounts, &path)
+}
+
+/// The `/proc/mounts` entry whose mount point is the deepest ancestor of `path`, as
+/// `(mount point, filesystem type)`.
+pub fn mount_for_path(mounts: &str, path: &Path) -> Option<(String, String)> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_device, mount_point, fs_type) = (fields.next()?, fields.next()?, fields.next()?);
+            // Spaces and other special characters in mount points are octal escapes
+            let mount_point = mount_point
+                .replace("\\040", " ")
+                .replace("\\011", "\t")
+                .replace("\\134", "\\");
+            Some((mount_point, fs_type.to_string()))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| Path::new(mount_point).components().count())
+}
+
+/// Whether a /proc/mounts filesystem type is a network mount, including FUSE ones like
+/// `fuse.sshfs`.
+pub fn is_network_filesystem(fs_type: &str) -> bool {
+    let fs_type = fs_type.strip_prefix("fuse.").unwrap_or(fs_type);
+    NETWORK_FILESYSTEMS.contains(&fs_type)
+}
+
+/// Proc-macro crates in Cargo.lock whose sources are in the registry cache, as
+/// `{name, version, denies_unsafe}`.
+fn find_proc_macro_crates() -> Vec<serde_json::Value> {
+    let Ok(packages) = read_lockfile(Path::new("Cargo.lock")) else {
+        return Vec::new();
+    };
+    packages
+        .iter()
+        .filter(|package| package.source.is_some())
+        .filter_map(|package| {
+            let dir = registry_source_dir(&package.name, &package.version)?;
+            let manifest = read_manifest(&dir.join("Cargo.toml")).ok()?;
+            let lib = manifest.get("lib")?;
+            // Older manifests spell it `proc_macro`
+            let proc_macro = lib.get("proc-macro").or_else(|| lib.get("proc_macro"));
+            if proc_macro.and_then(|p| p.as_bool()) != Some(true) {
+                return None;
+            }
+            let lib_path = lib
+                .get("path")
+                .and_then(|p| p.as_str())
+                .unwrap_or("src/lib.rs");
+            let source = std::fs::read_to_string(dir.join(lib_path)).unwrap_or_default();
+            Some(json!({
+                "name": package.name,
+                "version": package.version,
+                "denies_unsafe": denies_unsafe_code(&source)
+            }))
+        })
+        .collect()
+}
+
+/// Whether a crate root has `#![deny(unsafe_code)]` or `#![forbid(unsafe_code)]`, possibly
+/// among other lints.
+pub fn denies_unsafe_code(source: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim();
+        ["#![deny(", "#![forbid("].iter().any(|prefix| {
+            line.strip_prefix(prefix)
+                .and_then(|rest| rest.split_once(')'))
+                .is_some_and(|(lints, _)| lints.split(',').any(|lint| lint.trim() == "unsafe_code"))
+        })
+    })
+}
+
+/// Whether the package's edition (inherited from `[workspace.package]` if need be) is 2018
+/// or later; cargo defaults to 2015 when it isn't set.
+fn is_edition_2018_or_later() -> bool {
+    let Ok(manifest) = read_manifest(Path::new("Cargo.toml")) else {
+        return false;
+    };
+    let edition = match manifest.get("package").and_then(|p| p.get("edition")) {
+        Some(toml::Value::String(edition)) => Some(edition.as_str()),
+        Some(toml::Value::Table(_)) => manifest
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("edition"))
+            .and_then(|e| e.as_str()),
+        _ => None,
+    };
+    edition.is_some_and(|edition| edition != "2015")
+}
+
+/// `extern crate` declarations under src/ as `{file, line, declaration}`.
+fn find_extern_crates() -> Vec<serde_json::Value> {
+    let mut files = find_files(Path::new("src"), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    });
+    files.sort();
+    files
+        .iter()
+        .filter_map(|path| Some((path, std::fs::read_to_string(path).ok()?)))
+        .flat_map(|(path, source)| {
+            extern_crate_declarations(&source)
+                .into_iter()
+                .map(move |(line, declaration)| {
+                    json!({
+                        "file": path.display().to_string(),
+                        "line": line,
+                        "declaration": declaration
+                    })
+                })
+        })
+        .collect()
+}
+
+/// `extern crate` lines in `source` as (1-based line, declaration), leaving out the
+/// sysroot crates that still need one (`alloc` in `no_std` crates, `test` for benches),
+/// `extern crate self as ...` aliases, and declarations behind `#[cfg(test)]`.
+pub fn extern_crate_declarations(source: &str) -> Vec<(usize, String)> {
+    const STILL_NEEDED: [&str; 5] = ["std", "core", "alloc", "test", "self"];
+
+    let mut declarations = Vec::new();
+    let mut depth = 0usize;
+    // Brace depth of the body of an enclosing `#[cfg(test)]` item
+    let mut test_depth: Option<usize> = None;
+    let mut cfg_test_pending = false;
+
+    for (i, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") {
+            continue;
+        }
+        if trimmed.starts_with("#[cfg(test)]") {
+            cfg_test_pending = true;
+        } else if cfg_test_pending && !trimmed.starts_with("#[") && !trimmed.is_empty() {
+            cfg_test_pending = false;
+            if trimmed.contains('{') && test_depth.is_none() {
+                test_depth = Some(depth + 1);
+            } else if trimmed.contains("extern crate ") {
+                continue;
+            }
+        }
+
+        let item = trimmed
+            .strip_prefix("pub(crate) ")
+            .or_else(|| trimmed.strip_prefix("pub "))
+            .unwrap_or(trimmed);
+        if let Some(rest) = item.strip_prefix("extern crate ")
+            && test_depth.is_none_or(|test_depth| depth < test_depth)
+        {
+            let name = rest.split([' ', ';']).next().unwrap_or("");
+            if !STILL_NEEDED.contains(&name) {
+                declarations.push((i + 1, trimmed.to_string()));
+            }
+        }
+
+        for c in trimmed.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    if test_depth == Some(depth) {
+                        test_depth = None;
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+        }
+    }
+    declarations
+}
+
+/// Where incremental compilation is turned on for the release profile: the
+/// `CARGO_INCREMENTAL` override, `CARGO_PROFILE_RELEASE_INCREMENTAL`, or config files.
+fn release_incremental_sources() -> Vec<String> {
+    let mut sources = Vec::new();
+    for var in ["CARGO_INCREMENTAL", "CARGO_PROFILE_RELEASE_INCREMENTAL"] {
+        if env::var(var).is_ok_and(|value| value == "1" || value == "true") {
+            sources.push(var.to_string());
+        }
+    }
+
+    let files = cargo_config_files()
+        .into_iter()
+        .chain(std::iter::once(PathBuf::from("Cargo.toml")));
+    for path in files {
+        let incremental = read_manifest(&path).ok().and_then(|config| {
+            config
+                .get("profile")?
+                .get("release")?
+                .get("incremental")?
+                .as_bool()
+        });
+        if incremental == Some(true) {
+            sources.push(path.display().to_string());
+        }
+    }
+    sources
+}
+
+/// One check per installed toolchain: the sysroot directory exists and its rustc runs.
+fn check_toolchain_sysroots() -> Vec<serde_json::Value> {
+    let Ok(output) = run_command("rustup", &["toolchain", "list"]) else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let toolchains: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "no")
+        .map(String::from)
+        .collect();
+
+    // `rustup check` reports one "<toolchain> - <status>" line per toolchain
+    let updates = run_command("rustup", &["check"])
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+    let toolchains_dir = rustup_home().map(|home| home.join("toolchains"));
+
+    toolchains
+        .iter()
+        .map(|name| {
+            let check_name = format!("Toolchain: {}", name);
+            let suggestion = format!(
+                "Reinstall with: rustup toolchain uninstall {0} && rustup toolchain install {0}",
+                name
+            );
+            let update = updates
+                .lines()
+                .find_map(|line| line.strip_prefix(&format!("{} - ", name)))
+                .map(str::trim);
+
+            let sysroot = toolchains_dir.as_ref().map(|dir| dir.join(name));
+            if let Some(sysroot) = &sysroot
+                && !sysroot.join("bin").is_dir()
+            {
+                return json!({
+                    "name": check_name,
+                    "status": "error",
+                    "message": format!("Sysroot missing at {}", sysroot.display()),
+                    "suggestion": suggestion
+                });
+            }
+
+            match run_command("rustc", &[&format!("+{}", name), "--version"]) {
+                Ok(output) if output.status.success() => {
+                    let mut check = json!({
+                        "name": check_name,
+                        "status": "ok",
+                        "value": String::from_utf8_lossy(&output.stdout).trim(),
+                        "message": "Sysroot is intact",
+                        "sysroot": sysroot
+                    });
+                    if update.is_some_and(|u| u.starts_with("update available")) {
+                        check["message"] = json!("Sysroot is intact (update available)");
+                    }
+                    check
+                }
+                _ => json!({
+                    "name": check_name,
+                    "status": "error",
+                    "message": "rustc from this toolchain fails to run - the install may be corrupt",
+                    "sysroot": sysroot,
+                    "suggestion": suggestion
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Installed rustup targets other than the host that have no linker configured.
+fn find_targets_without_linker() -> Vec<serde_json::Value> {
+    let Some(host) = run_command("rustc", &["-vV"]).ok().and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.strip_prefix("host: ").map(|h| h.trim().to_string()))
+    }) else {
+        return Vec::new();
+    };
+
+    let Ok(output) = run_command("rustup", &["target", "list", "--installed"]) else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let mut config_paths = vec![PathBuf::from(".cargo/config.toml")];
+    if let Some(cargo_home) = cargo_home() {
+        config_paths.push(cargo_home.join("config.toml"));
+    }
+    let cargo_configs: Vec<toml::Value> = config_paths
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter_map(|content| content.parse::<toml::Value>().ok())
+        .collect();
+
+    let distro = detect_distro();
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|triple| !triple.is_empty() && *triple != host)
+        .filter(|triple| needs_external_linker(triple, &host))
+        .filter(|triple| {
+            let env_var = format!(
+                "CARGO_TARGET_{}_LINKER",
+                triple.to_uppercase().replace(['-', '.'], "_")
+            );
+            let configured = cargo_configs.iter().any(|config| {
+                config
+                    .get("target")
+                    .and_then(|t| t.get(*triple))
+                    .and_then(|t| t.get("linker"))
+                    .is_some()
+            });
+            !configured && env::var_os(env_var).is_none()
+        })
+        .map(|triple| {
+            let known = known_cross_linker(triple);
+            json!({
+                "triple": triple,
+                "suggested_linker": known.map(|(linker, _)| linker),
+                "distro_package": known.and_then(|(_, packages)| {
+                    packages
+                        .iter()
+                        .find(|(family, _)| Some(*family) == distro.as_deref())
+                        .map(|(_, package)| *package)
+                })
+            })
+        })
+        .collect()
+}
+
+/// Targets that link with rust-lld or a self-contained toolchain don't need a system linker.
+fn needs_external_linker(triple: &str, host: &str) -> bool {
+    if triple.starts_with("wasm")
+        || triple.starts_with("thumb")
+        || triple.starts_with("nvptx")
+        || triple.contains("-none")
+    {
+        return false;
+    }
+
+    // musl targets for the host architecture ship their own CRT objects
+    let arch = |t: &str| t.split('-').next().unwrap_or("").to_string();
+    !(triple.ends_with("-musl") && arch(triple) == arch(host))
+}
+
+/// Linker and per-distro package (Debian, Fedora, Arch) for common cross targets.
+type KnownLinker = (&'static str, [(&'static str, &'static str); 3]);
+
+fn known_cross_linker(triple: &str) -> Option<KnownLinker> {
+    let known = match triple {
+        "aarch64-unknown-linux-gnu" => (
+            "aarch64-linux-gnu-gcc",
+            [
+                ("debian", "gcc-aarch64-linux-gnu"),
+                ("fedora", "gcc-aarch64-linux-gnu"),
+                ("arch", "aarch64-linux-gnu-gcc"),
+            ],
+        ),
+        "armv7-unknown-linux-gnueabihf" => (
+            "arm-linux-gnueabihf-gcc",
+            [
+                ("debian", "gcc-arm-linux-gnueabihf"),
+                ("fedora", "gcc-arm-linux-gnu"),
+                ("arch", "arm-linux-gnueabihf-gcc"),
+            ],
+        ),
+        "riscv64gc-unknown-linux-gnu" => (
+            "riscv64-linux-gnu-gcc",
+            [
+                ("debian", "gcc-riscv64-linux-gnu"),
+                ("fedora", "gcc-riscv64-linux-gnu"),
+                ("arch", "riscv64-linux-gnu-gcc"),
+            ],
+        ),
+        "x86_64-unknown-linux-gnu" => (
+            "x86_64-linux-gnu-gcc",
+            [
+                ("debian", "gcc-x86-64-linux-gnu"),
+                ("fedora", "gcc-x86_64-linux-gnu"),
+                ("arch", "gcc"),
+            ],
+        ),
+        "x86_64-pc-windows-gnu" => (
+            "x86_64-w64-mingw32-gcc",
+            [
+                ("debian", "gcc-mingw-w64-x86-64"),
+                ("fedora", "mingw64-gcc"),
+                ("arch", "mingw-w64-gcc"),
+            ],
+        ),
+        _ => return None,
+    };
+    Some(known)
+}
+
+/// Distro family from /etc/os-release, reduced to the families `known_cross_linker` covers.
+fn detect_distro() -> Option<String> {
+    let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+    let field = |key: &str| {
+        os_release
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|value| value.trim_matches('"').to_lowercase())
+            .unwrap_or_default()
+    };
+    let ids = format!("{} {}", field("ID="), field("ID_LIKE="));
+
+    ["debian", "fedora", "arch"]
+        .iter()
+        .find(|family| {
+            ids.split_whitespace().any(|id| {
+                id == **family
+                    || (**family == "debian" && id == "ubuntu")
+                    || (**family == "fedora" && (id == "rhel" || id == "centos"))
+            })
+        })
+        .map(|family| family.to_string())
+}
+
+/// Time `rustc main.rs` for a hello-world in a scratch directory, discarding the binary.
+fn benchmark_rustc() -> Result<Duration> {
+    let dir = env::temp_dir().join(format!("oxy-doctor-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let source = dir.join("main.rs");
+    std::fs::write(
+        &source,
+        "fn main() {\n    println!(\"Hello, world!\");\n}\n",
+    )?;
+
+    // Windows can't write to a null device, so keep its binary in the scratch dir
+    let output = if cfg!(windows) {
+        dir.join("main.exe")
+    } else {
+        PathBuf::from("/dev/null")
+    };
+    let started = Instant::now();
+    let result = run_command(
+        "rustc",
+        &[&source.to_string_lossy(), "-o", &output.to_string_lossy()],
+    );
+    let elapsed = started.elapsed();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let result = result?;
+    if !result.status.success() {
+        return Err(anyhow!(
+            "{}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(elapsed)
+}
+
+/// TCP connect time to `host:443`, giving up after `LATENCY_ERROR`.
+fn measure_latency(host: &str) -> std::io::Result<Duration> {
+    let addr = (host, 443).to_socket_addrs()?.next().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "no addresses found")
+    })?;
+
+    let started = Instant::now();
+    TcpStream::connect_timeout(&addr, LATENCY_ERROR)?;
+    Ok(started.elapsed())
+}
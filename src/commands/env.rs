@@ -1,10 +1,102 @@
-use crate::utils::{output_json, output_text, run_command};
-use anyhow::Result;
-use serde_json::json;
+use crate::commands::sbom::iso8601_utc;
+use crate::history::unix_timestamp;
+use crate::utils::{
+    LockedPackage, cargo_config_files, cargo_home, find_files, format_bytes, get_dir_size,
+    output_json, output_text, read_lockfile, run_command,
+};
+use crate::EnvAction;
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use std::collections::HashSet;
 use std::env;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tokio::task::spawn_blocking;
+use tracing::{info, warn};
 
-pub async fn run(json_output: bool) -> Result<()> {
+/// Cargo config keys whose value here is cargo's built-in default, so setting them has no
+/// effect. Values are in TOML syntax.
+const CARGO_CONFIG_DEFAULTS: &[(&str, &str)] = &[
+    ("build.incremental", "true"),
+    ("build.target-dir", "\"target\""),
+    ("cargo-new.vcs", "\"git\""),
+    ("http.check-revoke", "true"),
+    ("http.low-speed-limit", "10"),
+    ("http.multiplexing", "true"),
+    ("http.timeout", "30"),
+    ("net.git-fetch-with-cli", "false"),
+    ("net.offline", "false"),
+    ("net.retry", "3"),
+    ("term.color", "\"auto\""),
+];
+
+/// Cargo config keys that often cause surprising builds, with what to look out for. Keys
+/// ending in `.` match every key under that table.
+const MISCONFIGURED_KEYS: &[(&str, &str)] = &[
+    (
+        "build.rustflags",
+        "ignored whenever RUSTFLAGS is set, and changing it rebuilds everything",
+    ),
+    (
+        "build.target",
+        "every build cross-compiles to this target unless --target is given",
+    ),
+    ("build.target-dir", "build artifacts don't go to ./target"),
+    (
+        "http.proxy",
+        "all downloads go through this proxy, so a stale one breaks fetching",
+    ),
+    (
+        "http.check-revoke",
+        "disabling it skips TLS certificate revocation checks",
+    ),
+    (
+        "net.offline",
+        "cargo won't download dependencies that aren't cached",
+    ),
+    (
+        "net.retry",
+        "controls how often failed downloads are retried before giving up",
+    ),
+    ("source.", "replaces where dependencies are downloaded from"),
+];
+
+/// Sections of `oxy env report`, as (oxy subcommand, heading).
+const REPORT_SECTIONS: [(&str, &str); 4] = [
+    ("env", "Environment"),
+    ("doctor", "Diagnostics"),
+    ("info", "Project"),
+    ("tools", "Tools"),
+];
+
+const REPORT_CSS: &str = "body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 1100px; color: #1f2328; background: #f6f8fa; }
+h1 { margin-bottom: 0.25rem; }
+.meta { color: #656d76; margin-top: 0; }
+section { background: #fff; border: 1px solid #d0d7de; border-radius: 6px; padding: 1rem 1.5rem; margin: 1.5rem 0; }
+table { border-collapse: collapse; width: 100%; margin: 0.25rem 0; }
+th, td { text-align: left; vertical-align: top; padding: 0.3rem 0.6rem; border-bottom: 1px solid #eaeef2; }
+th { color: #656d76; font-weight: 600; white-space: nowrap; }
+td table { margin: 0; }
+pre { margin: 0; white-space: pre-wrap; font-size: 0.85em; }
+.ok { color: #1a7f37; font-weight: 600; }
+.warning { color: #9a6700; font-weight: 600; }
+.error { color: #cf222e; font-weight: 600; }
+.missing { color: #8c959f; }
+";
+
+pub async fn run(
+    action: Option<EnvAction>,
+    effective_rustflags: bool,
+    json_output: bool,
+) -> Result<()> {
+    match action {
+        None if effective_rustflags => show_effective_rustflags(json_output).await,
+        None => show_environment(json_output).await,
+        Some(EnvAction::Clean { confirm }) => clean_caches(confirm, json_output).await,
+        Some(EnvAction::Report { output }) => write_report(&output, json_output).await,
+    }
+}
+
+async fn show_environment(json_output: bool) -> Result<()> {
     info!("Gathering Rust environment information...");
 
     let mut env_info = json!({});
@@ -63,6 +155,20 @@ pub async fn run(json_output: bool) -> Result<()> {
     }
     env_info["environment"] = env_vars;
 
+    let project_config = [".cargo/config.toml", ".cargo/config"]
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file());
+    let global_config = cargo_home().and_then(|home| {
+        [home.join("config.toml"), home.join("config")]
+            .into_iter()
+            .find(|path| path.is_file())
+    });
+    env_info["cargo_config"] = json!({
+        "project": project_config.as_deref().map(cargo_config_summary),
+        "global": global_config.as_deref().map(cargo_config_summary)
+    });
+
     if json_output {
         output_json(&env_info);
     } else {
@@ -92,7 +198,582 @@ pub async fn run(json_output: bool) -> Result<()> {
                 output_text(&format!("  {}: {}", key, value.as_str().unwrap_or("N/A")));
             }
         }
+
+        for (label, scope) in [("Project", "project"), ("Global", "global")] {
+            let config = &env_info["cargo_config"][scope];
+            output_text("");
+            let Some(path) = config["path"].as_str() else {
+                output_text(&format!("{} Cargo Config: none", label));
+                continue;
+            };
+            output_text(&format!("{} Cargo Config ({}):", label, path));
+            if let Some(error) = config["error"].as_str() {
+                output_text(&format!("  ❌ {}", error));
+                continue;
+            }
+            let settings = config["settings"].as_array().cloned().unwrap_or_default();
+            if settings.is_empty() {
+                output_text("  (empty)");
+            }
+            for setting in &settings {
+                let line = format!(
+                    "  {} = {}",
+                    setting["key"].as_str().unwrap_or(""),
+                    setting["value"].as_str().unwrap_or("")
+                );
+                if let Some(warning) = setting["warning"].as_str() {
+                    output_text(&format!("{}  ⚠️  {}", line, warning));
+                } else if setting["is_default"] == true {
+                    output_text(&format!("{}  (cargo's default)", line));
+                } else {
+                    output_text(&line);
+                }
+            }
+        }
     }
 
     Ok(())
 }
+
+/// A cargo config file as `{path, settings}`, or `{path, error}` if it can't be parsed.
+fn cargo_config_summary(path: &Path) -> Value {
+    let config = std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Ok(content.parse::<toml::Value>()?));
+    match config {
+        Ok(config) => json!({
+            "path": path,
+            "settings": cargo_config_settings(&config)
+        }),
+        Err(e) => json!({
+            "path": path,
+            "error": format!("Failed to parse: {}", e)
+        }),
+    }
+}
+
+/// Every setting in a cargo config as `{key, value, is_default, warning}`, with dotted keys
+/// and TOML-formatted values. `warning` is set for keys listed in `MISCONFIGURED_KEYS`.
+pub fn cargo_config_settings(config: &toml::Value) -> Vec<Value> {
+    fn flatten(value: &toml::Value, prefix: &str, settings: &mut Vec<(String, toml::Value)>) {
+        match value.as_table() {
+            Some(table) => {
+                for (key, value) in table {
+                    let key = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    flatten(value, &key, settings);
+                }
+            }
+            None => settings.push((prefix.to_string(), value.clone())),
+        }
+    }
+    let mut settings = Vec::new();
+    flatten(config, "", &mut settings);
+
+    settings
+        .into_iter()
+        .map(|(key, value)| {
+            let value = value.to_string();
+            let is_default = CARGO_CONFIG_DEFAULTS
+                .iter()
+                .any(|(default_key, default)| *default_key == key && *default == value);
+            let warning = MISCONFIGURED_KEYS
+                .iter()
+                .find(|(pattern, _)| {
+                    *pattern == key || (pattern.ends_with('.') && key.starts_with(pattern))
+                })
+                .filter(|_| !is_default)
+                .map(|(_, warning)| *warning);
+            json!({
+                "key": key,
+                "value": value,
+                "is_default": is_default,
+                "warning": warning
+            })
+        })
+        .collect()
+}
+
+async fn show_effective_rustflags(json_output: bool) -> Result<()> {
+    info!("Resolving RUSTFLAGS from the environment and cargo config...");
+
+    let encoded = env::var("CARGO_ENCODED_RUSTFLAGS").ok();
+    let from_env = env::var("RUSTFLAGS").ok();
+    let (config, config_files) = config_rustflags();
+    let (source, effective) = effective_rustflags(encoded.as_deref(), from_env.as_deref(), &config);
+
+    if json_output {
+        output_json(&json!({
+            "sources": {
+                "env": from_env,
+                "config": config,
+                "encoded": encoded
+            },
+            "config_files": config_files,
+            "effective_source": source,
+            "effective": effective
+        }));
+        return Ok(());
+    }
+
+    output_text("🚩 Effective RUSTFLAGS");
+    output_text("======================");
+    output_text(&format!(
+        "  CARGO_ENCODED_RUSTFLAGS: {}",
+        encoded
+            .as_deref()
+            .map(|flags| flags.replace('\x1f', " "))
+            .unwrap_or_else(|| "(unset)".to_string())
+    ));
+    output_text(&format!(
+        "  RUSTFLAGS:               {}",
+        from_env.as_deref().unwrap_or("(unset)")
+    ));
+    output_text(&format!(
+        "  cargo config:            {}",
+        if config.is_empty() {
+            "(none)".to_string()
+        } else {
+            config.join(" ")
+        }
+    ));
+    for path in &config_files {
+        output_text(&format!("    from {}", path.display()));
+    }
+    output_text("");
+
+    if effective.is_empty() {
+        output_text("✅ No extra flags are passed to rustc");
+    } else {
+        output_text(&format!("✅ rustc receives (from {}):", source));
+        output_text(&format!("   {}", effective.join(" ")));
+    }
+    if source != "config" && !config.is_empty() {
+        output_text(&format!(
+            "⚠️  The cargo config rustflags are ignored because {} is set",
+            source
+        ));
+    }
+
+    Ok(())
+}
+
+/// Flags from `target.<host>.rustflags`, or `build.rustflags` when no target entry
+/// exists, merged across every cargo config file that applies to this directory.
+/// `CARGO_BUILD_RUSTFLAGS` overrides `build.rustflags`, like any config env var.
+fn config_rustflags() -> (Vec<String>, Vec<PathBuf>) {
+    let host = run_command("rustc", &["-vV"]).ok().and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.strip_prefix("host: ").map(|h| h.trim().to_string()))
+    });
+
+    let configs: Vec<(PathBuf, toml::Value)> = cargo_config_files()
+        .into_iter()
+        .filter_map(|path| {
+            let config = std::fs::read_to_string(&path).ok()?.parse().ok()?;
+            Some((path, config))
+        })
+        .collect();
+
+    let collect = |lookup: &dyn Fn(&toml::Value) -> Option<&toml::Value>| {
+        let mut flags = Vec::new();
+        let mut files = Vec::new();
+        for (path, config) in &configs {
+            if let Some(value) = lookup(config) {
+                flags.extend(rustflags_value(value));
+                files.push(path.clone());
+            }
+        }
+        (flags, files)
+    };
+
+    if let Some(host) = &host {
+        let target = collect(&|config| config.get("target")?.get(host)?.get("rustflags"));
+        if !target.1.is_empty() {
+            return target;
+        }
+    }
+    if let Ok(flags) = env::var("CARGO_BUILD_RUSTFLAGS") {
+        return (
+            flags.split_whitespace().map(String::from).collect(),
+            Vec::new(),
+        );
+    }
+    collect(&|config| config.get("build")?.get("rustflags"))
+}
+
+/// A `rustflags` config value, which may be a space-separated string or an array.
+fn rustflags_value(value: &toml::Value) -> Vec<String> {
+    match value {
+        toml::Value::String(flags) => flags.split_whitespace().map(String::from).collect(),
+        toml::Value::Array(flags) => flags
+            .iter()
+            .filter_map(|flag| flag.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Apply cargo's precedence: `CARGO_ENCODED_RUSTFLAGS`, then `RUSTFLAGS`, then cargo
+/// config. The first source that is set wins outright; sources are never combined.
+/// Returns the winning source's name along with the flags.
+pub fn effective_rustflags(
+    encoded: Option<&str>,
+    env: Option<&str>,
+    config: &[String],
+) -> (&'static str, Vec<String>) {
+    if let Some(encoded) = encoded {
+        let flags = encoded
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(String::from)
+            .collect();
+        return ("encoded", flags);
+    }
+    if let Some(env) = env {
+        return ("env", env.split_whitespace().map(String::from).collect());
+    }
+    ("config", config.to_vec())
+}
+
+async fn write_report(output: &Path, json_output: bool) -> Result<()> {
+    info!("Collecting environment report...");
+
+    // Each section is the JSON output of running this oxy binary's own subcommand
+    let exe = env::current_exe().context("Failed to locate the oxy executable")?;
+    let handles: Vec<_> = REPORT_SECTIONS
+        .iter()
+        .map(|(command, _)| {
+            let exe = exe.clone();
+            spawn_blocking(move || {
+                let output = run_command(&exe.to_string_lossy(), &["--json", command]).ok()?;
+                json_document(&String::from_utf8_lossy(&output.stdout))
+            })
+        })
+        .collect();
+    let mut sections = Vec::new();
+    for ((_, heading), handle) in REPORT_SECTIONS.iter().zip(handles) {
+        sections.push((*heading, handle.await.ok().flatten().unwrap_or(Value::Null)));
+    }
+
+    let html = report_html(&sections, &iso8601_utc(unix_timestamp()));
+    std::fs::write(output, html)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    let missing: Vec<&str> = sections
+        .iter()
+        .filter(|(_, value)| value.is_null())
+        .map(|(heading, _)| *heading)
+        .collect();
+
+    if json_output {
+        output_json(&json!({
+            "written_to": output,
+            "sections": REPORT_SECTIONS.iter().map(|(command, _)| command).collect::<Vec<_>>(),
+            "missing_sections": missing
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Wrote environment report to {}",
+            output.display()
+        ));
+        if !missing.is_empty() {
+            output_text(&format!(
+                "⚠️  Sections without data: {}",
+                missing.join(", ")
+            ));
+        }
+        output_text("💡 Attach it when filing an issue about your development environment");
+    }
+
+    Ok(())
+}
+
+/// The pretty-printed JSON document at the end of an `oxy --json` run's stdout, after any
+/// log lines.
+pub fn json_document(stdout: &str) -> Option<Value> {
+    let start = stdout
+        .lines()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, line))
+        })
+        .find(|(_, line)| *line == "{")
+        .map(|(start, _)| start)?;
+    serde_json::from_str(&stdout[start..]).ok()
+}
+
+/// A standalone HTML page with one section per `(heading, data)`, rendering objects as
+/// key/value tables and arrays of objects as tables with a column per key.
+pub fn report_html(sections: &[(&str, Value)], generated: &str) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>oxygen environment report</title>\n<style>\n{}</style>\n</head>\n<body>\n<h1>oxygen environment report</h1>\n<p class=\"meta\">Generated {} by oxygen {}</p>\n",
+        REPORT_CSS,
+        escape_html(generated),
+        env!("CARGO_PKG_VERSION")
+    );
+    for (heading, data) in sections {
+        html.push_str(&format!("<section>\n<h2>{}</h2>\n", escape_html(heading)));
+        if data.is_null() {
+            html.push_str("<p class=\"missing\">Not available</p>\n");
+        } else {
+            html.push_str(&json_html(data));
+            html.push('\n');
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn json_html(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let rows: String = map
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "<tr><th>{}</th><td{}>{}</td></tr>",
+                        escape_html(key),
+                        status_class(key, value),
+                        json_html(value)
+                    )
+                })
+                .collect();
+            format!("<table>{}</table>", rows)
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            let mut columns: Vec<&String> = Vec::new();
+            for key in items
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(|item| item.keys())
+            {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+            let header: String = columns
+                .iter()
+                .map(|column| format!("<th>{}</th>", escape_html(column)))
+                .collect();
+            let rows: String = items
+                .iter()
+                .map(|item| {
+                    let cells: String = columns
+                        .iter()
+                        .map(|column| {
+                            let value = item.get(column.as_str()).unwrap_or(&Value::Null);
+                            format!(
+                                "<td{}>{}</td>",
+                                status_class(column, value),
+                                json_html(value)
+                            )
+                        })
+                        .collect();
+                    format!("<tr>{}</tr>", cells)
+                })
+                .collect();
+            format!("<table><tr>{}</tr>{}</table>", header, rows)
+        }
+        Value::Array(items) => items.iter().map(json_html).collect::<Vec<_>>().join(", "),
+        Value::String(text) if text.contains('\n') => format!("<pre>{}</pre>", escape_html(text)),
+        Value::String(text) => escape_html(text),
+        Value::Null => "<span class=\"missing\">-</span>".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Colour for the `status` values doctor and tools report.
+fn status_class(key: &str, value: &Value) -> &'static str {
+    if key != "status" {
+        return "";
+    }
+    match value.as_str() {
+        Some("ok" | "available") => " class=\"ok\"",
+        Some("warning" | "not_found") => " class=\"warning\"",
+        Some("error") => " class=\"error\"",
+        _ => "",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn clean_caches(confirm: bool, json_output: bool) -> Result<()> {
+    info!("Scanning cargo caches...");
+
+    let cargo_home = cargo_home().context("Failed to determine CARGO_HOME")?;
+    let cache_dirs = [
+        ("registry/cache", cargo_home.join("registry").join("cache")),
+        ("registry/src", cargo_home.join("registry").join("src")),
+        ("git", cargo_home.join("git")),
+    ];
+
+    let directories: Vec<_> = cache_dirs
+        .iter()
+        .map(|(name, path)| {
+            let size = get_dir_size(path).unwrap_or(0);
+            json!({
+                "name": name,
+                "path": path,
+                "size_bytes": size,
+                "size_formatted": format_bytes(size)
+            })
+        })
+        .collect();
+
+    // Everything referenced by a lock file below the current directory is kept
+    let lockfiles = find_files(Path::new("."), &|p| p.ends_with("Cargo.lock"));
+    if lockfiles.is_empty() {
+        // Without a lock file nothing counts as referenced, so the whole cache would be stale
+        let message = "No Cargo.lock found under the current directory";
+        let suggestion = "Run `oxy env clean` from a directory containing your projects, \
+                          or `cargo generate-lockfile` in one first";
+        if json_output {
+            output_json(&json!({
+                "error": message,
+                "suggestion": suggestion
+            }));
+        } else {
+            output_text(&format!("❌ {}", message));
+            output_text(&format!("💡 {}", suggestion));
+        }
+        return Err(anyhow!(message));
+    }
+    let mut referenced: Vec<LockedPackage> = Vec::new();
+    for lockfile in &lockfiles {
+        match read_lockfile(lockfile) {
+            Ok(packages) => referenced.extend(packages),
+            Err(e) => warn!("Skipping {}: {:#}", lockfile.display(), e),
+        }
+    }
+
+    let stale = find_stale_entries(&cargo_home, &referenced);
+    let reclaimable: u64 = stale.iter().map(|(_, size)| size).sum();
+
+    let mut bytes_freed = 0;
+    let mut errors = Vec::new();
+    if confirm {
+        for (path, size) in &stale {
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            match result {
+                Ok(()) => bytes_freed += size,
+                Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    if json_output {
+        output_json(&json!({
+            "directories": directories,
+            "lockfiles_scanned": lockfiles,
+            "stale_entries": stale.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+            "reclaimable_bytes": reclaimable,
+            "removed": confirm,
+            "bytes_freed": bytes_freed,
+            "errors": errors
+        }));
+    } else {
+        output_text("🧹 Cargo Cache Cleanup");
+        output_text("======================");
+        for dir in &directories {
+            output_text(&format!(
+                "  {:<16} {}",
+                dir["name"].as_str().unwrap_or(""),
+                dir["size_formatted"].as_str().unwrap_or("")
+            ));
+        }
+        output_text("");
+        output_text(&format!(
+            "Scanned {} Cargo.lock file(s) under the current directory",
+            lockfiles.len()
+        ));
+        output_text(&format!(
+            "Stale entries: {} ({})",
+            stale.len(),
+            format_bytes(reclaimable)
+        ));
+
+        if confirm {
+            output_text(&format!("✅ Freed {}", format_bytes(bytes_freed)));
+            for error in &errors {
+                output_text(&format!("  ❌ {}", error));
+            }
+        } else if !stale.is_empty() {
+            output_text("");
+            output_text("💡 Run `oxy env clean --confirm` to delete them");
+            output_text("   Crates used by projects outside this directory will be re-downloaded on demand");
+        }
+    }
+
+    Ok(())
+}
+
+/// Cache entries not referenced by any of the given lock file packages, with their sizes.
+fn find_stale_entries(cargo_home: &Path, referenced: &[LockedPackage]) -> Vec<(PathBuf, u64)> {
+    let referenced_crates: HashSet<String> = referenced
+        .iter()
+        .filter(|p| p.source_kind() != "git" && p.source_kind() != "path")
+        .map(|p| format!("{}-{}", p.name, p.version))
+        .collect();
+    // Git sources end in `#<full commit hash>`; checkouts are stored under a short hash
+    let referenced_revs: Vec<&str> = referenced
+        .iter()
+        .filter_map(|p| p.source.as_deref()?.rsplit_once('#').map(|(_, rev)| rev))
+        .collect();
+
+    let mut stale = Vec::new();
+    let registry = cargo_home.join("registry");
+
+    for index_dir in list_dir(&registry.join("cache")) {
+        for entry in list_dir(&index_dir) {
+            let name = entry.file_name().unwrap_or_default().to_string_lossy();
+            let crate_id = name.trim_end_matches(".crate");
+            if !referenced_crates.contains(crate_id) {
+                let size = std::fs::metadata(&entry).map(|m| m.len()).unwrap_or(0);
+                stale.push((entry, size));
+            }
+        }
+    }
+
+    for index_dir in list_dir(&registry.join("src")) {
+        for entry in list_dir(&index_dir) {
+            let name = entry.file_name().unwrap_or_default().to_string_lossy();
+            if !referenced_crates.contains(name.as_ref()) {
+                let size = get_dir_size(&entry).unwrap_or(0);
+                stale.push((entry, size));
+            }
+        }
+    }
+
+    for repo_dir in list_dir(&cargo_home.join("git").join("checkouts")) {
+        for checkout in list_dir(&repo_dir) {
+            let rev = checkout.file_name().unwrap_or_default().to_string_lossy();
+            if !referenced_revs.iter().any(|r| r.starts_with(rev.as_ref())) {
+                let size = get_dir_size(&checkout).unwrap_or(0);
+                stale.push((checkout, size));
+            }
+        }
+    }
+
+    stale
+}
+
+fn list_dir(path: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,130 @@
+use crate::utils::{output_json, output_text, run_command};
+use anyhow::{Result, anyhow};
+use serde_json::json;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+const ERROR_INDEX_URL: &str = "https://doc.rust-lang.org/error_codes";
+
+pub async fn run(code: &str, json_output: bool) -> Result<()> {
+    let Some(code) = normalize_error_code(code) else {
+        if json_output {
+            output_json(&json!({
+                "error": format!("Invalid error code: {}", code),
+                "suggestion": "Error codes look like E0502"
+            }));
+        } else {
+            output_text(&format!("❌ Invalid error code: {}", code));
+            output_text("💡 Error codes look like E0502");
+        }
+        return Err(anyhow!("Invalid error code: {}", code));
+    };
+
+    let output = run_command("rustc", &["--explain", &code])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim().trim_start_matches("error: ");
+        if json_output {
+            output_json(&json!({
+                "error": message,
+                "code": code
+            }));
+        } else {
+            output_text(&format!("❌ {}", message));
+        }
+        return Err(anyhow!("rustc --explain {} failed", code));
+    }
+
+    let explanation = String::from_utf8_lossy(&output.stdout).to_string();
+    let url = format!("{}/{}.html", ERROR_INDEX_URL, code);
+
+    if json_output {
+        output_json(&json!({
+            "code": code,
+            "explanation": strip_ansi(&explanation).trim_end(),
+            "url": url
+        }));
+        return Ok(());
+    }
+
+    let text = format!("{}\n🔗 {}\n", explanation.trim_end(), url);
+    if !std::io::stdout().is_terminal() || !page(&text) {
+        print!("{}", text);
+    }
+
+    Ok(())
+}
+
+/// Show `text` in `$PAGER` (or `less`), returning false if no pager could be started.
+fn page(text: &str) -> bool {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+
+    let Ok(mut child) = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager closing early (e.g. `q` in less) is not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
+}
+
+/// Accept `E0502`, `e0502` or `502` and return the canonical `E0502` form.
+pub fn normalize_error_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    let digits = code
+        .strip_prefix('E')
+        .or_else(|| code.strip_prefix('e'))
+        .unwrap_or(code);
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("E{:0>4}", digits))
+}
+
+/// Remove ANSI escape sequences (CSI and OSC) from terminal output.
+pub fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters then a final byte in @..~
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: terminated by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
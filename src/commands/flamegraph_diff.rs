@@ -0,0 +1,171 @@
+use crate::utils::{output_json, output_text, run_command};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const DEFAULT_OUTPUT: &str = "flamegraph-diff.svg";
+const TOP_REGRESSIONS: usize = 5;
+
+/// How to get folded stacks and diff them by hand, shown when inferno isn't installed.
+const MANUAL_STEPS: &[&str] = &[
+    "perf record -g --call-graph dwarf <binary>   (once per version)",
+    "perf script | inferno-collapse-perf > before.folded",
+    "inferno-diff-folded before.folded after.folded > diff.folded",
+    "inferno-flamegraph diff.folded > flamegraph-diff.svg",
+];
+
+pub async fn run(
+    baseline: &Path,
+    current: &Path,
+    output: Option<PathBuf>,
+    json_output: bool,
+) -> Result<()> {
+    let mut stacks = Vec::new();
+    for path in [baseline, current] {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // A rendered flamegraph has lost the sample counts per stack
+        if content.trim_start().starts_with('<') {
+            let error = format!("{} is an SVG, not folded stacks", path.display());
+            if json_output {
+                output_json(&json!({
+                    "error": error,
+                    "suggestion": "Collapse the profile with inferno-collapse-perf and pass the .folded file"
+                }));
+            } else {
+                output_text(&format!("❌ {}", error));
+                output_text(
+                    "💡 Collapse the profile with inferno-collapse-perf and pass the .folded file",
+                );
+            }
+            return Err(anyhow!(error));
+        }
+        stacks.push(content);
+    }
+
+    let top_regressions: Vec<_> = frame_deltas(&stacks[0], &stacks[1])
+        .into_iter()
+        .filter(|(_, delta)| *delta > 0)
+        .take(TOP_REGRESSIONS)
+        .map(|(frame, delta)| json!({ "frame": frame, "delta_samples": delta }))
+        .collect();
+
+    let inferno_installed = run_command("inferno-diff-folded", &["--version"]).is_ok()
+        && run_command("inferno-flamegraph", &["--version"]).is_ok();
+    let diff_path = if inferno_installed {
+        let output = output.unwrap_or_else(|| PathBuf::from(DEFAULT_OUTPUT));
+        render_diff(baseline, current, &output, json_output)?;
+        Some(output)
+    } else {
+        None
+    };
+
+    if json_output {
+        let mut result = json!({
+            "diff_path": diff_path,
+            "top_regressions": top_regressions
+        });
+        if !inferno_installed {
+            result["suggestion"] = json!("Install with: cargo install inferno");
+            result["manual_steps"] = json!(MANUAL_STEPS);
+        }
+        output_json(&result);
+        return Ok(());
+    }
+
+    output_text("🔥 Flamegraph Diff");
+    output_text("==================");
+    match &diff_path {
+        Some(path) => {
+            output_text(&format!("✅ Wrote {}", path.display()));
+            output_text("   Red frames got slower, blue frames got faster");
+        }
+        None => {
+            output_text("❌ inferno not installed");
+            output_text("💡 Install with: cargo install inferno");
+            output_text("");
+            output_text("To draw the differential flamegraph by hand:");
+            for (i, step) in MANUAL_STEPS.iter().enumerate() {
+                output_text(&format!("  {}. {}", i + 1, step));
+            }
+        }
+    }
+
+    output_text("");
+    if top_regressions.is_empty() {
+        output_text("✅ No frame gained samples");
+    } else {
+        output_text("🐢 Top regressions (self samples):");
+        for regression in &top_regressions {
+            output_text(&format!(
+                "  {:>+8}  {}",
+                regression["delta_samples"].as_i64().unwrap_or(0),
+                regression["frame"].as_str().unwrap_or("")
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Diff the folded stacks with `inferno-diff-folded` and draw the result as an SVG at
+/// `output`, keeping the diffed stacks next to it.
+fn render_diff(baseline: &Path, current: &Path, output: &Path, json_output: bool) -> Result<()> {
+    info!("Diffing folded stacks with inferno...");
+    let baseline = baseline.to_string_lossy();
+    let current = current.to_string_lossy();
+    let diff = run_command("inferno-diff-folded", &[&baseline, &current])?;
+    let folded = output.with_extension("folded");
+    let rendered = if diff.status.success() {
+        std::fs::write(&folded, &diff.stdout)
+            .with_context(|| format!("Failed to write {}", folded.display()))?;
+        run_command("inferno-flamegraph", &[&folded.to_string_lossy()])?
+    } else {
+        diff
+    };
+
+    if !rendered.status.success() {
+        let stderr = String::from_utf8_lossy(&rendered.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "inferno failed to draw the flamegraph",
+                "details": stderr.trim()
+            }));
+        } else {
+            output_text("❌ inferno failed to draw the flamegraph");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("inferno failed to draw the flamegraph"));
+    }
+    std::fs::write(output, &rendered.stdout)
+        .with_context(|| format!("Failed to write {}", output.display()))
+}
+
+/// Change in self samples (samples with the frame at the top of the stack) per frame
+/// between two sets of folded stacks, largest increase first.
+pub fn frame_deltas(baseline: &str, current: &str) -> Vec<(String, i64)> {
+    let mut deltas: HashMap<&str, i64> = HashMap::new();
+    for (stacks, sign) in [(baseline, -1), (current, 1)] {
+        for line in stacks.lines() {
+            // `frame;frame;frame <count>`
+            let Some((stack, count)) = line.trim().rsplit_once(' ') else {
+                continue;
+            };
+            let Ok(count) = count.parse::<i64>() else {
+                continue;
+            };
+            let frame = stack.rsplit(';').next().unwrap_or(stack);
+            *deltas.entry(frame).or_default() += sign * count;
+        }
+    }
+
+    let mut deltas: Vec<(String, i64)> = deltas
+        .into_iter()
+        .filter(|(_, delta)| *delta != 0)
+        .map(|(frame, delta)| (frame.to_string(), delta))
+        .collect();
+    deltas.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    deltas
+}
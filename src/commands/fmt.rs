@@ -0,0 +1,117 @@
+use crate::FmtAction;
+use crate::utils::{
+    find_files, is_rust_project, output_json, output_text, read_manifest, unified_diff,
+};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::info;
+
+pub async fn run(action: FmtAction, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    match action {
+        FmtAction::ShowDiff => show_diff(json_output).await,
+    }
+}
+
+async fn show_diff(json_output: bool) -> Result<()> {
+    info!("Computing formatting changes...");
+
+    let edition = read_manifest(Path::new("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| {
+            manifest
+                .get("package")
+                .and_then(|p| p.get("edition"))
+                .and_then(|e| e.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| "2021".to_string());
+
+    let rust_files = find_files(Path::new("."), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    });
+
+    let mut files = Vec::new();
+    for path in &rust_files {
+        let display_path = path.strip_prefix(".").unwrap_or(path).display().to_string();
+        let original = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", display_path))?;
+        let formatted = format_source(&original, &edition)
+            .with_context(|| format!("rustfmt failed on {}", display_path))?;
+
+        let diff = unified_diff(&original, &formatted, &display_path);
+        if !diff.is_empty() {
+            files.push((display_path, diff));
+        }
+    }
+
+    if json_output {
+        output_json(&json!({
+            "edition": edition,
+            "files_checked": rust_files.len(),
+            "files": files.iter().map(|(path, diff)| json!({
+                "path": path,
+                "diff_lines": diff.lines().collect::<Vec<_>>()
+            })).collect::<Vec<_>>()
+        }));
+    } else if files.is_empty() {
+        output_text(&format!(
+            "✅ All {} files are formatted correctly",
+            rust_files.len()
+        ));
+    } else {
+        for (_, diff) in &files {
+            output_text(diff.trim_end());
+            output_text("");
+        }
+        output_text(&format!(
+            "📝 {} of {} files would be reformatted",
+            files.len(),
+            rust_files.len()
+        ));
+        output_text("💡 Apply the changes with: cargo fmt");
+    }
+
+    Ok(())
+}
+
+/// Format `source` through rustfmt's stdin so out-of-line modules aren't pulled in.
+fn format_source(source: &str, edition: &str) -> Result<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", edition, "--emit", "stdout"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run rustfmt (install with: rustup component add rustfmt)")?;
+
+    child
+        .stdin
+        .take()
+        .context("Failed to open rustfmt stdin")?
+        .write_all(source.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
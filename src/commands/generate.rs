@@ -0,0 +1,294 @@
+use crate::commands::manifest::is_valid_dependency_name;
+use crate::utils::{
+    confirm, is_rust_project, output_json, output_text, read_lockfile, read_manifest, run_command,
+};
+use crate::GenerateKind;
+use anyhow::{Result, anyhow};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// bindgen release used for the build-dependency when the CLI isn't installed.
+const DEFAULT_BINDGEN_VERSION: &str = "0.71";
+
+const BINDGEN_BUILD_RS: &str = r#"use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", {{header}});
+
+    let bindings = bindgen::Builder::default()
+        .header({{header}})
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
+        .generate()
+        .expect("Unable to generate bindings");
+
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    bindings
+        .write_to_file(manifest_dir.join("bindings.rs"))
+        .expect("Couldn't write bindings");
+}
+"#;
+
+const BINDGEN_LIB_RS: &str = r#"#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+include!("../bindings.rs");
+"#;
+
+pub async fn run(what: GenerateKind, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    match what {
+        GenerateKind::LockfileSummary { output } => {
+            generate_lockfile_summary(output, json_output).await
+        }
+        GenerateKind::Bindgen { header, crate_name } => {
+            generate_bindgen_crate(&header, &crate_name, json_output).await
+        }
+    }
+}
+
+async fn generate_lockfile_summary(output: Option<PathBuf>, json_output: bool) -> Result<()> {
+    info!("Generating Cargo.lock summary...");
+
+    let lock_path = Path::new("Cargo.lock");
+    if !lock_path.exists() {
+        if json_output {
+            output_json(&json!({
+                "error": "Cargo.lock not found",
+                "suggestion": "Run `cargo generate-lockfile` first"
+            }));
+        } else {
+            output_text("❌ Cargo.lock not found");
+            output_text("💡 Run `cargo generate-lockfile` first");
+        }
+        return Err(anyhow!("Cargo.lock not found"));
+    }
+
+    let mut packages = read_lockfile(lock_path)?;
+    packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+    let last_changed = run_command(
+        "git",
+        &["log", "-1", "--format=%cd", "--date=short", "--", "Cargo.lock"],
+    )
+    .ok()
+    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    .filter(|date| !date.is_empty());
+
+    let mut markdown = String::from("# Cargo.lock Summary\n\n");
+    markdown.push_str(&format!("Total packages: {}\n\n", packages.len()));
+    match &last_changed {
+        Some(date) => markdown.push_str(&format!("Lock file last committed: {}\n\n", date)),
+        None => markdown.push_str("Lock file is not tracked in git\n\n"),
+    }
+    markdown.push_str("| Crate | Version | Source |\n");
+    markdown.push_str("|-------|---------|--------|\n");
+    for package in &packages {
+        markdown.push_str(&format!(
+            "| {} | {} | {} |\n",
+            package.name,
+            package.version,
+            package.source_kind()
+        ));
+    }
+
+    if let Some(path) = &output {
+        std::fs::write(path, &markdown)?;
+    }
+
+    if json_output {
+        output_json(&json!({
+            "total_packages": packages.len(),
+            "last_committed": last_changed,
+            "output_path": output,
+            "packages": packages.iter().map(|p| json!({
+                "name": p.name,
+                "version": p.version,
+                "source": p.source_kind()
+            })).collect::<Vec<_>>()
+        }));
+    } else if let Some(path) = &output {
+        output_text(&format!(
+            "✅ Wrote summary of {} packages to {}",
+            packages.len(),
+            path.display()
+        ));
+    } else {
+        output_text(markdown.trim_end());
+    }
+
+    Ok(())
+}
+
+async fn generate_bindgen_crate(header: &Path, crate_name: &str, json_output: bool) -> Result<()> {
+    info!(
+        "Scaffolding FFI crate {} for {}...",
+        crate_name,
+        header.display()
+    );
+
+    // The name becomes a directory and the package name in the generated files
+    if !is_valid_dependency_name(crate_name) {
+        if json_output {
+            output_json(&json!({
+                "error": "Invalid crate name",
+                "crate_name": crate_name,
+                "suggestion": "Use letters, digits, '-' and '_', starting with a letter or '_'"
+            }));
+        } else {
+            output_text(&format!("❌ Invalid crate name: '{}'", crate_name));
+            output_text("💡 Use letters, digits, '-' and '_', starting with a letter or '_'");
+        }
+        return Err(anyhow!("Invalid crate name: {}", crate_name));
+    }
+
+    if !header.is_file() {
+        if json_output {
+            output_json(&json!({
+                "error": "Header not found",
+                "header": header
+            }));
+        } else {
+            output_text(&format!("❌ Header not found: {}", header.display()));
+        }
+        return Err(anyhow!("Header not found: {}", header.display()));
+    }
+
+    let crate_dir = Path::new("crates").join(crate_name);
+    if crate_dir.exists() {
+        if json_output {
+            output_json(&json!({
+                "error": "Directory already exists",
+                "path": crate_dir
+            }));
+        } else {
+            output_text(&format!(
+                "❌ Directory '{}' already exists",
+                crate_dir.display()
+            ));
+        }
+        return Err(anyhow!("Directory already exists"));
+    }
+
+    let mut bindgen_version = bindgen_cli_version();
+    if bindgen_version.is_none() && !json_output {
+        output_text("⚠️  bindgen CLI not installed (the generated build.rs only needs the crate)");
+        if confirm("Install bindgen-cli with cargo install?") {
+            output_text("📦 Installing bindgen-cli...");
+            let output = run_command("cargo", &["install", "bindgen-cli"])?;
+            if output.status.success() {
+                bindgen_version = bindgen_cli_version();
+            } else {
+                output_text("❌ Failed to install bindgen-cli");
+                output_text(String::from_utf8_lossy(&output.stderr).trim());
+            }
+        }
+    }
+
+    let header_name = header
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!("Invalid header path: {}", header.display()))?;
+    let dependency_version = bindgen_version
+        .as_deref()
+        .and_then(|version| {
+            let mut parts = version.split('.');
+            Some(format!("{}.{}", parts.next()?, parts.next()?))
+        })
+        .unwrap_or_else(|| DEFAULT_BINDGEN_VERSION.to_string());
+    let edition = read_manifest(Path::new("Cargo.toml"))
+        .ok()
+        .and_then(|m| m.get("package")?.get("edition")?.as_str().map(String::from))
+        .unwrap_or_else(|| "2021".to_string());
+
+    std::fs::create_dir_all(crate_dir.join("src"))?;
+    let mut generated_files = Vec::new();
+    let mut write = |relative: &str, content: &str| -> Result<()> {
+        let path = crate_dir.join(relative);
+        std::fs::write(&path, content)?;
+        generated_files.push(path);
+        Ok(())
+    };
+
+    write(
+        "Cargo.toml",
+        &format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"{}\"\nbuild = \"build.rs\"\n\n[dependencies]\n\n[build-dependencies]\nbindgen = \"{}\"\n",
+            crate_name, edition, dependency_version
+        ),
+    )?;
+    write(
+        "build.rs",
+        // As a string literal, so quotes and backslashes in the file name are escaped
+        &BINDGEN_BUILD_RS.replace("{{header}}", &format!("{:?}", header_name)),
+    )?;
+    write("src/lib.rs", BINDGEN_LIB_RS)?;
+    write(&header_name, &std::fs::read_to_string(header)?)?;
+
+    // Generate the bindings up front when the CLI is around; build.rs keeps them fresh
+    if bindgen_version.is_some() {
+        let bindings = crate_dir.join("bindings.rs");
+        let output = run_command(
+            "bindgen",
+            &[
+                &crate_dir.join(&header_name).to_string_lossy(),
+                "-o",
+                &bindings.to_string_lossy(),
+            ],
+        )?;
+        if output.status.success() {
+            generated_files.push(bindings);
+        }
+    }
+
+    if json_output {
+        output_json(&json!({
+            "crate_dir": crate_dir,
+            "generated_files": generated_files,
+            "bindgen_version": bindgen_version
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Created FFI crate {} in {}",
+            crate_name,
+            crate_dir.display()
+        ));
+        for file in &generated_files {
+            output_text(&format!("  📄 {}", file.display()));
+        }
+        output_text("");
+        output_text(&format!(
+            "💡 Add \"{}\" to [workspace] members in Cargo.toml",
+            crate_dir.display()
+        ));
+        output_text(
+            "   bindgen needs libclang at build time (e.g. the clang or libclang-dev package)",
+        );
+    }
+
+    Ok(())
+}
+
+/// Version of the installed `bindgen` CLI, e.g. `0.71.1`.
+fn bindgen_cli_version() -> Option<String> {
+    let output = run_command("bindgen", &["--version"]).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .map(String::from)
+}
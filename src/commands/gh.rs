@@ -0,0 +1,188 @@
+use crate::commands::gpg::detach_sign;
+use crate::commands::verify::{default_run, select_binary};
+use crate::utils::{
+    cargo_messages, format_duration, is_rust_project, output_json, output_text, run_command,
+    run_command_with_timing, sha256_file,
+};
+use crate::GhAction;
+use anyhow::{Result, anyhow};
+use serde_json::json;
+use std::path::Path;
+use tracing::info;
+
+pub async fn run(action: GhAction, json_output: bool) -> Result<()> {
+    match action {
+        GhAction::Release {
+            tag,
+            draft,
+            notes,
+            bin,
+        } => create_release(&tag, draft, notes.as_deref(), bin, json_output).await,
+    }
+}
+
+async fn create_release(
+    tag: &str,
+    draft: bool,
+    notes: Option<&str>,
+    bin: Option<String>,
+    json_output: bool,
+) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let gh_available = run_command("gh", &["--version"])
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !gh_available {
+        if json_output {
+            output_json(&json!({
+                "error": "GitHub CLI not available",
+                "suggestion": "Install from https://cli.github.com and run `gh auth login`"
+            }));
+        } else {
+            output_text("❌ GitHub CLI (gh) not installed");
+            output_text("💡 Install from https://cli.github.com and run `gh auth login`");
+        }
+        return Err(anyhow!("GitHub CLI not available"));
+    }
+
+    // Like `cargo run`, fall back to default-run when no binary is named
+    let bin = bin.or_else(|| default_run(Path::new("Cargo.toml")));
+    let mut args = vec!["build", "--release", "--message-format=json-render-diagnostics"];
+    if let Some(name) = &bin {
+        args.extend(["--bin", name]);
+    }
+
+    info!("Building release binary...");
+    let (output, duration) = run_command_with_timing("cargo", &args)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo build --release failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!(
+                "❌ Build failed after {}",
+                format_duration(duration)
+            ));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo build --release failed"));
+    }
+
+    let artifacts = cargo_messages(&output, "compiler-artifact");
+    let binary = match select_binary(&artifacts, bin.as_deref()) {
+        Ok(binary) => binary.to_string_lossy().to_string(),
+        Err(e) => {
+            if json_output {
+                output_json(&json!({
+                    "error": e.to_string(),
+                    "suggestion": "Name the binary to release with --bin"
+                }));
+            } else {
+                output_text(&format!("❌ {}", e));
+                output_text("💡 Name the binary to release with --bin");
+            }
+            return Err(e);
+        }
+    };
+
+    info!("Signing release binary {}...", binary);
+    let signature = match detach_sign(&binary) {
+        Ok(signature) => signature,
+        Err(e) => {
+            if json_output {
+                output_json(&json!({
+                    "error": format!("Failed to sign {}", binary),
+                    "details": e.to_string(),
+                    "suggestion": "Run 'oxy gpg setup' to configure GPG signing"
+                }));
+            } else {
+                output_text(&format!("❌ Failed to sign {}", binary));
+                output_text(&format!("Error: {}", e));
+                output_text("💡 Run 'oxy gpg setup' to configure GPG signing");
+            }
+            return Err(anyhow!("Failed to sign release binary: {}", e));
+        }
+    };
+
+    info!("Generating checksums...");
+    let checksums_path = Path::new(&binary).with_file_name("SHA256SUMS");
+    let mut checksums = String::new();
+    for asset in [&binary, &signature] {
+        let asset_path = Path::new(asset);
+        let file_name = asset_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| asset.to_string());
+        checksums.push_str(&format!("{}  {}\n", sha256_file(asset_path)?, file_name));
+    }
+    std::fs::write(&checksums_path, &checksums)?;
+    let checksums_path = checksums_path.to_string_lossy().to_string();
+
+    let assets_uploaded = vec![binary.clone(), signature.clone(), checksums_path];
+
+    info!("Creating GitHub release {}...", tag);
+    let mut args = vec!["release", "create", tag];
+    args.extend(assets_uploaded.iter().map(String::as_str));
+    args.extend(["--title", tag]);
+    if draft {
+        args.push("--draft");
+    }
+    match notes {
+        Some(notes) => args.extend(["--notes", notes]),
+        None => args.push("--generate-notes"),
+    }
+
+    let output = run_command("gh", &args)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "gh release create failed",
+                "tag": tag,
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ Failed to create release {}", tag));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("gh release create failed"));
+    }
+
+    // gh prints the URL of the new release as its last line
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let release_url = stdout.lines().last().unwrap_or("").trim().to_string();
+
+    if json_output {
+        output_json(&json!({
+            "release_url": release_url,
+            "tag": tag,
+            "draft": draft,
+            "assets_uploaded": assets_uploaded,
+            "signatures": [signature]
+        }));
+    } else {
+        let kind = if draft { "draft release" } else { "release" };
+        output_text(&format!("✅ Created {} {}", kind, tag));
+        output_text(&format!("🔗 {}", release_url));
+        output_text("📦 Assets:");
+        for asset in &assets_uploaded {
+            output_text(&format!("  • {}", asset));
+        }
+    }
+
+    Ok(())
+}
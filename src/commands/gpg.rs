@@ -1,4 +1,4 @@
-use crate::utils::{output_json, output_text, run_command};
+use crate::utils::{output_json, output_text, run_command, run_command_interactive};
 use crate::GpgAction;
 use anyhow::{Result, anyhow};
 use serde_json::json;
@@ -10,6 +10,23 @@ pub async fn run(action: GpgAction, json_output: bool) -> Result<()> {
         GpgAction::Sign { target } => sign_target(&target, json_output).await,
         GpgAction::Verify { target } => verify_target(&target, json_output).await,
         GpgAction::Setup => setup_gpg_for_rust(json_output).await,
+        GpgAction::Cosign {
+            sign,
+            verify,
+            keyless,
+            certificate_identity,
+            certificate_oidc_issuer,
+        } => match (sign, verify) {
+            (Some(artifact), _) => cosign_sign(&artifact, keyless, json_output).await,
+            (None, Some(artifact)) => {
+                let signer = match (certificate_identity, certificate_oidc_issuer) {
+                    (Some(identity), Some(issuer)) => Some((identity, issuer)),
+                    _ => None,
+                };
+                cosign_verify(&artifact, keyless, signer, json_output).await
+            }
+            (None, None) => Err(anyhow!("Pass --sign <ARTIFACT> or --verify <ARTIFACT>")),
+        },
     }
 }
 
@@ -169,10 +186,8 @@ async fn sign_file(file_path: &str, json_output: bool) -> Result<()> {
         return Err(anyhow!("File not found"));
     }
 
-    let signature_path = format!("{}.sig", file_path);
-
-    match run_command("gpg", &["--detach-sign", "--armor", "--output", &signature_path, file_path]) {
-        Ok(_) => {
+    match detach_sign(file_path) {
+        Ok(signature_path) => {
             if json_output {
                 output_json(&json!({
                     "action": "sign_file",
@@ -204,6 +219,214 @@ async fn sign_file(file_path: &str, json_output: bool) -> Result<()> {
     Ok(())
 }
 
+/// Write an armored detached signature to `<file>.sig` and return its path.
+pub fn detach_sign(file_path: &str) -> Result<String> {
+    let signature_path = format!("{}.sig", file_path);
+    let output = run_command("gpg", &["--batch", "--yes", "--detach-sign", "--armor", "--output", &signature_path, file_path])?;
+    if !output.status.success() {
+        return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(signature_path)
+}
+
+/// Files cosign uses for key-based signing, as written by `cosign generate-key-pair`.
+const COSIGN_PRIVATE_KEY: &str = "cosign.key";
+const COSIGN_PUBLIC_KEY: &str = "cosign.pub";
+
+fn cosign_installed() -> bool {
+    run_command("cosign", &["version"])
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Check the preconditions shared by cosign signing and verification.
+fn cosign_preflight(artifact: &str, key: Option<&str>, json_output: bool) -> Result<()> {
+    if !cosign_installed() {
+        if json_output {
+            output_json(&json!({
+                "error": "cosign not installed",
+                "signing_method": "cosign",
+                "suggestion": "Install from https://docs.sigstore.dev/cosign/system_config/installation/"
+            }));
+        } else {
+            output_text("❌ cosign not installed");
+            output_text(
+                "💡 Install from https://docs.sigstore.dev/cosign/system_config/installation/",
+            );
+        }
+        return Err(anyhow!("cosign not installed"));
+    }
+
+    if !Path::new(artifact).exists() {
+        if json_output {
+            output_json(&json!({
+                "error": "File not found",
+                "signing_method": "cosign",
+                "artifact": artifact
+            }));
+        } else {
+            output_text(&format!("❌ File not found: {}", artifact));
+        }
+        return Err(anyhow!("File not found"));
+    }
+
+    if let Some(key) = key
+        && !Path::new(key).exists()
+    {
+        if json_output {
+            output_json(&json!({
+                "error": format!("{} not found", key),
+                "signing_method": "cosign",
+                "suggestion": "Create a key pair with `cosign generate-key-pair` or pass --keyless"
+            }));
+        } else {
+            output_text(&format!("❌ {} not found", key));
+            output_text(
+                "💡 Create a key pair with `cosign generate-key-pair` or pass --keyless",
+            );
+        }
+        return Err(anyhow!("{} not found", key));
+    }
+
+    Ok(())
+}
+
+/// Run cosign with the terminal attached in text mode, so keyless sign-in and key
+/// passwords can be prompted for; JSON mode captures its output instead.
+fn run_cosign(args: &[&str], json_output: bool) -> Result<(bool, String)> {
+    if json_output {
+        let output = run_command("cosign", args)?;
+        Ok((
+            output.status.success(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    } else {
+        let status = run_command_interactive("cosign", args)?;
+        Ok((status.success(), String::new()))
+    }
+}
+
+async fn cosign_sign(artifact: &str, keyless: bool, json_output: bool) -> Result<()> {
+    let key = (!keyless).then_some(COSIGN_PRIVATE_KEY);
+    cosign_preflight(artifact, key, json_output)?;
+
+    let bundle_path = format!("{}.bundle", artifact);
+    let mut args = vec!["sign-blob", "--yes", "--bundle", &bundle_path];
+    if let Some(key) = key {
+        args.extend(["--key", key]);
+    }
+    args.push(artifact);
+
+    info!("Signing {} with cosign...", artifact);
+    let (success, details) = run_cosign(&args, json_output)?;
+
+    if json_output {
+        output_json(&json!({
+            "signing_method": "cosign",
+            "artifact": artifact,
+            "bundle_path": bundle_path,
+            "keyless": keyless,
+            "success": success,
+            "details": details
+        }));
+    } else if success {
+        output_text(&format!("✅ Signed {} with cosign", artifact));
+        output_text(&format!("📝 Bundle saved to: {}", bundle_path));
+    } else {
+        output_text(&format!("❌ cosign failed to sign {}", artifact));
+    }
+
+    if !success {
+        return Err(anyhow!("cosign sign-blob failed for {}", artifact));
+    }
+    Ok(())
+}
+
+/// Verify `artifact`'s bundle. Keyless verification needs the expected `(identity, issuer)`
+/// of the signer, since any Sigstore identity can produce a valid certificate.
+async fn cosign_verify(
+    artifact: &str,
+    keyless: bool,
+    signer: Option<(String, String)>,
+    json_output: bool,
+) -> Result<()> {
+    if keyless && signer.is_none() {
+        let msg = "Keyless verification needs the expected signer";
+        let suggestion =
+            "Pass --certificate-identity <IDENTITY> and --certificate-oidc-issuer <URL>";
+        if json_output {
+            output_json(&json!({
+                "error": msg,
+                "signing_method": "cosign",
+                "artifact": artifact,
+                "suggestion": suggestion
+            }));
+        } else {
+            output_text(&format!("❌ {}", msg));
+            output_text(&format!("💡 {}", suggestion));
+        }
+        return Err(anyhow!(msg));
+    }
+    let key = (!keyless).then_some(COSIGN_PUBLIC_KEY);
+    cosign_preflight(artifact, key, json_output)?;
+
+    let bundle_path = format!("{}.bundle", artifact);
+    if !Path::new(&bundle_path).exists() {
+        if json_output {
+            output_json(&json!({
+                "error": "Bundle file not found",
+                "signing_method": "cosign",
+                "artifact": artifact,
+                "expected_bundle": bundle_path
+            }));
+        } else {
+            output_text(&format!("❌ Bundle file not found: {}", bundle_path));
+        }
+        return Err(anyhow!("Bundle file not found"));
+    }
+
+    let mut args = vec!["verify-blob", "--bundle", &bundle_path];
+    match (key, &signer) {
+        (Some(key), _) => args.extend(["--key", key]),
+        (None, Some((identity, issuer))) => args.extend([
+            "--certificate-identity",
+            identity,
+            "--certificate-oidc-issuer",
+            issuer,
+        ]),
+        (None, None) => {}
+    }
+    args.push(artifact);
+
+    info!("Verifying {} with cosign...", artifact);
+    let (success, details) = run_cosign(&args, json_output)?;
+
+    if json_output {
+        output_json(&json!({
+            "signing_method": "cosign",
+            "artifact": artifact,
+            "bundle_path": bundle_path,
+            "keyless": keyless,
+            "certificate_identity": signer.as_ref().map(|(identity, _)| identity),
+            "certificate_oidc_issuer": signer.as_ref().map(|(_, issuer)| issuer),
+            "success": success,
+            "details": details
+        }));
+    } else if success {
+        output_text(&format!("✅ cosign signature verified: {}", artifact));
+        if let Some((identity, issuer)) = &signer {
+            output_text(&format!("   Signed by {} ({})", identity, issuer));
+        }
+    } else {
+        output_text(&format!("❌ cosign failed to verify {}", artifact));
+    }
+
+    if !success {
+        return Err(anyhow!("cosign verify-blob failed for {}", artifact));
+    }
+    Ok(())
+}
+
 async fn verify_target(target: &str, json_output: bool) -> Result<()> {
     info!("Verifying target: {}", target);
 
@@ -487,6 +710,31 @@ async fn setup_gpg_for_rust(json_output: bool) -> Result<()> {
         }));
     }
 
+    // cosign is an optional, keyless-capable alternative to GPG for release artifacts
+    match run_command("cosign", &["version"]) {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|line| line.starts_with("GitVersion"))
+                .map(|line| line.trim_start_matches("GitVersion:").trim().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            setup_steps.push(json!({
+                "step": "check_cosign",
+                "status": "success",
+                "message": "cosign is installed (Sigstore signing available)",
+                "details": version
+            }));
+        }
+        _ => {
+            setup_steps.push(json!({
+                "step": "check_cosign",
+                "status": "info",
+                "message": "cosign not installed (optional Sigstore alternative to GPG)",
+                "suggestion": "Install from https://docs.sigstore.dev/cosign/system_config/installation/"
+            }));
+        }
+    }
+
     if json_output {
         output_json(&json!({
             "action": "setup_gpg",
@@ -527,6 +775,7 @@ async fn setup_gpg_for_rust(json_output: bool) -> Result<()> {
             output_text("   • Sign last commit: oxy gpg sign commit");
             output_text("   • Create signed tag: git tag -s v1.0.0 -m 'Version 1.0.0'");
             output_text("   • Verify signatures: oxy gpg verify commit");
+            output_text("   • Sign with cosign: oxy gpg cosign --sign <file> --keyless");
         }
     }
 
@@ -0,0 +1,170 @@
+use crate::utils::{confirm, output_json, output_text, run_command};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Marks hooks written by oxy, so re-running the install updates them in place.
+const HOOK_MARKER: &str = "# Installed by oxy install-hooks";
+
+/// Suffix an existing hook is renamed to when oxy's hook is chained in front of it.
+const CHAINED_SUFFIX: &str = "pre-oxy";
+
+/// Git hooks oxy can install and the `oxy` command each one runs.
+const HOOKS: &[(&str, &str)] = &[
+    ("pre-commit", "oxy check --no-clippy"),
+    ("pre-push", "oxy check"),
+];
+
+pub async fn run(pre_commit: bool, pre_push: bool, force: bool, json_output: bool) -> Result<()> {
+    let Some(hooks_dir) = hooks_dir() else {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a git repository"
+            }));
+        } else {
+            output_text("❌ Not in a git repository");
+        }
+        return Err(anyhow!("Not in a git repository"));
+    };
+
+    // With neither flag, install both hooks
+    let selected: Vec<&(&str, &str)> = HOOKS
+        .iter()
+        .filter(|(name, _)| match *name {
+            "pre-commit" => pre_commit || !pre_push,
+            _ => pre_push || !pre_commit,
+        })
+        .collect();
+
+    std::fs::create_dir_all(&hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, command) in selected {
+        let path = hooks_dir.join(name);
+        let chained_name = format!("{}.{}", name, CHAINED_SUFFIX);
+        let chained_path = hooks_dir.join(&chained_name);
+        let existing = std::fs::read_to_string(&path).ok();
+        let ours = existing
+            .as_deref()
+            .is_some_and(|content| content.contains(HOOK_MARKER));
+
+        // Chain behind an existing hook rather than replacing it, unless --force
+        let chained = if existing.is_some() && !ours && !force {
+            let chain = json_output
+                || confirm(&format!(
+                    "A {} hook already exists. Keep running it before oxy's hook?",
+                    name
+                ));
+            let reason = if !chain {
+                Some("existing hook kept (use --force to overwrite)".to_string())
+            } else if chained_path.exists() {
+                Some(format!("{} already exists", chained_path.display()))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                skipped.push(json!({
+                    "name": name,
+                    "path": path,
+                    "reason": reason
+                }));
+                continue;
+            }
+            std::fs::rename(&path, &chained_path)
+                .with_context(|| format!("Failed to move {}", path.display()))?;
+            true
+        } else {
+            // Reinstalling keeps a hook that an earlier install chained
+            ours && !force && chained_path.exists()
+        };
+
+        info!("Installing {} hook to {}", name, path.display());
+        let script = hook_script(command, chained.then_some(chained_name.as_str()));
+        std::fs::write(&path, script)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        make_executable(&path)?;
+
+        installed.push(json!({
+            "name": name,
+            "path": path,
+            "chained": chained
+        }));
+    }
+
+    if json_output {
+        output_json(&json!({
+            "hooks_installed": installed,
+            "hooks_skipped": skipped
+        }));
+        return Ok(());
+    }
+
+    output_text("🪝 Git Hooks");
+    output_text("============");
+    for hook in &installed {
+        let name = hook["name"].as_str().unwrap_or("");
+        let path = hook["path"].as_str().unwrap_or("");
+        if hook["chained"] == true {
+            output_text(&format!(
+                "✅ Installed {} hook (runs {}.{} first): {}",
+                name, name, CHAINED_SUFFIX, path
+            ));
+        } else {
+            output_text(&format!("✅ Installed {} hook: {}", name, path));
+        }
+    }
+    for hook in &skipped {
+        output_text(&format!(
+            "⏭️  Skipped {}: {}",
+            hook["name"].as_str().unwrap_or(""),
+            hook["reason"].as_str().unwrap_or("")
+        ));
+    }
+    if !installed.is_empty() {
+        output_text("💡 Bypass a hook once with: git commit --no-verify / git push --no-verify");
+    }
+
+    Ok(())
+}
+
+/// The repository's hooks directory, honouring `core.hooksPath` and worktrees.
+fn hooks_dir() -> Option<PathBuf> {
+    let output = run_command("git", &["rev-parse", "--git-path", "hooks"]).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Shell script for a hook running `command`, after the `chained` hook next to it if set.
+pub fn hook_script(command: &str, chained: Option<&str>) -> String {
+    let mut script = format!("#!/bin/sh\n{}\n", HOOK_MARKER);
+    if let Some(chained) = chained {
+        // Like git itself, only run the previous hook if it is executable
+        script.push_str(&format!(
+            "chained=\"$(dirname \"$0\")/{}\"\nif [ -x \"$chained\" ]; then\n    \"$chained\" \"$@\" || exit $?\nfi\n",
+            chained
+        ));
+    }
+    script.push_str(&format!("exec {}\n", command));
+    script
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    std::fs::set_permissions(path, permissions)
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
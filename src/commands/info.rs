@@ -1,10 +1,42 @@
-use crate::utils::{is_rust_project, output_json, output_text, run_command};
-use anyhow::Result;
+use crate::commands::sbom::iso8601_days;
+use crate::history::unix_timestamp;
+use crate::utils::{
+    DEPENDENCY_TABLES, crates_io_version, find_files, format_bytes, is_rust_project, output_json,
+    output_text, read_lockfile, read_manifest, run_command,
+};
+use anyhow::{Context, Result, anyhow};
 use serde_json::json;
-use std::path::Path;
-use tracing::info;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
-pub async fn run(json_output: bool) -> Result<()> {
+/// Technical-debt markers counted by `--debt-scan`, as (kind, pattern).
+const DEBT_PATTERNS: &[(&str, &str)] = &[
+    ("todo", "todo!("),
+    ("fixme", "fixme"),
+    ("unimplemented", "unimplemented!("),
+    ("unwrap", ".unwrap()"),
+    ("expect", ".expect("),
+];
+const TOP_DEBT_FILES: usize = 10;
+
+/// Public item kinds counted by `--api-surface`, as (JSON key, keyword).
+const API_ITEM_KINDS: &[(&str, &str)] = &[
+    ("structs", "struct"),
+    ("enums", "enum"),
+    ("traits", "trait"),
+    ("functions", "fn"),
+    ("types", "type"),
+    ("consts", "const"),
+];
+
+pub async fn run(
+    diff: Option<String>,
+    debt_scan: bool,
+    deprecated_scan: bool,
+    api_surface: bool,
+    json_output: bool,
+) -> Result<()> {
     info!("Gathering project information...");
 
     let mut project_info = json!({});
@@ -22,30 +54,51 @@ pub async fn run(json_output: bool) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(git_ref) = diff {
+        return show_manifest_diff(&git_ref, json_output).await;
+    }
+    if debt_scan {
+        return show_debt_scan(json_output).await;
+    }
+    if deprecated_scan {
+        return show_deprecated_scan(json_output).await;
+    }
+    if api_surface {
+        return show_api_surface(json_output).await;
+    }
+
     project_info["is_rust_project"] = json!(true);
 
     // Read Cargo.toml
-    if let Ok(cargo_content) = std::fs::read_to_string("Cargo.toml") {
-        if let Ok(manifest) = cargo_content.parse::<toml::Value>() {
-            if let Some(package) = manifest.get("package") {
-                project_info["package"] = json!({
-                    "name": package.get("name").and_then(|v| v.as_str()),
-                    "version": package.get("version").and_then(|v| v.as_str()),
-                    "edition": package.get("edition").and_then(|v| v.as_str()),
-                    "authors": package.get("authors"),
-                    "description": package.get("description").and_then(|v| v.as_str()),
-                });
-            }
+    if let Ok(cargo_content) = std::fs::read_to_string("Cargo.toml")
+        && let Ok(manifest) = cargo_content.parse::<toml::Value>()
+    {
+        if let Some(package) = manifest.get("package") {
+            project_info["package"] = json!({
+                "name": package.get("name").and_then(|v| v.as_str()),
+                "version": package.get("version").and_then(|v| v.as_str()),
+                "edition": package.get("edition").and_then(|v| v.as_str()),
+                "authors": package.get("authors"),
+                "description": package.get("description").and_then(|v| v.as_str()),
+            });
+        }
 
-            if let Some(dependencies) = manifest.get("dependencies") {
-                project_info["dependencies_count"] =
-                    json!(dependencies.as_table().map(|t| t.len()).unwrap_or(0));
-            }
+        if let Some(dependencies) = manifest.get("dependencies") {
+            project_info["dependencies_count"] =
+                json!(dependencies.as_table().map(|t| t.len()).unwrap_or(0));
+        }
 
-            if let Some(dev_dependencies) = manifest.get("dev-dependencies") {
-                project_info["dev_dependencies_count"] =
-                    json!(dev_dependencies.as_table().map(|t| t.len()).unwrap_or(0));
-            }
+        if let Some(dev_dependencies) = manifest.get("dev-dependencies") {
+            project_info["dev_dependencies_count"] =
+                json!(dev_dependencies.as_table().map(|t| t.len()).unwrap_or(0));
+        }
+
+        if let Some(name) = manifest
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        {
+            project_info["deps_freshness"] = dependency_freshness(name);
         }
     }
 
@@ -104,6 +157,28 @@ pub async fn run(json_output: bool) -> Result<()> {
     }
     project_info["common_files"] = json!(found_files);
 
+    let manifest = read_manifest(Path::new("Cargo.toml")).ok();
+    project_info["build_script"] = build_script_info(manifest.as_ref());
+    if let Some(manifest) = &manifest {
+        let binaries: Vec<_> = cargo_targets(manifest, "bin", Path::new("."))
+            .into_iter()
+            .map(|mut binary| {
+                let name = binary["name"].as_str().unwrap_or_default();
+                let output = Path::new("target/release").join(format!(
+                    "{}{}",
+                    name,
+                    std::env::consts::EXE_SUFFIX
+                ));
+                let size = std::fs::metadata(&output).ok().map(|m| m.len());
+                binary["built"] = json!(size.is_some());
+                binary["size_bytes"] = json!(size);
+                binary
+            })
+            .collect();
+        project_info["binaries"] = json!(binaries);
+        project_info["examples"] = json!(cargo_targets(manifest, "example", Path::new(".")));
+    }
+
     // Check target directory size if it exists
     if let Ok(metadata) = std::fs::metadata("target") {
         if metadata.is_dir() {
@@ -146,6 +221,16 @@ pub async fn run(json_output: bool) -> Result<()> {
             output_text(&format!("Dev Dependencies: {}", dev_deps));
         }
 
+        let freshness = &project_info["deps_freshness"];
+        if let Some(mean) = freshness["mean_age_days"].as_u64() {
+            output_text(&format!(
+                "Average dependency age: {} days (oldest: {} at {} days)",
+                mean,
+                freshness["oldest"]["name"].as_str().unwrap_or("unknown"),
+                freshness["max_age_days"].as_u64().unwrap_or(0)
+            ));
+        }
+
         output_text("");
 
         if let Some(git) = project_info["git"].as_object() {
@@ -166,14 +251,14 @@ pub async fn run(json_output: bool) -> Result<()> {
                     };
                     output_text(&format!("  Status: {}", status));
                 }
-                if let Some(commit) = git["last_commit"].as_object() {
-                    if let (Some(msg), Some(author), Some(date)) = (
+                if let Some(commit) = git["last_commit"].as_object()
+                    && let (Some(msg), Some(author), Some(date)) = (
                         commit["message"].as_str(),
                         commit["author"].as_str(),
                         commit["date"].as_str(),
-                    ) {
-                        output_text(&format!("  Last Commit: {} by {} ({})", msg, author, date));
-                    }
+                    )
+                {
+                    output_text(&format!("  Last Commit: {} by {} ({})", msg, author, date));
                 }
                 output_text("");
             } else {
@@ -182,23 +267,965 @@ pub async fn run(json_output: bool) -> Result<()> {
             }
         }
 
-        if let Some(files) = project_info["common_files"].as_array() {
-            if !files.is_empty() {
-                output_text("📄 Project Files:");
-                for file in files {
-                    if let Some(filename) = file.as_str() {
-                        output_text(&format!("  ✅ {}", filename));
-                    }
+        if let Some(files) = project_info["common_files"].as_array()
+            && !files.is_empty()
+        {
+            output_text("📄 Project Files:");
+            for file in files {
+                if let Some(filename) = file.as_str() {
+                    output_text(&format!("  ✅ {}", filename));
+                }
+            }
+        }
+
+        let build_script = &project_info["build_script"];
+        if build_script["exists"].as_bool() == Some(true) {
+            output_text("");
+            output_text(&format!(
+                "🔨 Build Script: {}",
+                build_script["path"].as_str().unwrap_or("build.rs")
+            ));
+            let tools: Vec<&str> = [
+                ("uses_cc", "cc"),
+                ("uses_cmake", "cmake"),
+                ("uses_pkg_config", "pkg-config"),
+                ("uses_bindgen", "bindgen"),
+            ]
+            .iter()
+            .filter(|(key, _)| build_script[*key].as_bool() == Some(true))
+            .map(|(_, name)| *name)
+            .collect();
+            if !tools.is_empty() {
+                output_text(&format!("  Uses: {}", tools.join(", ")));
+            }
+            if let Some(lib) = build_script["links_native_lib"].as_str() {
+                output_text(&format!("  Links native library: {}", lib));
+            }
+        }
+
+        for (key, title) in [("binaries", "🚀 Binaries:"), ("examples", "📚 Examples:")] {
+            let Some(targets) = project_info[key].as_array().filter(|t| !t.is_empty()) else {
+                continue;
+            };
+            output_text("");
+            output_text(title);
+            let width = targets
+                .iter()
+                .filter_map(|t| t["name"].as_str())
+                .map(str::len)
+                .max()
+                .unwrap_or(0);
+            for target in targets {
+                let mut line = format!(
+                    "  {:<width$}  {}",
+                    target["name"].as_str().unwrap_or(""),
+                    target["path"].as_str().unwrap_or("")
+                );
+                if let Some(features) = target["required_features"].as_array()
+                    && !features.is_empty()
+                {
+                    let features: Vec<&str> = features.iter().filter_map(|f| f.as_str()).collect();
+                    line.push_str(&format!("  (requires {})", features.join(", ")));
+                }
+                if let Some(size) = target["size_bytes"].as_u64() {
+                    line.push_str(&format!("  [release: {}]", format_bytes(size)));
+                } else if target["built"].as_bool() == Some(false) {
+                    line.push_str("  [not built in release]");
                 }
+                output_text(&line);
             }
         }
 
-        if let Some(has_target) = project_info["has_target_dir"].as_bool() {
-            if has_target {
-                output_text("  📁 target/ directory exists");
+        if let Some(has_target) = project_info["has_target_dir"].as_bool()
+            && has_target
+        {
+            output_text("  📁 target/ directory exists");
+        }
+    }
+
+    Ok(())
+}
+
+async fn show_debt_scan(json_output: bool) -> Result<()> {
+    info!("Scanning sources for technical debt markers...");
+
+    let files: Vec<(String, Vec<usize>)> = find_files(Path::new("."), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    })
+    .iter()
+    .filter_map(|path| {
+        let source = std::fs::read_to_string(path).ok()?;
+        let path = path.strip_prefix(".").unwrap_or(path);
+        Some((path.display().to_string(), count_debt_markers(&source)))
+    })
+    .filter(|(_, counts)| counts.iter().any(|c| *c > 0))
+    .collect();
+
+    if json_output {
+        let debt_items: Vec<_> = DEBT_PATTERNS
+            .iter()
+            .enumerate()
+            .map(|(i, (kind, _))| {
+                let mut kind_files: Vec<_> = files
+                    .iter()
+                    .filter(|(_, counts)| counts[i] > 0)
+                    .map(|(path, counts)| (path, counts[i]))
+                    .collect();
+                kind_files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+                json!({
+                    "kind": kind,
+                    "count": kind_files.iter().map(|(_, count)| count).sum::<usize>(),
+                    "files": kind_files
+                        .iter()
+                        .map(|(path, count)| json!({ "path": path, "count": count }))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        output_json(&json!({ "debt_items": debt_items }));
+        return Ok(());
+    }
+
+    output_text("🧹 Technical Debt Scan");
+    output_text("======================");
+
+    if files.is_empty() {
+        output_text("✅ No debt markers found");
+        return Ok(());
+    }
+
+    for (i, (kind, _)) in DEBT_PATTERNS.iter().enumerate() {
+        let total: usize = files.iter().map(|(_, counts)| counts[i]).sum();
+        output_text(&format!("  {:<14} {}", kind, total));
+    }
+
+    let mut ranked: Vec<&(String, Vec<usize>)> = files.iter().collect();
+    ranked.sort_by(|a, b| {
+        let total = |counts: &[usize]| counts.iter().sum::<usize>();
+        total(&b.1).cmp(&total(&a.1)).then_with(|| a.0.cmp(&b.0))
+    });
+
+    let path_width = ranked
+        .iter()
+        .take(TOP_DEBT_FILES)
+        .map(|(path, _)| path.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("File".len());
+    let mut header = format!("{:<width$}", "File", width = path_width);
+    for (kind, _) in DEBT_PATTERNS {
+        header.push_str(&format!("  {:>width$}", kind, width = kind.len()));
+    }
+    header.push_str("  total");
+
+    output_text("");
+    output_text(&format!("Top {} files:", TOP_DEBT_FILES.min(ranked.len())));
+    output_text(&header);
+    output_text(&"-".repeat(header.chars().count()));
+    for (path, counts) in ranked.iter().take(TOP_DEBT_FILES) {
+        let mut row = format!("{:<width$}", path, width = path_width);
+        for ((kind, _), count) in DEBT_PATTERNS.iter().zip(counts) {
+            row.push_str(&format!("  {:>width$}", count, width = kind.len()));
+        }
+        row.push_str(&format!("  {:>5}", counts.iter().sum::<usize>()));
+        output_text(&row);
+    }
+
+    Ok(())
+}
+
+/// Occurrences of each `DEBT_PATTERNS` entry in `source`, in the same order.
+pub fn count_debt_markers(source: &str) -> Vec<usize> {
+    let lowercase = source.to_lowercase();
+    DEBT_PATTERNS
+        .iter()
+        .map(|(kind, pattern)| {
+            // FIXME is usually a comment marker rather than code, so match it in any case
+            if *kind == "fixme" {
+                lowercase.matches(pattern).count()
+            } else {
+                source.matches(pattern).count()
+            }
+        })
+        .collect()
+}
+
+async fn show_deprecated_scan(json_output: bool) -> Result<()> {
+    info!("Scanning sources for #[deprecated] items...");
+
+    let mut files: Vec<(String, Vec<serde_json::Value>)> = find_files(Path::new("."), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    })
+    .iter()
+    .filter_map(|path| {
+        let source = std::fs::read_to_string(path).ok()?;
+        let path = path.strip_prefix(".").unwrap_or(path);
+        Some((path.display().to_string(), find_deprecated_items(&source)))
+    })
+    .filter(|(_, items)| !items.is_empty())
+    .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    let total_count: usize = files.iter().map(|(_, items)| items.len()).sum();
+
+    if json_output {
+        let deprecated_items: Vec<serde_json::Value> = files
+            .iter()
+            .flat_map(|(path, items)| {
+                items.iter().map(move |item| {
+                    let mut item = item.clone();
+                    item["file"] = json!(path);
+                    item
+                })
+            })
+            .collect();
+        output_json(&json!({
+            "deprecated_items": deprecated_items,
+            "total_count": total_count
+        }));
+        return Ok(());
+    }
+
+    output_text("🗑️  Deprecated Items");
+    output_text("===================");
+
+    if files.is_empty() {
+        output_text("✅ No #[deprecated] items found");
+        return Ok(());
+    }
+
+    for (path, items) in &files {
+        output_text("");
+        output_text(&format!("📄 {} ({})", path, items.len()));
+        for item in items {
+            let mut line = format!(
+                "  L{:<5} {}",
+                item["line"].as_u64().unwrap_or(0),
+                item["item_name"].as_str().unwrap_or("<unknown>")
+            );
+            if let Some(since) = item["since"].as_str() {
+                line.push_str(&format!(" (since {})", since));
+            }
+            if let Some(note) = item["note"].as_str() {
+                line.push_str(&format!(" - {}", note));
             }
+            output_text(&line);
         }
     }
 
+    output_text("");
+    output_text(&format!(
+        "📊 {} deprecated items in {} files",
+        total_count,
+        files.len()
+    ));
+
     Ok(())
 }
+
+/// `#[deprecated]` attributes in `source` as `{line, item_name, since, note}`, where
+/// `line` is the 1-based line of the attribute and `item_name` the item it applies to.
+pub fn find_deprecated_items(source: &str) -> Vec<serde_json::Value> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut items = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim_start();
+        if !trimmed.starts_with("#[deprecated") {
+            i += 1;
+            continue;
+        }
+
+        // The attribute may span several lines; collect it up to its closing bracket
+        let start = i;
+        let mut attribute = String::new();
+        let mut depth = 0i32;
+        while i < lines.len() {
+            attribute.push_str(lines[i].trim());
+            attribute.push(' ');
+            depth += lines[i].matches('[').count() as i32 - lines[i].matches(']').count() as i32;
+            i += 1;
+            if depth <= 0 {
+                break;
+            }
+        }
+
+        let string_field = |field: &str| {
+            let rest = attribute.split_once(field)?.1.trim_start();
+            let rest = rest.strip_prefix('=')?.trim_start().strip_prefix('"')?;
+            rest.split_once('"').map(|(value, _)| value.to_string())
+        };
+        let since = string_field("since");
+        // `#[deprecated = "..."]` is shorthand for a note
+        let note = string_field("note").or_else(|| string_field("deprecated"));
+
+        // The item is the next line that isn't another attribute, comment or blank
+        let item_name = lines[i..]
+            .iter()
+            .map(|line| line.trim())
+            .find(|line| {
+                !line.is_empty()
+                    && !line.starts_with("#[")
+                    && !line.starts_with("//")
+                    && !line.starts_with(']')
+            })
+            .and_then(deprecated_item_name);
+
+        items.push(json!({
+            "line": start + 1,
+            "item_name": item_name,
+            "since": since,
+            "note": note
+        }));
+    }
+
+    items
+}
+
+/// Name declared on an item line such as `pub(crate) async fn foo(` or `Variant,`.
+fn deprecated_item_name(line: &str) -> Option<String> {
+    const KEYWORDS: &[&str] = &[
+        "fn", "struct", "enum", "trait", "type", "mod", "const", "static", "union",
+    ];
+    const QUALIFIERS: &[&str] = &["pub", "async", "unsafe", "extern", "default"];
+
+    let mut words = line
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '!'))
+        .filter(|word| !word.is_empty())
+        .peekable();
+    while let Some(word) = words.next() {
+        match word {
+            // `pub(crate)`, `pub(super)`, `pub(in path)`
+            "crate" | "super" | "self" | "in" => continue,
+            "macro_rules!" => return words.next().map(String::from),
+            "impl" => return Some(line.trim_end_matches(['{', ' ']).to_string()),
+            _ if QUALIFIERS.contains(&word) => continue,
+            // `const fn` is a function, not a constant
+            "const" if words.peek() == Some(&"fn") => continue,
+            _ if KEYWORDS.contains(&word) => return words.next().map(String::from),
+            // Enum variants, fields and associated items without a keyword
+            _ => return Some(word.to_string()),
+        }
+    }
+    None
+}
+
+async fn show_api_surface(json_output: bool) -> Result<()> {
+    let lib_path = read_manifest(Path::new("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.get("lib")?.get("path")?.as_str().map(String::from))
+        .unwrap_or_else(|| "src/lib.rs".to_string());
+    if !Path::new(&lib_path).is_file() {
+        if json_output {
+            output_json(&json!({
+                "error": format!("No library target ({} not found)", lib_path)
+            }));
+        } else {
+            output_text(&format!("❌ No library target ({} not found)", lib_path));
+        }
+        return Err(anyhow!("No library target"));
+    }
+
+    info!("Scanning {} and its modules for public items...", lib_path);
+
+    // Walk the module tree from the crate root, following `mod name;` declarations
+    let mut totals = vec![0; API_ITEM_KINDS.len()];
+    let mut files_scanned = 0;
+    let mut pending = vec![PathBuf::from(&lib_path)];
+    let mut seen = Vec::new();
+    while let Some(path) = pending.pop() {
+        if seen.contains(&path) {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for (total, count) in totals.iter_mut().zip(count_public_items(&source)) {
+            *total += count;
+        }
+        files_scanned += 1;
+        pending.extend(
+            module_declarations(&source)
+                .iter()
+                .filter_map(|name| module_file(&path, name)),
+        );
+        seen.push(path);
+    }
+
+    if json_output {
+        let api_surface: serde_json::Map<String, serde_json::Value> = API_ITEM_KINDS
+            .iter()
+            .zip(&totals)
+            .map(|((key, _), count)| (key.to_string(), json!(count)))
+            .collect();
+        output_json(&json!({ "api_surface": api_surface }));
+        return Ok(());
+    }
+
+    output_text("🔌 Public API Surface");
+    output_text("=====================");
+    for ((key, _), count) in API_ITEM_KINDS.iter().zip(&totals) {
+        output_text(&format!("  {:<10} {}", key, count));
+    }
+    output_text("");
+    output_text(&format!(
+        "📊 {} public items in {} files",
+        totals.iter().sum::<usize>(),
+        files_scanned
+    ));
+
+    Ok(())
+}
+
+/// Number of `pub` items of each `API_ITEM_KINDS` kind declared in `source`, in the same
+/// order. Restricted visibility like `pub(crate)` isn't public API and is skipped.
+pub fn count_public_items(source: &str) -> Vec<usize> {
+    const QUALIFIERS: &[&str] = &["async", "unsafe", "extern", "\"C\"", "\"system\""];
+
+    let mut counts = vec![0; API_ITEM_KINDS.len()];
+    for line in source.lines() {
+        let Some(rest) = line.trim_start().strip_prefix("pub ") else {
+            continue;
+        };
+        let mut words = rest.split_whitespace().peekable();
+        while let Some(word) = words.next() {
+            if QUALIFIERS.contains(&word) {
+                continue;
+            }
+            // `const fn` is a function, not a constant
+            if word == "const" && words.peek() == Some(&"fn") {
+                continue;
+            }
+            if let Some(i) = API_ITEM_KINDS.iter().position(|(_, kw)| *kw == word) {
+                counts[i] += 1;
+            }
+            break;
+        }
+    }
+    counts
+}
+
+/// Names of the out-of-line modules (`mod name;`) declared in `source`.
+pub fn module_declarations(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let rest = if line.starts_with("pub(") {
+                line.split_once(") ")?.1
+            } else {
+                line.strip_prefix("pub ").unwrap_or(line)
+            };
+            let name = rest.strip_prefix("mod ")?.strip_suffix(';')?.trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// The file holding module `name` declared in `parent`, following rustc's lookup rules.
+fn module_file(parent: &Path, name: &str) -> Option<PathBuf> {
+    let dir = parent.parent()?;
+    let stem = parent.file_stem()?.to_string_lossy();
+    // `lib.rs`, `main.rs` and `mod.rs` own their directory; `foo.rs` owns `foo/`
+    let dir = if matches!(stem.as_ref(), "lib" | "main" | "mod") {
+        dir.to_path_buf()
+    } else {
+        dir.join(stem.as_ref())
+    };
+    [
+        dir.join(format!("{}.rs", name)),
+        dir.join(name).join("mod.rs"),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// How long ago the locked version of each direct crates.io dependency of `package_name`
+/// was last updated on crates.io. Responses are cached by `crates_io_version`.
+fn dependency_freshness(package_name: &str) -> serde_json::Value {
+    let Ok(locked) = read_lockfile(Path::new("Cargo.lock")) else {
+        return serde_json::Value::Null;
+    };
+    let Some(root) = locked
+        .iter()
+        .find(|package| package.name == package_name && package.source.is_none())
+    else {
+        return serde_json::Value::Null;
+    };
+
+    info!("Checking dependency ages on crates.io...");
+    let today = (unix_timestamp() / 86_400) as i64;
+    let mut ages = Vec::new();
+    for dependency in &root.dependencies {
+        let mut parts = dependency.split_whitespace();
+        let Some(name) = parts.next() else {
+            continue;
+        };
+        let version = parts.next();
+        let Some(package) = locked.iter().find(|package| {
+            package.name == name && version.is_none_or(|version| package.version == version)
+        }) else {
+            continue;
+        };
+        if package.source_kind() != "crates.io" {
+            continue;
+        }
+        match crates_io_version(&package.name, &package.version) {
+            Ok(info) => {
+                if let Some(days) = info["updated_at"].as_str().and_then(iso8601_days) {
+                    let age = (today - days).max(0) as u64;
+                    ages.push((package.name.clone(), package.version.clone(), age));
+                }
+            }
+            Err(e) => {
+                // Don't hammer an unreachable registry once per crate
+                warn!("Skipping dependency ages, crates.io unavailable: {}", e);
+                break;
+            }
+        }
+    }
+    freshness_summary(&ages)
+}
+
+/// Mean and maximum age in days of `(name, version, age_days)` dependencies.
+pub fn freshness_summary(ages: &[(String, String, u64)]) -> serde_json::Value {
+    let Some((name, version, max_age)) = ages.iter().max_by_key(|(_, _, age)| *age) else {
+        return serde_json::Value::Null;
+    };
+    let total: u64 = ages.iter().map(|(_, _, age)| age).sum();
+    json!({
+        "mean_age_days": (total as f64 / ages.len() as f64).round() as u64,
+        "max_age_days": max_age,
+        "oldest": {
+            "name": name,
+            "version": version,
+            "age_days": max_age
+        },
+        "dependencies_checked": ages.len()
+    })
+}
+
+/// The `bin` or `example` targets of the package as `{name, path, required_features}`:
+/// those declared as `[[bin]]`/`[[example]]` tables, then the ones cargo discovers under
+/// `root` (`src/main.rs`, `src/bin/`, `examples/`) unless `autobins`/`autoexamples` is off.
+pub fn cargo_targets(manifest: &toml::Value, kind: &str, root: &Path) -> Vec<serde_json::Value> {
+    let package = manifest.get("package");
+    let package_name = package
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+    let auto_dir = if kind == "bin" { "src/bin" } else { "examples" };
+    let default_path = |name: &str| {
+        if kind == "bin" && name == package_name && root.join("src/main.rs").is_file() {
+            return "src/main.rs".to_string();
+        }
+        let dir = format!("{}/{}/main.rs", auto_dir, name);
+        if root.join(&dir).is_file() {
+            dir
+        } else {
+            format!("{}/{}.rs", auto_dir, name)
+        }
+    };
+
+    let mut targets: Vec<serde_json::Value> = manifest
+        .get(kind)
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|target| {
+            let name = target.get("name")?.as_str()?;
+            let path = target
+                .get("path")
+                .and_then(|p| p.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| default_path(name));
+            let features: Vec<&str> = target
+                .get("required-features")
+                .and_then(|f| f.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.as_str())
+                .collect();
+            Some(json!({
+                "name": name,
+                "path": path,
+                "required_features": features
+            }))
+        })
+        .collect();
+
+    let auto_key = if kind == "bin" {
+        "autobins"
+    } else {
+        "autoexamples"
+    };
+    if package
+        .and_then(|p| p.get(auto_key))
+        .and_then(|a| a.as_bool())
+        == Some(false)
+    {
+        return targets;
+    }
+    let mut discovered = Vec::new();
+    if kind == "bin" && root.join("src/main.rs").is_file() && !package_name.is_empty() {
+        discovered.push((package_name.to_string(), "src/main.rs".to_string()));
+    }
+    if let Ok(entries) = std::fs::read_dir(root.join(auto_dir)) {
+        let mut found: Vec<(String, String)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = if path.is_dir() && path.join("main.rs").is_file() {
+                    path.file_name()?
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    path.file_stem()?
+                } else {
+                    return None;
+                };
+                let name = name.to_string_lossy().to_string();
+                let path = default_path(&name);
+                Some((name, path))
+            })
+            .collect();
+        found.sort();
+        discovered.extend(found);
+    }
+    for (name, path) in discovered {
+        // A table for the same name or file configures the discovered target instead
+        let declared = targets
+            .iter()
+            .any(|t| t["name"] == name.as_str() || t["path"] == path.as_str());
+        if !declared {
+            targets.push(json!({
+                "name": name,
+                "path": path,
+                "required_features": []
+            }));
+        }
+    }
+    targets
+}
+
+/// Summarize the package's build script, honouring `package.build` overrides.
+fn build_script_info(manifest: Option<&toml::Value>) -> serde_json::Value {
+    let package = manifest.and_then(|m| m.get("package"));
+    let links = package
+        .and_then(|p| p.get("links"))
+        .and_then(|l| l.as_str());
+    let path = match package.and_then(|p| p.get("build")) {
+        Some(toml::Value::String(path)) => Some(path.as_str()),
+        Some(toml::Value::Boolean(false)) => None,
+        _ => Some("build.rs"),
+    };
+
+    let source = path.and_then(|p| std::fs::read_to_string(p).ok());
+    let mut build_script = match &source {
+        Some(source) => scan_build_script(source),
+        None => json!({
+            "uses_cc": false,
+            "uses_cmake": false,
+            "uses_pkg_config": false,
+            "uses_bindgen": false
+        }),
+    };
+    build_script["exists"] = json!(source.is_some());
+    build_script["path"] = json!(source.as_ref().and(path));
+    build_script["links_native_lib"] = json!(links);
+    build_script
+}
+
+/// Look for the common native-build helper crates being referenced in a build script.
+pub fn scan_build_script(source: &str) -> serde_json::Value {
+    json!({
+        "uses_cc": mentions_crate(source, "cc"),
+        "uses_cmake": mentions_crate(source, "cmake"),
+        "uses_pkg_config": mentions_crate(source, "pkg_config"),
+        "uses_bindgen": mentions_crate(source, "bindgen")
+    })
+}
+
+/// Whether `name` appears as a whole identifier followed by a path (`cc::Build`) or import.
+fn mentions_crate(source: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    source.match_indices(name).any(|(start, _)| {
+        let before = source[..start].chars().next_back();
+        let after = source[start + name.len()..].trim_start();
+        !before.is_some_and(is_ident) && (after.starts_with("::") || after.starts_with(';'))
+    })
+}
+
+async fn show_manifest_diff(git_ref: &str, json_output: bool) -> Result<()> {
+    info!("Comparing Cargo.toml against {}...", git_ref);
+
+    let output = run_command("git", &["show", &format!("{}:Cargo.toml", git_ref)])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": format!("Could not read Cargo.toml at {}", git_ref),
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ Could not read Cargo.toml at {}", git_ref));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("git show {}:Cargo.toml failed", git_ref));
+    }
+
+    let old_manifest = String::from_utf8_lossy(&output.stdout)
+        .parse::<toml::Value>()
+        .with_context(|| format!("Failed to parse Cargo.toml at {}", git_ref))?;
+    let new_manifest = read_manifest(Path::new("Cargo.toml"))?;
+
+    let old_deps = collect_dependencies(&old_manifest);
+    let new_deps = collect_dependencies(&new_manifest);
+
+    let mut deps_added = Vec::new();
+    let mut deps_updated = Vec::new();
+    for ((table, name), version) in &new_deps {
+        match old_deps.get(&(table.clone(), name.clone())) {
+            None => deps_added.push(json!({
+                "name": name,
+                "table": table,
+                "version": version
+            })),
+            Some(old_version) if old_version != version => deps_updated.push(json!({
+                "name": name,
+                "table": table,
+                "from_version": old_version,
+                "to_version": version
+            })),
+            Some(_) => {}
+        }
+    }
+    let deps_removed: Vec<_> = old_deps
+        .iter()
+        .filter(|(key, _)| !new_deps.contains_key(*key))
+        .map(|((table, name), version)| {
+            json!({
+                "name": name,
+                "table": table,
+                "version": version
+            })
+        })
+        .collect();
+
+    let old_features = collect_features(&old_manifest);
+    let new_features = collect_features(&new_manifest);
+    let mut features_changed = Vec::new();
+    for (name, enables) in &new_features {
+        match old_features.get(name) {
+            None => features_changed.push(json!({
+                "name": name,
+                "change": "added",
+                "enables": enables
+            })),
+            Some(old_enables) if old_enables != enables => features_changed.push(json!({
+                "name": name,
+                "change": "modified",
+                "from": old_enables,
+                "enables": enables
+            })),
+            Some(_) => {}
+        }
+    }
+    for (name, enables) in &old_features {
+        if !new_features.contains_key(name) {
+            features_changed.push(json!({
+                "name": name,
+                "change": "removed",
+                "from": enables
+            }));
+        }
+    }
+
+    if json_output {
+        output_json(&json!({
+            "git_ref": git_ref,
+            "deps_added": deps_added,
+            "deps_removed": deps_removed,
+            "deps_updated": deps_updated,
+            "features_changed": features_changed
+        }));
+        return Ok(());
+    }
+
+    output_text(&format!("📝 Cargo.toml changes since {}", git_ref));
+    output_text("==============================");
+
+    if deps_added.is_empty()
+        && deps_removed.is_empty()
+        && deps_updated.is_empty()
+        && features_changed.is_empty()
+    {
+        output_text("✅ No dependency or feature changes");
+        return Ok(());
+    }
+
+    let mut rows: Vec<[String; 5]> = Vec::new();
+    let field =
+        |entry: &serde_json::Value, key: &str| entry[key].as_str().unwrap_or("").to_string();
+    for dep in &deps_added {
+        rows.push([
+            "+ added".to_string(),
+            field(dep, "name"),
+            field(dep, "table"),
+            String::new(),
+            field(dep, "version"),
+        ]);
+    }
+    for dep in &deps_removed {
+        rows.push([
+            "- removed".to_string(),
+            field(dep, "name"),
+            field(dep, "table"),
+            field(dep, "version"),
+            String::new(),
+        ]);
+    }
+    for dep in &deps_updated {
+        rows.push([
+            "~ updated".to_string(),
+            field(dep, "name"),
+            field(dep, "table"),
+            field(dep, "from_version"),
+            field(dep, "to_version"),
+        ]);
+    }
+
+    if !rows.is_empty() {
+        let header = [
+            "Change".to_string(),
+            "Crate".to_string(),
+            "Table".to_string(),
+            "From".to_string(),
+            "To".to_string(),
+        ];
+        let widths: Vec<usize> = (0..5)
+            .map(|i| {
+                rows.iter()
+                    .chain(std::iter::once(&header))
+                    .map(|row| row[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let render = |row: &[String; 5]| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        output_text("");
+        output_text(&render(&header));
+        output_text(&"-".repeat(widths.iter().sum::<usize>() + 8));
+        for row in &rows {
+            output_text(&render(row));
+        }
+    }
+
+    if !features_changed.is_empty() {
+        output_text("");
+        output_text("🔧 Features:");
+        for feature in &features_changed {
+            let name = feature["name"].as_str().unwrap_or("unknown");
+            let list = |key: &str| {
+                feature[key]
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|i| i.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default()
+            };
+            match feature["change"].as_str() {
+                Some("added") => output_text(&format!("  + {} = [{}]", name, list("enables"))),
+                Some("removed") => output_text(&format!("  - {}", name)),
+                _ => output_text(&format!(
+                    "  ~ {}: [{}] → [{}]",
+                    name,
+                    list("from"),
+                    list("enables")
+                )),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Dependency requirements keyed by (table, crate), including target-specific tables.
+pub fn collect_dependencies(manifest: &toml::Value) -> BTreeMap<(String, String), String> {
+    let mut tables: Vec<(String, &toml::Value)> = DEPENDENCY_TABLES
+        .iter()
+        .filter_map(|key| manifest.get(*key).map(|t| (key.to_string(), t)))
+        .collect();
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        for (cfg, target) in targets {
+            for key in DEPENDENCY_TABLES {
+                if let Some(table) = target.get(*key) {
+                    tables.push((format!("target.{}.{}", cfg, key), table));
+                }
+            }
+        }
+    }
+
+    let mut deps = BTreeMap::new();
+    for (table_name, table) in tables {
+        let Some(table) = table.as_table() else {
+            continue;
+        };
+        for (name, spec) in table {
+            deps.insert(
+                (table_name.clone(), name.clone()),
+                dependency_requirement(spec),
+            );
+        }
+    }
+    deps
+}
+
+/// A short description of where a dependency comes from, e.g. `1.0` or `git = <url>`.
+fn dependency_requirement(spec: &toml::Value) -> String {
+    if let Some(version) = spec.as_str() {
+        return version.to_string();
+    }
+    if let Some(version) = spec.get("version").and_then(|v| v.as_str()) {
+        return version.to_string();
+    }
+    for source in ["path", "git"] {
+        if let Some(location) = spec.get(source).and_then(|v| v.as_str()) {
+            return format!("{} = {}", source, location);
+        }
+    }
+    if spec.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+        return "workspace".to_string();
+    }
+    "*".to_string()
+}
+
+fn collect_features(manifest: &toml::Value) -> BTreeMap<String, Vec<String>> {
+    manifest
+        .get("features")
+        .and_then(|f| f.as_table())
+        .into_iter()
+        .flatten()
+        .map(|(name, enables)| {
+            let enables = enables
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.as_str())
+                .map(String::from)
+                .collect();
+            (name.clone(), enables)
+        })
+        .collect()
+}
@@ -0,0 +1,157 @@
+use crate::LintAction;
+use crate::utils::{output_json, output_text, run_command};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Headings `clippy-driver -W help` prints above clippy's own lints and groups.
+const LINTS_HEADING: &str = "Lint checks loaded by this crate:";
+const GROUPS_HEADING: &str = "Lint groups loaded by this crate:";
+
+pub async fn run(action: LintAction, json_output: bool) -> Result<()> {
+    match action {
+        LintAction::List { filter } => list_lints(filter.as_deref(), json_output).await,
+    }
+}
+
+async fn list_lints(filter: Option<&str>, json_output: bool) -> Result<()> {
+    info!("Listing clippy lints...");
+
+    // clippy-driver prints rustc's lint help with clippy's lints registered, without
+    // having to compile a crate first
+    let output = match run_command("clippy-driver", &["-W", "help"]) {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            if json_output {
+                output_json(&json!({
+                    "error": "clippy not installed",
+                    "suggestion": "rustup component add clippy"
+                }));
+            } else {
+                output_text("❌ clippy not installed");
+                output_text("💡 Install with: rustup component add clippy");
+            }
+            return Err(anyhow!("clippy not installed"));
+        }
+    };
+
+    let help = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let pattern = filter.map(|f| f.to_lowercase().replace('-', "_"));
+    let lints: Vec<Value> = parse_clippy_lints(&help)
+        .into_iter()
+        .filter(|lint| {
+            pattern.as_deref().is_none_or(|pattern| {
+                lint["name"]
+                    .as_str()
+                    .is_some_and(|name| name.contains(pattern))
+            })
+        })
+        .collect();
+
+    if json_output {
+        output_json(&json!({ "lints": lints }));
+        return Ok(());
+    }
+
+    output_text("📎 Clippy Lints");
+    output_text("===============");
+    if lints.is_empty() {
+        match filter {
+            Some(filter) => output_text(&format!("No lints match '{}'", filter)),
+            None => output_text("No lints found in clippy's help output"),
+        }
+        return Ok(());
+    }
+
+    let field = |lint: &Value, key: &str| lint[key].as_str().unwrap_or("").to_string();
+    let name_width = lints
+        .iter()
+        .map(|l| field(l, "name").len())
+        .max()
+        .unwrap_or(0);
+    let group_width = lints
+        .iter()
+        .map(|l| field(l, "group").len())
+        .max()
+        .unwrap_or(0);
+    output_text(&format!(
+        "  {:<name_width$}  {:<5}  {:<group_width$}  description",
+        "name",
+        "level",
+        "group",
+        name_width = name_width,
+        group_width = group_width
+    ));
+    for lint in &lints {
+        output_text(&format!(
+            "  {:<name_width$}  {:<5}  {:<group_width$}  {}",
+            field(lint, "name"),
+            field(lint, "level"),
+            field(lint, "group"),
+            field(lint, "description"),
+            name_width = name_width,
+            group_width = group_width
+        ));
+    }
+    output_text("");
+    output_text(&format!("📊 {} lint(s)", lints.len()));
+    if filter.is_none() {
+        output_text("💡 Narrow the list with --filter <pattern>");
+    }
+
+    Ok(())
+}
+
+/// Clippy's lints from `-W help` output as `{name, level, group, description}`, with names
+/// in the `clippy::snake_case` form used in attributes and `[lints]` tables.
+pub fn parse_clippy_lints(help: &str) -> Vec<Value> {
+    let mut lints = Vec::new();
+    let mut groups: HashMap<String, String> = HashMap::new();
+    let mut section = "";
+
+    for line in help.lines() {
+        let trimmed = line.trim();
+        if trimmed.ends_with(':') && !line.starts_with(' ') {
+            section = trimmed;
+            continue;
+        }
+        let Some((name, rest)) = trimmed.split_once(char::is_whitespace) else {
+            continue;
+        };
+        if !name.starts_with("clippy::") {
+            continue;
+        }
+        let name = name.replace('-', "_");
+
+        if section == LINTS_HEADING {
+            let (level, description) = rest
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .unwrap_or((rest.trim(), ""));
+            lints.push((name, level.to_string(), description.trim().to_string()));
+        } else if section == GROUPS_HEADING && name != "clippy::all" {
+            // `clippy::all` is the union of the default groups, not a category of its own
+            let group = name.trim_start_matches("clippy::").to_string();
+            for member in rest.split(',') {
+                groups.insert(member.trim().replace('-', "_"), group.clone());
+            }
+        }
+    }
+
+    lints
+        .into_iter()
+        .map(|(name, level, description)| {
+            json!({
+                "group": groups.get(&name),
+                "name": name,
+                "level": level,
+                "description": description
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,366 @@
+use crate::ManifestAction;
+use crate::utils::{DEPENDENCY_TABLES, is_rust_project, output_json, output_text};
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Editions cargo understands in `package.edition`.
+const VALID_EDITIONS: &[&str] = &["2015", "2018", "2021", "2024"];
+
+/// Conventional order of top-level manifest sections; anything else goes last.
+const SECTION_ORDER: &[&str] = &[
+    "cargo-features",
+    "package",
+    "workspace",
+    "lib",
+    "bin",
+    "example",
+    "test",
+    "bench",
+    "dependencies",
+    "dev-dependencies",
+    "build-dependencies",
+    "target",
+    "features",
+    "lints",
+    "patch",
+    "replace",
+    "badges",
+    "profile",
+];
+
+pub async fn run(action: ManifestAction, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string("Cargo.toml").context("Failed to read Cargo.toml")?;
+
+    match action {
+        ManifestAction::Show => show_manifest(&content, json_output),
+        ManifestAction::Validate => validate(&content, json_output),
+        ManifestAction::Format { in_place } => format_in_order(&content, in_place, json_output),
+    }
+}
+
+fn show_manifest(content: &str, json_output: bool) -> Result<()> {
+    let mut document = content
+        .parse::<toml_edit::DocumentMut>()
+        .context("Failed to parse Cargo.toml")?;
+
+    if json_output {
+        let manifest: toml::Value = toml::from_str(content)?;
+        output_json(&json!({ "manifest": manifest }));
+    } else {
+        order_sections(&mut document);
+        document.fmt();
+        output_text(document.to_string().trim_end());
+    }
+
+    Ok(())
+}
+
+fn validate(content: &str, json_output: bool) -> Result<()> {
+    info!("Validating Cargo.toml...");
+
+    let errors = validate_manifest(content);
+
+    if json_output {
+        output_json(&json!({
+            "valid": errors.is_empty(),
+            "errors": errors
+        }));
+    } else if errors.is_empty() {
+        output_text("✅ Cargo.toml is valid");
+    } else {
+        output_text(&format!(
+            "❌ Found {} problem(s) in Cargo.toml:",
+            errors.len()
+        ));
+        for error in &errors {
+            output_text(&format!(
+                "  • {}: {}",
+                error["field"].as_str().unwrap_or("Cargo.toml"),
+                error["message"].as_str().unwrap_or("")
+            ));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(anyhow!("Cargo.toml is invalid"));
+    }
+
+    Ok(())
+}
+
+fn format_in_order(content: &str, in_place: bool, json_output: bool) -> Result<()> {
+    let mut document = content
+        .parse::<toml_edit::DocumentMut>()
+        .context("Failed to parse Cargo.toml")?;
+    order_sections(&mut document);
+    let formatted = document.to_string();
+    let changed = formatted != content;
+
+    if in_place && changed {
+        std::fs::write("Cargo.toml", &formatted).context("Failed to write Cargo.toml")?;
+    }
+
+    if json_output {
+        output_json(&json!({
+            "changed": changed,
+            "in_place": in_place,
+            "content": if in_place { None } else { Some(&formatted) }
+        }));
+    } else if !in_place {
+        output_text(formatted.trim_end());
+    } else if changed {
+        output_text("✅ Reordered Cargo.toml sections");
+    } else {
+        output_text("✅ Cargo.toml sections are already in order");
+    }
+
+    Ok(())
+}
+
+/// Check a manifest for problems cargo or crates.io would reject, as `{field, message}` entries.
+pub fn validate_manifest(content: &str) -> Vec<Value> {
+    let mut errors = Vec::new();
+    let mut error = |field: &str, message: String| {
+        errors.push(json!({ "field": field, "message": message }));
+    };
+
+    let manifest: toml::Value = match toml::from_str(content) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            error("Cargo.toml", e.message().to_string());
+            return errors;
+        }
+    };
+
+    let package = manifest.get("package");
+    if package.is_none() && manifest.get("workspace").is_none() {
+        error(
+            "package",
+            "Missing [package] table (or [workspace] for a virtual manifest)".to_string(),
+        );
+    }
+
+    if let Some(package) = package {
+        match package.get("name").and_then(|n| n.as_str()) {
+            None => error("package.name", "Missing required field".to_string()),
+            Some(name) if !is_valid_crate_name(name) => error(
+                "package.name",
+                format!(
+                    "'{}' is not a valid crate name (use letters, digits, '-' or '_', starting with a letter)",
+                    name
+                ),
+            ),
+            Some(_) => {}
+        }
+
+        if let Some(version) = package.get("version").and_then(|v| v.as_str())
+            && !is_semver(version)
+        {
+            error(
+                "package.version",
+                format!("'{}' is not a valid semver version", version),
+            );
+        }
+
+        if let Some(edition) = package.get("edition").and_then(|e| e.as_str())
+            && !VALID_EDITIONS.contains(&edition)
+        {
+            error(
+                "package.edition",
+                format!(
+                    "'{}' is not a valid edition (expected one of {})",
+                    edition,
+                    VALID_EDITIONS.join(", ")
+                ),
+            );
+        }
+    }
+
+    let mut tables: Vec<(String, &toml::Value)> = DEPENDENCY_TABLES
+        .iter()
+        .filter_map(|key| manifest.get(*key).map(|t| (key.to_string(), t)))
+        .collect();
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        for (cfg, target) in targets {
+            for key in DEPENDENCY_TABLES {
+                if let Some(table) = target.get(*key) {
+                    tables.push((format!("target.{}.{}", cfg, key), table));
+                }
+            }
+        }
+    }
+
+    for (table_name, table) in tables {
+        let Some(table) = table.as_table() else {
+            continue;
+        };
+
+        // crates.io treats `-` and `_` as the same character, and renames can hide duplicates
+        let mut seen: HashMap<(String, String), &str> = HashMap::new();
+        for (key, spec) in table {
+            let field = format!("{}.{}", table_name, key);
+            if !is_valid_dependency_name(key) {
+                error(&field, format!("'{}' is not a valid dependency name", key));
+            }
+
+            let package = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+            let requirement = spec
+                .as_str()
+                .or_else(|| spec.get("version").and_then(|v| v.as_str()))
+                .unwrap_or("")
+                .to_string();
+            let normalized = (package.to_lowercase().replace('-', "_"), requirement);
+            if let Some(previous) = seen.insert(normalized, key) {
+                error(
+                    &field,
+                    format!("Duplicate of dependency '{}' in [{}]", previous, table_name),
+                );
+            }
+        }
+    }
+
+    errors
+}
+
+fn is_valid_crate_name(name: &str) -> bool {
+    name.len() <= 64
+        && name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn is_valid_dependency_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `MAJOR.MINOR.PATCH` with optional `-prerelease` and `+build` suffixes.
+fn is_semver(version: &str) -> bool {
+    let core = version.split(['-', '+']).next().unwrap_or("");
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Renumber table positions so sections print in `SECTION_ORDER`, keeping each section's
+/// sub-tables (e.g. `[profile.release]`) together and in their original order.
+fn order_sections(document: &mut toml_edit::DocumentMut) {
+    let rank = |key: &str| {
+        SECTION_ORDER
+            .iter()
+            .position(|section| *section == key)
+            .unwrap_or(SECTION_ORDER.len())
+    };
+
+    let root = document.as_table_mut();
+    let mut keys: Vec<(usize, usize, String)> = root
+        .iter()
+        .map(|(key, item)| (rank(key), first_position(item), key.to_string()))
+        .collect();
+    keys.sort();
+
+    let mut next_position = 1;
+    for (_, _, key) in keys {
+        if let Some(item) = root.get_mut(&key) {
+            renumber(item, &mut next_position);
+        }
+    }
+
+    // Moved tables keep their old blank-line prefix, so re-space the headers: nothing before
+    // the first one (unless root values precede it) and a blank line before every other one
+    let has_root_values = root.iter().any(|(_, item)| item.is_value());
+    let mut is_first = !has_root_values;
+    for position in 1..next_position {
+        for (_, item) in root.iter_mut() {
+            respace_header(item, position, &mut is_first);
+        }
+    }
+}
+
+fn respace_header(item: &mut toml_edit::Item, position: usize, is_first: &mut bool) {
+    let respace_table = |table: &mut toml_edit::Table, is_first: &mut bool| {
+        if table.position() == Some(position) && !table.is_implicit() && !table.is_dotted() {
+            let prefix = table
+                .decor()
+                .prefix()
+                .and_then(|p| p.as_str())
+                .unwrap_or("")
+                .trim_start_matches(['\n', '\r'])
+                .to_string();
+            let prefix = if *is_first {
+                prefix
+            } else {
+                format!("\n{}", prefix)
+            };
+            table.decor_mut().set_prefix(prefix);
+            *is_first = false;
+        }
+        for (_, child) in table.iter_mut() {
+            respace_header(child, position, is_first);
+        }
+    };
+
+    match item {
+        toml_edit::Item::Table(table) => respace_table(table, is_first),
+        toml_edit::Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                respace_table(table, is_first);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn first_position(item: &toml_edit::Item) -> usize {
+    match item {
+        toml_edit::Item::Table(table) => table.position().unwrap_or(usize::MAX),
+        toml_edit::Item::ArrayOfTables(array) => array
+            .iter()
+            .filter_map(|t| t.position())
+            .min()
+            .unwrap_or(usize::MAX),
+        // Plain values always print before the first table header
+        _ => 0,
+    }
+}
+
+fn renumber(item: &mut toml_edit::Item, next_position: &mut usize) {
+    let renumber_table = |table: &mut toml_edit::Table, next_position: &mut usize| {
+        table.set_position(*next_position);
+        *next_position += 1;
+        for (_, child) in table.iter_mut() {
+            renumber(child, next_position);
+        }
+    };
+
+    match item {
+        toml_edit::Item::Table(table) => renumber_table(table, next_position),
+        toml_edit::Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                renumber_table(table, next_position);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,143 @@
+use crate::commands::check::NIGHTLY_TARGET_DIR;
+use crate::commands::diff::{binary_size, count_tests, count_warnings};
+use crate::commands::info::collect_dependencies;
+use crate::utils::{
+    count_rust_loc, format_bytes, is_rust_project, output_json, output_text, read_manifest,
+    run_command,
+};
+use anyhow::Result;
+use serde_json::{Value, json};
+use std::path::Path;
+use tokio::task::spawn_blocking;
+use tracing::info;
+
+pub async fn run(json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    info!("Collecting project metrics...");
+
+    // The cargo steps share the target directory lock, so they take turns; the doc coverage
+    // run uses the nightly target dir and overlaps with them
+    let here = Path::new(".");
+    let (loc, dependencies, tests, warnings, doc_coverage, binary) = tokio::join!(
+        spawn_blocking(move || count_rust_loc(here) as u64),
+        spawn_blocking(|| {
+            read_manifest(Path::new("Cargo.toml"))
+                .ok()
+                .map(|manifest| collect_dependencies(&manifest).len() as u64)
+        }),
+        spawn_blocking(move || count_tests(here, None)),
+        spawn_blocking(move || count_warnings(here, None)),
+        spawn_blocking(doc_coverage),
+        spawn_blocking(move || binary_size(here, None)),
+    );
+
+    let metrics = json!({
+        "lines_of_code": loc.ok(),
+        "dependencies": dependencies.ok().flatten(),
+        "tests": tests.ok().flatten(),
+        "clippy_warnings": warnings.ok().flatten(),
+        "doc_coverage_percent": doc_coverage.ok().flatten(),
+        "binary_size_bytes": binary.ok().flatten()
+    });
+
+    if json_output {
+        output_json(&metrics);
+        return Ok(());
+    }
+
+    let number = |key: &str| metrics[key].as_u64().map(|n| n.to_string());
+    let rows = [
+        ("Lines of code", number("lines_of_code")),
+        ("Dependencies", number("dependencies")),
+        ("Tests", number("tests")),
+        ("Clippy warnings", number("clippy_warnings")),
+        (
+            "Doc coverage",
+            metrics["doc_coverage_percent"]
+                .as_f64()
+                .map(|percent| format!("{:.1}%", percent)),
+        ),
+        (
+            "Binary size",
+            metrics["binary_size_bytes"].as_u64().map(format_bytes),
+        ),
+    ];
+
+    output_text("📊 Project Metrics");
+    output_text("==================");
+    for (label, value) in &rows {
+        output_text(&format!(
+            "  {:<16} {}",
+            label,
+            value.as_deref().unwrap_or("n/a")
+        ));
+    }
+    if metrics["doc_coverage_percent"].is_null() {
+        output_text("");
+        output_text(
+            "💡 Doc coverage needs rustdoc's --show-coverage: rustup toolchain install nightly",
+        );
+    }
+
+    Ok(())
+}
+
+/// Percentage of documented items in the library (or sole target), measured with nightly
+/// rustdoc's `--show-coverage` since stable rustdoc doesn't report it.
+fn doc_coverage() -> Option<f64> {
+    let mut args = vec![
+        "+nightly",
+        "rustdoc",
+        "--quiet",
+        "--target-dir",
+        NIGHTLY_TARGET_DIR,
+    ];
+    if Path::new("src/lib.rs").exists() {
+        args.push("--lib");
+    }
+    args.extend([
+        "--",
+        "-Z",
+        "unstable-options",
+        "--show-coverage",
+        "--output-format",
+        "json",
+    ]);
+
+    let output = run_command("cargo", &args).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    doc_coverage_percent(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Documented items as a percentage of all items in rustdoc's JSON coverage report, which
+/// maps each file to `{total, with_docs, ..}`.
+pub fn doc_coverage_percent(report: &str) -> Option<f64> {
+    let files: Value = report
+        .lines()
+        .find(|line| line.trim_start().starts_with('{'))
+        .and_then(|line| serde_json::from_str(line).ok())?;
+    let (total, documented) =
+        files
+            .as_object()?
+            .values()
+            .fold((0, 0), |(total, documented), file| {
+                (
+                    total + file["total"].as_u64().unwrap_or(0),
+                    documented + file["with_docs"].as_u64().unwrap_or(0),
+                )
+            });
+    (total > 0).then(|| documented as f64 * 100.0 / total as f64)
+}
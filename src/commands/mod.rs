@@ -1,10 +1,37 @@
+pub mod bench;
 pub mod build;
 pub mod check;
+pub mod ci;
+pub mod codegen;
+pub mod completions;
+pub mod config;
 pub mod deps;
+pub mod diff;
 pub mod doctor;
 pub mod env;
+pub mod explain;
+pub mod flamegraph_diff;
+pub mod fmt;
+pub mod generate;
+pub mod gh;
 pub mod gpg;
+pub mod hooks;
 pub mod info;
 pub mod init;
+pub mod lint;
+pub mod manifest;
+pub mod metrics;
+pub mod outdated_toolchains;
+pub mod perf;
+pub mod perf_hint;
+pub mod publish_check;
+pub mod sbom;
+pub mod sign_release;
+pub mod target;
+pub mod test;
 pub mod toolchain;
 pub mod tools;
+pub mod trace;
+pub mod verify;
+pub mod vet;
+pub mod workspace;
@@ -0,0 +1,135 @@
+use crate::utils::{output_json, output_text, run_command};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use tracing::info;
+
+pub async fn run(update: bool, json_output: bool) -> Result<()> {
+    info!("Checking installed toolchains for updates...");
+
+    let output = match run_command("rustup", &["check"]) {
+        Ok(output) if output.status.success() => output,
+        result => {
+            let details = match result {
+                Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                Err(e) => e.to_string(),
+            };
+            if json_output {
+                output_json(&json!({
+                    "error": "rustup check failed",
+                    "details": details
+                }));
+            } else {
+                output_text("❌ rustup check failed");
+                output_text(&details);
+            }
+            return Err(anyhow!("rustup check failed"));
+        }
+    };
+
+    let mut toolchains = parse_rustup_check(&String::from_utf8_lossy(&output.stdout));
+
+    if update {
+        for toolchain in toolchains.iter_mut().filter(|t| t["up_to_date"] == false) {
+            let name = toolchain["name"].as_str().unwrap_or_default().to_string();
+            info!("Updating {}...", name);
+            let updated = run_command("rustup", &["update", &name])
+                .is_ok_and(|output| output.status.success());
+            toolchain["updated"] = json!(updated);
+        }
+    }
+
+    let outdated = toolchains
+        .iter()
+        .filter(|toolchain| toolchain["up_to_date"] == false)
+        .count();
+
+    if json_output {
+        output_json(&json!({ "toolchains": toolchains }));
+    } else {
+        output_text("🦀 Toolchain Updates");
+        output_text("====================");
+        for toolchain in &toolchains {
+            let name = toolchain["name"].as_str().unwrap_or_default();
+            let installed = toolchain["installed_version"].as_str().unwrap_or("?");
+            let date = toolchain["installed_date"]
+                .as_str()
+                .map(|date| format!(" ({})", date))
+                .unwrap_or_default();
+            if toolchain["up_to_date"] == true {
+                output_text(&format!("✅ {} - {}{}", name, installed, date));
+                continue;
+            }
+            output_text(&format!(
+                "⬆️  {} - {}{} -> {}",
+                name,
+                installed,
+                date,
+                toolchain["latest_version"].as_str().unwrap_or("?")
+            ));
+            match toolchain["updated"].as_bool() {
+                Some(true) => output_text("   ✅ Updated"),
+                Some(false) => output_text("   ❌ Update failed"),
+                None => output_text(&format!(
+                    "   💡 {}",
+                    toolchain["update_command"].as_str().unwrap_or_default()
+                )),
+            }
+        }
+        output_text("");
+        if outdated == 0 {
+            output_text("✅ All toolchains are up to date");
+        } else if update {
+            output_text(&format!("📊 {} toolchain(s) had updates", outdated));
+        } else {
+            output_text(&format!(
+                "📊 {} toolchain(s) have updates - run with --update to install them",
+                outdated
+            ));
+        }
+    }
+
+    if toolchains
+        .iter()
+        .any(|toolchain| toolchain["updated"] == false)
+    {
+        return Err(anyhow!("Some toolchains failed to update"));
+    }
+
+    Ok(())
+}
+
+/// Toolchains in `rustup check` output, whose lines look like
+/// `stable-<host> - Update available : 1.70.0 (90c541806 2023-05-31) -> 1.71.0 (..)` or
+/// `stable-<host> - Up to date : 1.71.0 (..)`. rustup's own line is skipped.
+pub fn parse_rustup_check(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, rest) = line.split_once(" - ")?;
+            let (status, versions) = rest.split_once(" : ")?;
+            let name = name.trim();
+            if name == "rustup" {
+                return None;
+            }
+            let (installed, latest) = match versions.split_once(" -> ") {
+                Some((installed, latest)) => (installed, latest),
+                None => (versions, versions),
+            };
+            let version = |full: &str| full.split_whitespace().next().map(String::from);
+            // The build date is the last word inside the parentheses
+            let installed_date = installed
+                .split_whitespace()
+                .last()
+                .filter(|word| word.ends_with(')'))
+                .map(|word| word.trim_end_matches(')').to_string());
+            Some(json!({
+                "name": name,
+                "up_to_date": status.trim() == "Up to date",
+                "installed_version": version(installed),
+                "installed_date": installed_date,
+                "latest_version": version(latest),
+                "update_command": format!("rustup update {}", name)
+            }))
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+use crate::utils::{
+    format_duration, is_rust_project, output_json, output_text, run_command,
+    run_command_with_timing,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::path::Path;
+use tracing::{error, info};
+
+pub const TIMING_REPORT: &str = "target/cargo-timings/cargo-timing.html";
+pub const TOP_SLOW_CRATES: usize = 10;
+
+pub async fn run(open: bool, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        let msg = "Not a Rust project (no Cargo.toml found)";
+        if json_output {
+            output_json(&json!({
+                "error": msg,
+                "success": false
+            }));
+        } else {
+            error!("{}", msg);
+        }
+        return Ok(());
+    }
+
+    info!("Profiling compile times with cargo build --timings...");
+
+    let (output, duration) = run_command_with_timing("cargo", &["build", "--timings"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "success": false,
+                "error": "cargo build --timings failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!(
+                "❌ Build failed after {}",
+                format_duration(duration)
+            ));
+            output_text(&stderr);
+        }
+        return Err(anyhow!("cargo build --timings failed"));
+    }
+
+    let report = std::fs::read_to_string(TIMING_REPORT)
+        .map_err(|e| anyhow!("Failed to read timing report {}: {}", TIMING_REPORT, e))?;
+    let mut units = parse_unit_data(&report);
+    units.sort_by(|a, b| b["total_ms"].as_u64().cmp(&a["total_ms"].as_u64()));
+    units.truncate(TOP_SLOW_CRATES);
+
+    if json_output {
+        output_json(&json!({
+            "top_slow_crates": units,
+            "report_path": TIMING_REPORT,
+            "build_duration": format_duration(duration)
+        }));
+    } else {
+        output_text("⏱️  Compile Time Profile");
+        output_text("=======================");
+        output_text(&format!("Total build time: {}", format_duration(duration)));
+        output_text("");
+
+        if units.is_empty() {
+            output_text("Nothing was compiled - everything was already up to date");
+            output_text("💡 Run `cargo clean` first for a full profile");
+        } else {
+            output_text("Slowest compilation units:");
+            for unit in &units {
+                output_text(&format!(
+                    "  {:>8}ms  (codegen {:>7}ms)  {}",
+                    unit["total_ms"].as_u64().unwrap_or(0),
+                    unit["codegen_ms"].as_u64().unwrap_or(0),
+                    unit["name"].as_str().unwrap_or("unknown")
+                ));
+            }
+        }
+
+        output_text("");
+        output_text(&format!("📄 Full report: {}", TIMING_REPORT));
+    }
+
+    if open {
+        open_in_browser(Path::new(TIMING_REPORT))?;
+    }
+
+    Ok(())
+}
+
+/// Extract per-unit timings from the `UNIT_DATA` array embedded in cargo's HTML report.
+pub fn parse_unit_data(html: &str) -> Vec<Value> {
+    let Some(start) = html.find("const UNIT_DATA = ") else {
+        return Vec::new();
+    };
+    let data = &html[start + "const UNIT_DATA = ".len()..];
+    let Some(end) = data.find("];") else {
+        return Vec::new();
+    };
+
+    let Ok(Value::Array(units)) = serde_json::from_str::<Value>(&data[..=end]) else {
+        return Vec::new();
+    };
+
+    units
+        .iter()
+        .filter_map(|unit| {
+            let name = unit.get("name")?.as_str()?;
+            let duration = unit.get("duration")?.as_f64()?;
+            // Units that were already fresh show up with a zero duration
+            if duration <= 0.0 {
+                return None;
+            }
+            let target = unit
+                .get("target")
+                .and_then(|t| t.as_str())
+                .unwrap_or("")
+                .trim();
+
+            // Older cargo reports `rmeta_time`; newer cargo splits the unit into sections
+            let codegen = if let Some(rmeta) = unit.get("rmeta_time").and_then(|r| r.as_f64()) {
+                duration - rmeta
+            } else {
+                unit.get("sections")
+                    .and_then(|s| s.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter(|section| section[0].as_str() == Some("codegen"))
+                    .filter_map(|section| {
+                        Some(section[1]["end"].as_f64()? - section[1]["start"].as_f64()?)
+                    })
+                    .sum()
+            };
+
+            let display_name = if target.is_empty() {
+                name.to_string()
+            } else {
+                format!("{} ({})", name, target)
+            };
+
+            Some(json!({
+                "name": display_name,
+                "version": unit.get("version").and_then(|v| v.as_str()),
+                "codegen_ms": (codegen * 1000.0).round() as u64,
+                "total_ms": (duration * 1000.0).round() as u64
+            }))
+        })
+        .collect()
+}
+
+fn open_in_browser(path: &Path) -> Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+
+    run_command(opener, &[&path.to_string_lossy()])?;
+    Ok(())
+}
@@ -0,0 +1,266 @@
+use crate::utils::{find_files, is_rust_project, output_json, output_text};
+use anyhow::Result;
+use serde_json::{Value, json};
+use std::path::Path;
+use tracing::info;
+
+/// Lines an `fn` signature may span before we give up looking for its parameters.
+const MAX_SIGNATURE_LINES: usize = 10;
+
+pub async fn run(json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    info!("Scanning sources for performance anti-patterns...");
+
+    let hints: Vec<Value> = find_files(Path::new("."), &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    })
+    .iter()
+    .filter_map(|path| {
+        let source = std::fs::read_to_string(path).ok()?;
+        let file = path.strip_prefix(".").unwrap_or(path).display().to_string();
+        Some(
+            find_perf_hints(&source)
+                .into_iter()
+                .map(move |mut hint| {
+                    hint["file"] = json!(file);
+                    hint
+                })
+                .collect::<Vec<_>>(),
+        )
+    })
+    .flatten()
+    .collect();
+
+    if json_output {
+        output_json(&json!({ "hints": hints }));
+        return Ok(());
+    }
+
+    output_text("🏎️  Performance Hints");
+    output_text("====================");
+
+    if hints.is_empty() {
+        output_text("✅ No common performance anti-patterns found");
+        return Ok(());
+    }
+
+    let mut current_file = "";
+    for hint in &hints {
+        let file = hint["file"].as_str().unwrap_or("");
+        if file != current_file {
+            output_text("");
+            output_text(&format!("📄 {}", file));
+            current_file = file;
+        }
+        output_text(&format!(
+            "  L{:<5} [{}] {}",
+            hint["line"].as_u64().unwrap_or(0),
+            hint["kind"].as_str().unwrap_or(""),
+            hint["suggestion"].as_str().unwrap_or("")
+        ));
+    }
+
+    output_text("");
+    output_text(&format!(
+        "📊 {} hints - these are heuristics, so profile before optimizing",
+        hints.len()
+    ));
+
+    Ok(())
+}
+
+/// Heuristic performance hints for `source` as `{kind, line, suggestion}`, with 1-based
+/// lines. Loops are tracked by brace depth, so this is a line scanner, not a parser.
+pub fn find_perf_hints(source: &str) -> Vec<Value> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut hints = Vec::new();
+    let mut hint = |kind: &str, line: usize, suggestion: &str| {
+        hints.push(json!({
+            "kind": kind,
+            "line": line + 1,
+            "suggestion": suggestion
+        }));
+    };
+
+    let mut depth = 0usize;
+    // Brace depth of the body of each enclosing loop
+    let mut loops: Vec<usize> = Vec::new();
+    let mut pending_loop = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let code = strip_strings_and_comments(line);
+        let trimmed = code.trim();
+        let in_loop = !loops.is_empty();
+
+        if in_loop && code.contains(".clone()") {
+            hint(
+                "clone_in_loop",
+                i,
+                "`.clone()` inside a loop allocates on every iteration; borrow, or clone once before the loop",
+            );
+        }
+        if in_loop && code.contains("format!(") {
+            hint(
+                "format_in_loop",
+                i,
+                "`format!` inside a loop allocates a new String each time; reuse a buffer with `write!`",
+            );
+        }
+
+        if trimmed.contains("fn ") && !trimmed.starts_with("//") {
+            let signature = fn_signature(&lines[i..]);
+            if signature.contains("Box<dyn ") {
+                hint(
+                    "box_dyn",
+                    i,
+                    "`Box<dyn Trait>` in a signature forces dynamic dispatch; consider a generic or `impl Trait`",
+                );
+            }
+            if has_owned_string_param(&signature) {
+                hint(
+                    "string_param",
+                    i,
+                    "`String` parameter forces callers to allocate; take `&str` unless the function stores it",
+                );
+            }
+        }
+
+        if code.contains("BTreeMap::new()") || code.contains("BTreeMap::with_capacity(") {
+            hint(
+                "btreemap",
+                i,
+                "`BTreeMap` keeps keys sorted at O(log n) per operation; use `HashMap` if ordering isn't needed",
+            );
+        }
+
+        let starts_loop = trimmed.starts_with("for ")
+            || trimmed.starts_with("while ")
+            || trimmed.starts_with("loop ")
+            || trimmed == "loop"
+            || trimmed.contains("=> for ")
+            || trimmed.contains("= loop ");
+        pending_loop |= starts_loop;
+
+        for c in code.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    if pending_loop {
+                        loops.push(depth);
+                        pending_loop = false;
+                    }
+                }
+                '}' => {
+                    if loops.last() == Some(&depth) {
+                        loops.pop();
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    hints
+}
+
+/// The text of an `fn` signature starting at `lines[0]`, up to its body or `;`.
+fn fn_signature(lines: &[&str]) -> String {
+    let mut signature = String::new();
+    for line in lines.iter().take(MAX_SIGNATURE_LINES) {
+        let code = strip_strings_and_comments(line);
+        let end = code.find(['{', ';']);
+        signature.push_str(&code[..end.unwrap_or(code.len())]);
+        signature.push(' ');
+        if end.is_some() {
+            break;
+        }
+    }
+    signature
+}
+
+/// Whether a parameter is declared as `String` or `&String`; return types don't count.
+fn has_owned_string_param(signature: &str) -> bool {
+    let Some((_, rest)) = signature.split_once('(') else {
+        return false;
+    };
+    // Cut at the `)` matching the opening one, so tuple return types are left out
+    let mut depth = 0usize;
+    let end = rest.char_indices().find_map(|(i, c)| match c {
+        '(' => {
+            depth += 1;
+            None
+        }
+        ')' if depth == 0 => Some(i),
+        ')' => {
+            depth -= 1;
+            None
+        }
+        _ => None,
+    });
+    rest[..end.unwrap_or(rest.len())].split(',').any(|param| {
+        param
+            .split_once(':')
+            .map(|(_, ty)| ty.trim())
+            .is_some_and(|ty| ty == "String" || ty == "&String" || ty == "&mut String")
+    })
+}
+
+/// `line` with string/char literal contents and `//` comments removed, so braces and
+/// patterns inside them are ignored.
+fn strip_strings_and_comments(line: &str) -> String {
+    let mut code = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => {
+                    in_string = false;
+                    code.push('"');
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                code.push('"');
+            }
+            '/' if chars.peek() == Some(&'/') => break,
+            // Char literals like '{', but not lifetimes like 'a
+            '\'' => {
+                let rest: String = chars.clone().take(3).collect();
+                if let Some(escaped) = rest.strip_prefix('\\') {
+                    if let Some(end) = escaped.find('\'') {
+                        for _ in 0..end + 2 {
+                            chars.next();
+                        }
+                    }
+                } else if rest.chars().nth(1) == Some('\'') {
+                    chars.next();
+                    chars.next();
+                } else {
+                    code.push(c);
+                }
+            }
+            _ => code.push(c),
+        }
+    }
+    code
+}
@@ -0,0 +1,228 @@
+use crate::utils::{
+    cargo_messages, is_rust_project, output_json, output_text, read_manifest, run_command,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::path::Path;
+use tracing::info;
+
+/// Fields crates.io refuses to publish without; `license-file` may replace `license`.
+const REQUIRED_FIELDS: [&str; 2] = ["description", "license"];
+
+pub async fn run(json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let manifest = read_manifest(Path::new("Cargo.toml"))?;
+    let Some(package) = manifest.get("package") else {
+        if json_output {
+            output_json(&json!({
+                "error": "No [package] in Cargo.toml",
+                "suggestion": "Run oxy publish-check in the directory of the crate to publish"
+            }));
+        } else {
+            output_text("❌ No [package] in Cargo.toml");
+            output_text("💡 Run oxy publish-check in the directory of the crate to publish");
+        }
+        return Err(anyhow!("No [package] in Cargo.toml"));
+    };
+    let name = package
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+
+    let mut findings = Vec::new();
+    let mut finding = |severity: &str, description: String, file: Option<&str>| {
+        findings.push(json!({
+            "severity": severity,
+            "description": description,
+            "file": file
+        }));
+    };
+
+    for field in REQUIRED_FIELDS {
+        let missing = package.get(field).is_none()
+            && !(field == "license" && package.get("license-file").is_some());
+        if missing {
+            finding(
+                "error",
+                format!("[package] has no `{}`, which crates.io requires", field),
+                Some("Cargo.toml"),
+            );
+        }
+    }
+
+    if let Some(publish) = package.get("publish")
+        && publish.as_bool() == Some(false)
+    {
+        finding(
+            "error",
+            "`publish = false` prevents publishing this crate".to_string(),
+            Some("Cargo.toml"),
+        );
+    }
+
+    if let Some(url) = package.get("documentation").and_then(|d| d.as_str())
+        && !is_docs_rs_url(url, name)
+    {
+        finding(
+            "warning",
+            format!(
+                "`documentation` is {}, not https://docs.rs/{} where docs.rs will build it",
+                url, name
+            ),
+            Some("Cargo.toml"),
+        );
+    }
+
+    // Patches only apply when building this workspace, not for users of the published crate
+    if let Some(patch) = manifest.get("patch").and_then(|p| p.as_table()) {
+        for (source, crates) in patch {
+            let crates: Vec<&str> = crates
+                .as_table()
+                .map(|t| t.keys().map(String::as_str).collect())
+                .unwrap_or_default();
+            finding(
+                "warning",
+                format!(
+                    "[patch.{}] ({}) won't apply for users of the published crate",
+                    source,
+                    crates.join(", ")
+                ),
+                Some("Cargo.toml"),
+            );
+        }
+    }
+
+    info!("Listing packaged files with cargo package --list...");
+    let output = run_command("cargo", &["package", "--list", "--allow-dirty"])?;
+    if output.status.success() {
+        let listing = String::from_utf8_lossy(&output.stdout);
+        for file in secret_files(listing.lines()) {
+            finding(
+                "error",
+                "File that may contain secrets would be published; add it to `exclude` in Cargo.toml"
+                    .to_string(),
+                Some(file),
+            );
+        }
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        finding(
+            "error",
+            format!("cargo package --list failed: {}", stderr.trim()),
+            None,
+        );
+    }
+
+    let has_examples = Path::new("examples").is_dir() || manifest.get("example").is_some();
+    if has_examples {
+        info!("Checking that the examples compile...");
+        let output = run_command("cargo", &["check", "--examples", "--message-format=json"])?;
+        if !output.status.success() {
+            let mut files: Vec<String> = cargo_messages(&output, "compiler-message")
+                .iter()
+                .filter(|message| message["message"]["level"] == "error")
+                .filter_map(|message| primary_span_file(&message["message"]))
+                .collect();
+            files.sort();
+            files.dedup();
+            if files.is_empty() {
+                finding("error", "Examples don't compile".to_string(), None);
+            }
+            for file in &files {
+                finding("error", "Example doesn't compile".to_string(), Some(file));
+            }
+        }
+    }
+
+    let passed = findings.is_empty();
+
+    if json_output {
+        output_json(&json!({
+            "passed": passed,
+            "findings": findings
+        }));
+    } else {
+        output_text(&format!("📦 Publish Check: {}", name));
+        output_text("==================");
+        for finding in &findings {
+            let icon = if finding["severity"] == "error" {
+                "❌"
+            } else {
+                "⚠️ "
+            };
+            let file = finding["file"]
+                .as_str()
+                .map(|file| format!(" ({})", file))
+                .unwrap_or_default();
+            output_text(&format!(
+                "{} {}{}",
+                icon,
+                finding["description"].as_str().unwrap_or(""),
+                file
+            ));
+        }
+        if passed {
+            output_text("✅ Ready to publish - run `cargo publish --dry-run` for the final check");
+        } else {
+            output_text("");
+            output_text(&format!("📊 {} finding(s)", findings.len()));
+        }
+    }
+
+    if !passed {
+        return Err(anyhow!("Publish check found {} issue(s)", findings.len()));
+    }
+
+    Ok(())
+}
+
+/// Packaged files that commonly hold secrets: `.env` files (other than examples), and
+/// `.pem` and `.key` files.
+pub fn secret_files<'a>(files: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    files
+        .filter(|file| {
+            let name = Path::new(file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let env_file = (name == ".env" || name.starts_with(".env."))
+                && !matches!(
+                    name.as_str(),
+                    ".env.example" | ".env.sample" | ".env.template"
+                );
+            env_file || name.ends_with(".pem") || name.ends_with(".key")
+        })
+        .collect()
+}
+
+/// Whether `url` is the crate's docs.rs page, e.g. `https://docs.rs/serde` or
+/// `https://docs.rs/serde/latest/serde/`.
+pub fn is_docs_rs_url(url: &str, name: &str) -> bool {
+    let Some(path) = url
+        .strip_prefix("https://docs.rs/")
+        .or_else(|| url.strip_prefix("http://docs.rs/"))
+    else {
+        return false;
+    };
+    path.split('/').next() == Some(name)
+}
+
+/// File of the primary span of a compiler diagnostic.
+fn primary_span_file(message: &Value) -> Option<String> {
+    message["spans"]
+        .as_array()?
+        .iter()
+        .find(|span| span["is_primary"] == true)
+        .and_then(|span| span["file_name"].as_str().map(String::from))
+}
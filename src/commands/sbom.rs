@@ -0,0 +1,329 @@
+use crate::SbomFormat;
+use crate::history::unix_timestamp;
+use crate::utils::{
+    LockedPackage, cargo_subcommand_installed, crates_io_version, is_rust_project, output_json,
+    output_text, read_lockfile, read_manifest, registry_source_dir, run_command,
+};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Placeholder SPDX uses for values that weren't determined.
+const NOASSERTION: &str = "NOASSERTION";
+
+/// A package listed in the generated SBOM.
+pub struct SbomPackage {
+    pub name: String,
+    pub version: String,
+    pub license: Option<String>,
+    /// Whether it comes from crates.io, which gives it a known download location
+    pub from_crates_io: bool,
+    /// Workspace packages have no source and are what the document describes
+    pub is_local: bool,
+}
+
+impl SbomPackage {
+    pub fn purl(&self) -> String {
+        format!("pkg:cargo/{}@{}", self.name, self.version)
+    }
+
+    fn spdx_id(&self) -> String {
+        let id: String = format!("{}-{}", self.name, self.version)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        format!("SPDXRef-Package-{}", id)
+    }
+}
+
+pub async fn run(format: SbomFormat, output: PathBuf, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    // cargo-sbom only writes JSON, so it can stand in for the CycloneDX document but not for
+    // SPDX tag-value; the format written must not depend on what's installed
+    let delegate = matches!(format, SbomFormat::CycloneDx) && cargo_subcommand_installed("sbom");
+    let (generator, package_count) = if delegate {
+        info!("Generating SBOM with cargo-sbom...");
+        let result = run_command("cargo", &["sbom", "--output-format", "cyclone_dx_json_1_4"])?;
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            if json_output {
+                output_json(&json!({
+                    "error": "cargo sbom failed",
+                    "details": stderr.trim()
+                }));
+            } else {
+                output_text("❌ cargo sbom failed");
+                output_text(stderr.trim());
+            }
+            return Err(anyhow!("cargo sbom failed"));
+        }
+        std::fs::write(&output, &result.stdout)
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+        ("cargo-sbom", None)
+    } else {
+        let lock_path = Path::new("Cargo.lock");
+        if !lock_path.exists() {
+            if json_output {
+                output_json(&json!({
+                    "error": "Cargo.lock not found",
+                    "suggestion": "Run `cargo generate-lockfile` first"
+                }));
+            } else {
+                output_text("❌ Cargo.lock not found");
+                output_text("💡 Run `cargo generate-lockfile` first");
+            }
+            return Err(anyhow!("Cargo.lock not found"));
+        }
+
+        info!("Generating SBOM from Cargo.lock...");
+        let mut locked = read_lockfile(lock_path)?;
+        locked.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+        let packages: Vec<SbomPackage> = locked
+            .iter()
+            .map(|package| SbomPackage {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                license: package_license(package),
+                from_crates_io: package.source_kind() == "crates.io",
+                is_local: package.source.is_none(),
+            })
+            .collect();
+
+        let name = document_name();
+        let created = iso8601_utc(unix_timestamp());
+        let document = match format {
+            SbomFormat::Spdx => spdx_tag_value(&name, &packages, &created),
+            SbomFormat::CycloneDx => cyclonedx_json(&packages, &created),
+        };
+        std::fs::write(&output, document)
+            .with_context(|| format!("Failed to write {}", output.display()))?;
+        ("oxygen", Some(packages.len()))
+    };
+
+    let format_name = match format {
+        SbomFormat::Spdx => "SPDX",
+        SbomFormat::CycloneDx => "CycloneDX",
+    };
+    if json_output {
+        output_json(&json!({
+            "format": format_name,
+            "output": output,
+            "generator": generator,
+            "package_count": package_count
+        }));
+    } else {
+        match package_count {
+            Some(count) => output_text(&format!(
+                "✅ Wrote {} SBOM with {} packages to {}",
+                format_name,
+                count,
+                output.display()
+            )),
+            None => output_text(&format!(
+                "✅ Wrote {} SBOM to {} (generated by cargo-sbom)",
+                format_name,
+                output.display()
+            )),
+        }
+        if generator == "oxygen" && matches!(format, SbomFormat::CycloneDx) {
+            output_text("💡 Install cargo-sbom for a more complete SBOM: cargo install cargo-sbom");
+        }
+    }
+
+    Ok(())
+}
+
+/// Declared license of a locked package, from its downloaded sources or crates.io.
+fn package_license(package: &LockedPackage) -> Option<String> {
+    let license = if package.source.is_none() {
+        // Workspace members; only the root manifest is known without `cargo metadata`
+        read_manifest(Path::new("Cargo.toml"))
+            .ok()
+            .and_then(|manifest| manifest.get("package").cloned())
+            .filter(|root| root.get("name").and_then(|n| n.as_str()) == Some(&package.name))
+            .and_then(|root| root.get("license")?.as_str().map(String::from))
+    } else {
+        registry_license(&package.name, &package.version).or_else(|| {
+            (package.source_kind() == "crates.io")
+                .then(|| crates_io_version(&package.name, &package.version).ok())
+                .flatten()
+                .and_then(|info| info["license"].as_str().map(String::from))
+        })
+    };
+    // Cargo still accepts the old `MIT/Apache-2.0` form, which isn't an SPDX expression
+    license.map(|license| license.replace('/', " OR "))
+}
+
+/// License from the package's sources under `$CARGO_HOME/registry/src`, if downloaded.
+fn registry_license(name: &str, version: &str) -> Option<String> {
+    let manifest = read_manifest(&registry_source_dir(name, version)?.join("Cargo.toml")).ok()?;
+    manifest
+        .get("package")?
+        .get("license")?
+        .as_str()
+        .map(String::from)
+}
+
+/// The package name, or the directory name for a virtual workspace.
+fn document_name() -> String {
+    read_manifest(Path::new("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| {
+            manifest
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(String::from)
+        })
+        .or_else(|| {
+            std::env::current_dir()
+                .ok()?
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "project".to_string())
+}
+
+/// A minimal SPDX 2.3 tag-value document listing `packages`.
+pub fn spdx_tag_value(name: &str, packages: &[SbomPackage], created: &str) -> String {
+    let mut document = format!(
+        "SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\nSPDXID: SPDXRef-DOCUMENT\nDocumentName: {}\nDocumentNamespace: https://spdx.org/spdxdocs/{}-{}\nCreator: Tool: oxygen-{}\nCreated: {}\n",
+        name,
+        name,
+        created,
+        env!("CARGO_PKG_VERSION"),
+        created
+    );
+
+    for package in packages {
+        let download_location = if package.from_crates_io {
+            format!(
+                "https://crates.io/api/v1/crates/{}/{}/download",
+                package.name, package.version
+            )
+        } else {
+            NOASSERTION.to_string()
+        };
+        document.push_str(&format!(
+            "\nPackageName: {}\nSPDXID: {}\nPackageVersion: {}\nPackageDownloadLocation: {}\nFilesAnalyzed: false\nPackageLicenseConcluded: {}\nPackageLicenseDeclared: {}\nPackageCopyrightText: {}\nExternalRef: PACKAGE-MANAGER purl {}\n",
+            package.name,
+            package.spdx_id(),
+            package.version,
+            download_location,
+            NOASSERTION,
+            package.license.as_deref().unwrap_or(NOASSERTION),
+            NOASSERTION,
+            package.purl()
+        ));
+    }
+
+    document.push('\n');
+    for package in packages.iter().filter(|package| package.is_local) {
+        document.push_str(&format!(
+            "Relationship: SPDXRef-DOCUMENT DESCRIBES {}\n",
+            package.spdx_id()
+        ));
+    }
+    document
+}
+
+/// A minimal CycloneDX 1.5 JSON document listing `packages`.
+fn cyclonedx_json(packages: &[SbomPackage], created: &str) -> String {
+    let components: Vec<_> = packages
+        .iter()
+        .map(|package| {
+            let mut component = json!({
+                "type": if package.is_local { "application" } else { "library" },
+                "bom-ref": package.purl(),
+                "name": package.name,
+                "version": package.version,
+                "purl": package.purl()
+            });
+            if let Some(license) = &package.license {
+                component["licenses"] = json!([{ "expression": license }]);
+            }
+            component
+        })
+        .collect();
+
+    let bom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.4",
+        "version": 1,
+        "metadata": {
+            "timestamp": created,
+            "tools": [{ "name": "oxygen", "version": env!("CARGO_PKG_VERSION") }]
+        },
+        "components": components
+    });
+    format!(
+        "{}\n",
+        serde_json::to_string_pretty(&bom).unwrap_or_default()
+    )
+}
+
+/// Days since the Unix epoch of the date in an ISO 8601 timestamp such as crates.io's
+/// `2024-01-15T12:34:56.789+00:00`; the time of day is ignored.
+pub fn iso8601_days(timestamp: &str) -> Option<i64> {
+    let date = timestamp.get(..10)?;
+    let mut parts = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Inverse of the civil date conversion in `iso8601_utc`
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * mp + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Format seconds since the Unix epoch as an ISO 8601 UTC timestamp.
+pub fn iso8601_utc(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
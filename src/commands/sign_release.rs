@@ -0,0 +1,208 @@
+use crate::commands::build::{BuildTarget, find_artifacts};
+use crate::commands::gpg::detach_sign;
+use crate::utils::{
+    format_duration, is_rust_project, output_json, output_text, read_manifest, run_command,
+    run_command_with_timing, sha256_file, sha512_file,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const DEFAULT_OUTPUT_DIR: &str = "release";
+
+pub async fn run(
+    version: Option<String>,
+    output_dir: Option<PathBuf>,
+    tag: bool,
+    json_output: bool,
+) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let version = version.or_else(|| {
+        read_manifest(Path::new("Cargo.toml"))
+            .ok()?
+            .get("package")?
+            .get("version")?
+            .as_str()
+            .map(String::from)
+    });
+    let output_dir = output_dir.unwrap_or_else(|| PathBuf::from(DEFAULT_OUTPUT_DIR));
+
+    info!("Building release binary...");
+    let (output, duration) = run_command_with_timing("cargo", &["build", "--release"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo build --release failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!(
+                "❌ Build failed after {}",
+                format_duration(duration)
+            ));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo build --release failed"));
+    }
+
+    let Some(binary) = find_artifacts(&BuildTarget::Default)
+        .first()
+        .and_then(|artifact| artifact["path"].as_str())
+        .map(PathBuf::from)
+    else {
+        if json_output {
+            output_json(&json!({
+                "error": "No release binary found",
+                "suggestion": "sign-release needs a package with a binary target"
+            }));
+        } else {
+            output_text("❌ No release binary found in target/release");
+            output_text("💡 sign-release needs a package with a binary target");
+        }
+        return Err(anyhow!("No release binary found"));
+    };
+
+    std::fs::create_dir_all(&output_dir)?;
+    let file_name = binary
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid binary path: {}", binary.display()))?;
+    let released_binary = output_dir.join(file_name);
+    std::fs::copy(&binary, &released_binary)?;
+    let released_binary = released_binary.to_string_lossy().to_string();
+
+    info!("Signing {}...", released_binary);
+    let signature = match detach_sign(&released_binary) {
+        Ok(signature) => signature,
+        Err(e) => {
+            if json_output {
+                output_json(&json!({
+                    "error": format!("Failed to sign {}", released_binary),
+                    "details": e.to_string(),
+                    "suggestion": "Run 'oxy gpg setup' to configure GPG signing"
+                }));
+            } else {
+                output_text(&format!("❌ Failed to sign {}", released_binary));
+                output_text(&format!("Error: {}", e));
+                output_text("💡 Run 'oxy gpg setup' to configure GPG signing");
+            }
+            return Err(anyhow!("Failed to sign release binary: {}", e));
+        }
+    };
+
+    info!("Generating checksums...");
+    let mut artifacts = Vec::new();
+    let mut sha256_sums = String::new();
+    let mut sha512_sums = String::new();
+    for (kind, path) in [("binary", &released_binary), ("signature", &signature)] {
+        let path = Path::new(path);
+        let sha256 = sha256_file(path)?;
+        let sha512 = sha512_file(path)?;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        sha256_sums.push_str(&format!("{}  {}\n", sha256, name));
+        sha512_sums.push_str(&format!("{}  {}\n", sha512, name));
+        artifacts.push(json!({
+            "kind": kind,
+            "path": path,
+            "sha256": sha256,
+            "sha512": sha512
+        }));
+    }
+    for (name, sums) in [("SHA256SUMS", &sha256_sums), ("SHA512SUMS", &sha512_sums)] {
+        let path = output_dir.join(name);
+        std::fs::write(&path, sums)?;
+        artifacts.push(json!({ "kind": "checksums", "path": path }));
+    }
+
+    let git_tag = if tag {
+        Some(create_signed_tag(version.as_deref(), json_output)?)
+    } else {
+        None
+    };
+
+    if json_output {
+        output_json(&json!({
+            "version": version,
+            "output_dir": output_dir,
+            "artifacts": artifacts,
+            "git_tag": git_tag
+        }));
+    } else {
+        output_text(&format!(
+            "✅ Signed release {} in {}",
+            version.as_deref().unwrap_or("build"),
+            output_dir.display()
+        ));
+        output_text("📦 Artifacts:");
+        for artifact in &artifacts {
+            output_text(&format!("  • {}", display_artifact(artifact)));
+        }
+        if let Some(git_tag) = &git_tag {
+            output_text(&format!("🏷️  Created signed tag {}", git_tag));
+            output_text(&format!("💡 Push it with: git push origin {}", git_tag));
+        }
+    }
+
+    Ok(())
+}
+
+fn display_artifact(artifact: &Value) -> String {
+    let path = artifact["path"].as_str().unwrap_or("");
+    match artifact["sha256"].as_str() {
+        Some(sha256) => format!("{} (sha256 {})", path, &sha256[..sha256.len().min(16)]),
+        None => path.to_string(),
+    }
+}
+
+/// Create a GPG-signed `v<version>` tag on the current commit and return its name.
+fn create_signed_tag(version: Option<&str>, json_output: bool) -> Result<String> {
+    let Some(version) = version else {
+        if json_output {
+            output_json(&json!({
+                "error": "No version to tag",
+                "suggestion": "Pass a version or set package.version in Cargo.toml"
+            }));
+        } else {
+            output_text("❌ No version to tag");
+            output_text("💡 Pass a version or set package.version in Cargo.toml");
+        }
+        return Err(anyhow!("No version to tag"));
+    };
+
+    let tag = format!("v{}", version.trim_start_matches('v'));
+    info!("Creating signed tag {}...", tag);
+    let output = run_command(
+        "git",
+        &["tag", "-s", &tag, "-m", &format!("Release {}", tag)],
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": format!("Failed to create tag {}", tag),
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ Failed to create tag {}", tag));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("git tag -s {} failed", tag));
+    }
+
+    Ok(tag)
+}
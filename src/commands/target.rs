@@ -0,0 +1,166 @@
+use crate::TargetAction;
+use crate::utils::{output_json, output_text, run_command};
+use anyhow::{Context, Result, anyhow};
+use serde_json::json;
+use std::path::Path;
+use tracing::info;
+
+pub async fn run(action: TargetAction, json_output: bool) -> Result<()> {
+    match action {
+        TargetAction::List { installed_only } => list_targets(installed_only, json_output).await,
+        TargetAction::Add {
+            triple,
+            set_default,
+        } => add_target(&triple, set_default, json_output).await,
+        TargetAction::Remove { triple } => remove_target(&triple, json_output).await,
+    }
+}
+
+async fn list_targets(installed_only: bool, json_output: bool) -> Result<()> {
+    info!("Listing compilation targets...");
+
+    let output = run_command("rustup", &["target", "list"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "rustup target list failed",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ Failed to list targets");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("rustup target list failed"));
+    }
+
+    // rustup marks installed targets with an "(installed)" suffix
+    let targets: Vec<_> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let triple = parts.next()?;
+            let installed = parts.any(|part| part == "(installed)");
+            Some(json!({ "triple": triple, "installed": installed }))
+        })
+        .filter(|target| !installed_only || target["installed"] == true)
+        .collect();
+
+    if json_output {
+        output_json(&json!({ "targets": targets }));
+    } else {
+        output_text("🎯 Compilation Targets");
+        output_text("======================");
+        for target in &targets {
+            let triple = target["triple"].as_str().unwrap_or("unknown");
+            if target["installed"] == true {
+                output_text(&format!("  {} ✅", triple));
+            } else {
+                output_text(&format!("  {}", triple));
+            }
+        }
+        output_text("");
+        output_text("💡 Use `oxy target add <triple>` to install a target");
+    }
+
+    Ok(())
+}
+
+async fn add_target(triple: &str, set_default: bool, json_output: bool) -> Result<()> {
+    info!("Adding target: {}", triple);
+
+    let output = run_command("rustup", &["target", "add", triple])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "action": "add",
+                "triple": triple,
+                "status": "error",
+                "error": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ Failed to add target: {}", triple));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("Failed to add target: {}", triple));
+    }
+
+    let config_path = if set_default {
+        Some(set_default_target(triple)?)
+    } else {
+        None
+    };
+
+    if json_output {
+        output_json(&json!({
+            "action": "add",
+            "triple": triple,
+            "status": "success",
+            "default_set_in": config_path
+        }));
+    } else {
+        output_text(&format!("✅ Added target: {}", triple));
+        if let Some(path) = &config_path {
+            output_text(&format!(
+                "🎯 Set build.target = \"{}\" in {}",
+                triple,
+                path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Make `triple` the default `build.target` in the project's .cargo/config.toml.
+fn set_default_target(triple: &str) -> Result<std::path::PathBuf> {
+    let path = Path::new(".cargo/config.toml");
+    let mut config = match std::fs::read_to_string(path) {
+        Ok(content) => content
+            .parse::<toml_edit::DocumentMut>()
+            .context("Failed to parse .cargo/config.toml")?,
+        Err(_) => toml_edit::DocumentMut::new(),
+    };
+    if !config.contains_table("build") {
+        config["build"] = toml_edit::table();
+    }
+    config["build"]["target"] = toml_edit::value(triple);
+
+    std::fs::create_dir_all(".cargo")?;
+    std::fs::write(path, config.to_string()).context("Failed to write .cargo/config.toml")?;
+    Ok(path.to_path_buf())
+}
+
+async fn remove_target(triple: &str, json_output: bool) -> Result<()> {
+    info!("Removing target: {}", triple);
+
+    let output = run_command("rustup", &["target", "remove", triple])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "action": "remove",
+                "triple": triple,
+                "status": "error",
+                "error": stderr.trim()
+            }));
+        } else {
+            output_text(&format!("❌ Failed to remove target: {}", triple));
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("Failed to remove target: {}", triple));
+    }
+
+    if json_output {
+        output_json(&json!({
+            "action": "remove",
+            "triple": triple,
+            "status": "success"
+        }));
+    } else {
+        output_text(&format!("✅ Removed target: {}", triple));
+    }
+
+    Ok(())
+}
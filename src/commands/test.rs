@@ -0,0 +1,139 @@
+use crate::utils::{
+    cargo_subcommand_installed, is_rust_project, output_json, output_text, run_command,
+};
+use crate::{CoverageFormat, TestAction};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use tracing::info;
+
+const COVERAGE_DIR: &str = "target/coverage";
+
+pub async fn run(action: TestAction, json_output: bool) -> Result<()> {
+    match action {
+        TestAction::CoverageGate { threshold, format } => {
+            coverage_gate(threshold, format, json_output).await
+        }
+    }
+}
+
+async fn coverage_gate(
+    threshold: f64,
+    format: Option<CoverageFormat>,
+    json_output: bool,
+) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    if !cargo_subcommand_installed("llvm-cov") {
+        if json_output {
+            output_json(&json!({
+                "error": "cargo-llvm-cov not installed",
+                "suggestion": "cargo install cargo-llvm-cov"
+            }));
+        } else {
+            output_text("❌ cargo-llvm-cov not installed");
+            output_text("💡 Install with: cargo install cargo-llvm-cov");
+        }
+        return Err(anyhow!("cargo-llvm-cov not installed"));
+    }
+
+    info!("Running tests with coverage...");
+    let output = run_command("cargo", &["llvm-cov", "--json", "--summary-only"])?;
+    let coverage_pct = output
+        .status
+        .success()
+        .then(|| line_coverage_percent(&String::from_utf8_lossy(&output.stdout)))
+        .flatten();
+    let Some(coverage_pct) = coverage_pct else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "error": "cargo llvm-cov failed",
+                "details": stderr.trim()
+            }));
+        } else {
+            output_text("❌ cargo llvm-cov failed");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("cargo llvm-cov failed"));
+    };
+
+    // `report` reuses the profile data of the run above instead of testing again
+    let report = format.and_then(|format| {
+        let (flag, output_flag, path) = match format {
+            CoverageFormat::Lcov => ("--lcov", "--output-path", "lcov.info"),
+            CoverageFormat::Cobertura => ("--cobertura", "--output-path", "cobertura.xml"),
+            // The HTML report is a directory of its own
+            CoverageFormat::Html => ("--html", "--output-dir", "html"),
+        };
+        let path = format!("{}/{}", COVERAGE_DIR, path);
+        info!("Writing coverage report to {}...", path);
+        let _ = std::fs::create_dir_all(COVERAGE_DIR);
+        run_command("cargo", &["llvm-cov", "report", flag, output_flag, &path])
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|_| path)
+    });
+
+    let passed = coverage_pct >= threshold;
+
+    if json_output {
+        output_json(&json!({
+            "coverage_pct": coverage_pct,
+            "threshold": threshold,
+            "passed": passed,
+            "report": report
+        }));
+    } else {
+        output_text("🧪 Coverage Gate");
+        output_text("================");
+        output_text(&format!("Line coverage: {:.2}%", coverage_pct));
+        output_text(&format!("Threshold:     {:.2}%", threshold));
+        if let Some(report) = &report {
+            output_text(&format!("📄 Report written to {}", report));
+        } else if format.is_some() {
+            output_text("⚠️  Failed to write the coverage report");
+        }
+        output_text("");
+        if passed {
+            output_text("✅ Coverage gate passed");
+        } else {
+            output_text(&format!(
+                "❌ Coverage gate failed: {:.2}% is below {:.2}%",
+                coverage_pct, threshold
+            ));
+        }
+    }
+
+    if !passed {
+        return Err(anyhow!(
+            "Line coverage {:.2}% is below the {:.2}% threshold",
+            coverage_pct,
+            threshold
+        ));
+    }
+
+    Ok(())
+}
+
+/// Overall line coverage from `cargo llvm-cov --json`, which is llvm-cov's export format:
+/// `{"data": [{"totals": {"lines": {"count", "covered", "percent"}}}]}`.
+pub fn line_coverage_percent(report: &str) -> Option<f64> {
+    let report: Value = serde_json::from_str(report.trim()).ok()?;
+    let lines = &report["data"].as_array()?.first()?["totals"]["lines"];
+    match (lines["count"].as_u64(), lines["covered"].as_u64()) {
+        // No instrumented lines at all; llvm-cov reports 0% for that, which isn't a regression
+        (Some(0), _) => Some(100.0),
+        (Some(count), Some(covered)) => Some(covered as f64 * 100.0 / count as f64),
+        _ => lines["percent"].as_f64(),
+    }
+}
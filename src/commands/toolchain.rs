@@ -1,20 +1,32 @@
-use crate::utils::{output_json, output_text, run_command};
+use crate::utils::{
+    cargo_messages, confirm, format_bytes, get_dir_size, is_rust_project, output_json, output_text,
+    run_command, run_command_in, rustup_home,
+};
 use crate::ToolchainAction;
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use serde_json::json;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 pub async fn run(action: ToolchainAction, json_output: bool) -> Result<()> {
     match action {
-        ToolchainAction::List => list_toolchains(json_output).await,
+        ToolchainAction::List { with_sizes } => list_toolchains(with_sizes, json_output).await,
         ToolchainAction::Install { toolchain } => install_toolchain(&toolchain, json_output).await,
         ToolchainAction::Default { toolchain } => set_default_toolchain(&toolchain, json_output).await,
         ToolchainAction::Show => show_active_toolchain(json_output).await,
         ToolchainAction::Remove { toolchain } => remove_toolchain(&toolchain, json_output).await,
+        ToolchainAction::Pin { force } => pin_toolchain(force, json_output).await,
+        ToolchainAction::CrossCheck { targets } => cross_check(targets, json_output).await,
+        ToolchainAction::Which { binary } => which_binary(&binary, json_output).await,
+        ToolchainAction::Check => check_toolchain_file(json_output).await,
+        ToolchainAction::Purge { dry_run, keep, yes } => {
+            purge_toolchains(dry_run, &keep, yes, json_output).await
+        }
     }
 }
 
-async fn list_toolchains(json_output: bool) -> Result<()> {
+async fn list_toolchains(with_sizes: bool, json_output: bool) -> Result<()> {
     info!("Listing installed toolchains...");
 
     let output = run_command("rustup", &["toolchain", "list"])?;
@@ -27,19 +39,27 @@ async fn list_toolchains(json_output: bool) -> Result<()> {
         let line = line.trim();
         if line.is_empty() { continue; }
         
-        let (name, is_default) = if line.contains("(default)") {
-            let name = line.replace("(default)", "").trim().to_string();
+        // Newer rustup prints annotations like "(active, default)" after the name
+        let (name, annotations) = line.split_once(' ').unwrap_or((line, ""));
+        let name = name.to_string();
+        let is_default = annotations.contains("default");
+        if is_default {
             default_toolchain = Some(name.clone());
-            (name, true)
-        } else {
-            (line.to_string(), false)
-        };
+        }
         
-        toolchains.push(json!({
+        let mut entry = json!({
             "name": name,
             "is_default": is_default,
             "status": "installed"
-        }));
+        });
+
+        if with_sizes {
+            let size = toolchain_dir(&name).and_then(|dir| get_dir_size(&dir).ok());
+            entry["disk_usage_bytes"] = json!(size);
+            entry["disk_usage_formatted"] = json!(size.map(format_bytes));
+        }
+
+        toolchains.push(entry);
     }
 
     if json_output {
@@ -57,11 +77,16 @@ async fn list_toolchains(json_output: bool) -> Result<()> {
             for toolchain in &toolchains {
                 let name = toolchain["name"].as_str().unwrap_or("unknown");
                 let is_default = toolchain["is_default"].as_bool().unwrap_or(false);
+                let size = toolchain
+                    .get("disk_usage_formatted")
+                    .and_then(|s| s.as_str())
+                    .map(|s| format!(" [{}]", s))
+                    .unwrap_or_default();
                 
                 if is_default {
-                    output_text(&format!("  {} (default) ✅", name));
+                    output_text(&format!("  {} (default) ✅{}", name, size));
                 } else {
-                    output_text(&format!("  {}", name));
+                    output_text(&format!("  {}{}", name, size));
                 }
             }
         }
@@ -74,6 +99,110 @@ async fn list_toolchains(json_output: bool) -> Result<()> {
     Ok(())
 }
 
+/// Resolve the on-disk directory of an installed toolchain.
+fn toolchain_dir(toolchain: &str) -> Option<PathBuf> {
+    // Ask the toolchain itself first; this handles custom/linked toolchains
+    if let Ok(output) = run_command("rustc", &[&format!("+{}", toolchain), "--print", "sysroot"])
+        && output.status.success()
+    {
+        let sysroot = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        if sysroot.is_dir() {
+            return Some(sysroot);
+        }
+    }
+
+    let dir = rustup_home()?.join("toolchains").join(toolchain);
+    dir.is_dir().then_some(dir)
+}
+
+async fn which_binary(binary: &str, json_output: bool) -> Result<()> {
+    info!("Looking up {} in installed toolchains...", binary);
+
+    let active_path = run_command("rustup", &["which", binary])
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()));
+    let active_toolchain = active_path.as_deref().and_then(toolchain_from_path);
+
+    let exe = format!("{}{}", binary, std::env::consts::EXE_SUFFIX);
+    let mut all_versions: Vec<(String, PathBuf)> = rustup_home()
+        .and_then(|home| std::fs::read_dir(home.join("toolchains")).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                entry.path().join("bin").join(&exe),
+            )
+        })
+        .filter(|(_, path)| path.is_file())
+        .collect();
+    all_versions.sort();
+
+    if active_path.is_none() && all_versions.is_empty() {
+        if json_output {
+            output_json(&json!({
+                "error": format!("No installed toolchain provides {}", binary),
+                "binary": binary
+            }));
+        } else {
+            output_text(&format!("❌ No installed toolchain provides {}", binary));
+            output_text("💡 Components are added with: rustup component add <name>");
+        }
+        return Err(anyhow!("No installed toolchain provides {}", binary));
+    }
+
+    if json_output {
+        output_json(&json!({
+            "binary": binary,
+            "path": active_path,
+            "toolchain": active_toolchain,
+            "all_versions": all_versions
+                .iter()
+                .map(|(toolchain, path)| json!({ "toolchain": toolchain, "path": path }))
+                .collect::<Vec<_>>()
+        }));
+        return Ok(());
+    }
+
+    output_text(&format!("🔍 {}", binary));
+    output_text("==================");
+    match (&active_path, &active_toolchain) {
+        (Some(path), Some(toolchain)) => {
+            output_text(&format!("  Active: {} ({})", path.display(), toolchain))
+        }
+        (Some(path), None) => output_text(&format!("  Active: {}", path.display())),
+        (None, _) => output_text(&format!(
+            "  ⚠️  The active toolchain doesn't provide {}",
+            binary
+        )),
+    }
+
+    output_text("");
+    output_text(&format!(
+        "📦 Installed in {} toolchain(s):",
+        all_versions.len()
+    ));
+    for (toolchain, path) in &all_versions {
+        let marker = if active_toolchain.as_deref() == Some(toolchain) {
+            "✅"
+        } else {
+            "  "
+        };
+        output_text(&format!("  {} {} - {}", marker, toolchain, path.display()));
+    }
+
+    Ok(())
+}
+
+/// The toolchain a path under `$RUSTUP_HOME/toolchains/<name>/` belongs to.
+pub fn toolchain_from_path(path: &Path) -> Option<String> {
+    let mut components = path.components().map(|c| c.as_os_str().to_string_lossy());
+    components.find(|component| component == "toolchains")?;
+    components.next().map(|name| name.to_string())
+}
+
 async fn install_toolchain(toolchain: &str, json_output: bool) -> Result<()> {
     info!("Installing toolchain: {}", toolchain);
 
@@ -209,4 +338,531 @@ async fn remove_toolchain(toolchain: &str, json_output: bool) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn purge_toolchains(
+    dry_run: bool,
+    keep: &[String],
+    yes: bool,
+    json_output: bool,
+) -> Result<()> {
+    info!("Finding unused toolchains...");
+
+    // Listing inside a project with a pinned toolchain would otherwise install it. A failed
+    // listing must not look like "no overrides", or overridden toolchains would be removed
+    let no_install = [("RUSTUP_AUTO_INSTALL", OsStr::new("0"))];
+    let stdout = |args: &[&str]| -> Result<String> {
+        let output = run_command_in("rustup", args, Path::new("."), &no_install)?;
+        if !output.status.success() {
+            let msg = format!(
+                "rustup {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            if json_output {
+                output_json(&json!({ "error": msg }));
+            } else {
+                output_text(&format!("❌ {}", msg));
+            }
+            return Err(anyhow!(msg));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    let overrides = override_toolchains(&stdout(&["override", "list"])?);
+    let candidates = purge_candidates(&stdout(&["toolchain", "list"])?, &overrides, keep);
+    let toolchains_dir = rustup_home().map(|home| home.join("toolchains"));
+    let candidate_bytes: u64 = candidates
+        .iter()
+        .filter_map(|name| toolchain_dir(name))
+        .filter_map(|dir| get_dir_size(&dir).ok())
+        .sum();
+
+    if candidates.is_empty() || dry_run {
+        if json_output {
+            output_json(&json!({
+                "removed": candidates,
+                "bytes_freed": candidate_bytes,
+                "dry_run": dry_run
+            }));
+        } else if candidates.is_empty() {
+            output_text("✅ No unused toolchains to remove");
+        } else {
+            output_text(&format!(
+                "🧹 Would remove {} toolchain(s), freeing {}:",
+                candidates.len(),
+                format_bytes(candidate_bytes)
+            ));
+            for name in &candidates {
+                output_text(&format!("  - {}", name));
+            }
+        }
+        return Ok(());
+    }
+
+    if !yes {
+        let prompt = format!(
+            "Remove {} ({})?",
+            candidates.join(", "),
+            format_bytes(candidate_bytes)
+        );
+        if json_output || !confirm(&prompt) {
+            if json_output {
+                output_json(&json!({
+                    "error": "Confirmation required",
+                    "toolchains": candidates,
+                    "suggestion": "Pass --yes to remove without asking"
+                }));
+            } else {
+                output_text("❌ Nothing removed");
+                output_text("💡 Pass --yes to remove without asking");
+            }
+            return Err(anyhow!("Purge not confirmed"));
+        }
+    }
+
+    let size = || toolchains_dir.as_ref().and_then(|dir| get_dir_size(dir).ok());
+    let before = size();
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+    for name in &candidates {
+        info!("Uninstalling {}...", name);
+        let args = ["toolchain", "uninstall", name];
+        match run_command_in("rustup", &args, Path::new("."), &no_install) {
+            Ok(output) if output.status.success() => removed.push(name.clone()),
+            _ => failed.push(name.clone()),
+        }
+    }
+    let bytes_freed = before
+        .zip(size())
+        .map(|(before, after)| before.saturating_sub(after));
+
+    if json_output {
+        output_json(&json!({
+            "removed": removed,
+            "failed": failed,
+            "bytes_freed": bytes_freed,
+            "dry_run": false
+        }));
+    } else {
+        for name in &removed {
+            output_text(&format!("🗑️  Removed {}", name));
+        }
+        for name in &failed {
+            output_text(&format!("❌ Failed to remove {}", name));
+        }
+        if let Some(bytes) = bytes_freed {
+            output_text(&format!("💾 Freed {}", format_bytes(bytes)));
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(anyhow!("Failed to remove {} toolchain(s)", failed.len()));
+    }
+
+    Ok(())
+}
+
+/// Toolchains set as directory overrides in `rustup override list` output, whose lines
+/// are `<directory>  <toolchain>`.
+fn override_toolchains(overrides: &str) -> Vec<String> {
+    overrides
+        .lines()
+        .filter(|line| line.trim() != "no overrides")
+        .filter_map(|line| line.split_whitespace().last().map(String::from))
+        .collect()
+}
+
+/// Toolchains in `rustup toolchain list` output that `oxy toolchain purge` removes: all but
+/// the default and active ones, `overrides`, and those matching `keep` by full name or by
+/// channel without the host triple.
+pub fn purge_candidates(list: &str, overrides: &[String], keep: &[String]) -> Vec<String> {
+    list.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (name, annotations) = line.split_once(' ').unwrap_or((line, ""));
+            let in_use = annotations.contains("default") || annotations.contains("active");
+            if name.is_empty() || in_use {
+                return None;
+            }
+            let channel = parse_channel(name, None);
+            let kept = keep.iter().any(|keep| *keep == name || *keep == channel);
+            (!kept && !overrides.iter().any(|o| o == name)).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Architectures that start the host triple in a rustup toolchain name.
+const TRIPLE_ARCHES: &[&str] = &[
+    "x86_64", "i686", "i586", "aarch64", "arm", "armv7", "riscv64gc", "powerpc64le", "s390x",
+    "wasm32", "loongarch64",
+];
+
+async fn pin_toolchain(force: bool, json_output: bool) -> Result<()> {
+    info!("Pinning active toolchain...");
+
+    let output = run_command("rustup", &["show", "active-toolchain"])?;
+    let active_output = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || active_output.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if json_output {
+            output_json(&json!({
+                "action": "pin",
+                "status": "error",
+                "error": "No active toolchain",
+                "stderr": stderr.trim()
+            }));
+        } else {
+            output_text("❌ Could not determine the active toolchain");
+            output_text(stderr.trim());
+        }
+        return Err(anyhow!("No active toolchain"));
+    }
+
+    let host = run_command("rustc", &["-vV"]).ok().and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.strip_prefix("host: ").map(String::from))
+    });
+    let toolchain = parse_channel(&active_output, host.as_deref());
+
+    let path = Path::new("rust-toolchain.toml");
+    let was_existing = path.exists();
+    let mut document = if was_existing {
+        std::fs::read_to_string(path)
+            .context("Failed to read rust-toolchain.toml")?
+            .parse::<toml_edit::DocumentMut>()
+            .context("Failed to parse rust-toolchain.toml")?
+    } else {
+        toml_edit::DocumentMut::new()
+    };
+
+    let existing = document
+        .get("toolchain")
+        .and_then(|t| t.get("channel"))
+        .and_then(|c| c.as_str())
+        .map(String::from);
+    if let Some(existing) = &existing
+        && *existing != toolchain
+        && !force
+    {
+        // JSON consumers can't answer a prompt, so they have to opt in with --force
+        let confirmed = !json_output
+            && confirm(&format!(
+                "rust-toolchain.toml pins {}; replace it with {}?",
+                existing, toolchain
+            ));
+        if !confirmed {
+            if json_output {
+                output_json(&json!({
+                    "action": "pin",
+                    "status": "error",
+                    "error": format!("rust-toolchain.toml already pins {}", existing),
+                    "suggestion": "Use --force to overwrite it"
+                }));
+            } else {
+                output_text(&format!("❌ Kept existing pin on {}", existing));
+                output_text("💡 Use --force to overwrite it");
+            }
+            return Err(anyhow!("rust-toolchain.toml already pins {}", existing));
+        }
+    }
+
+    // Only the channel changes; components, targets and comments are kept
+    if !document.contains_table("toolchain") {
+        document["toolchain"] = toml_edit::table();
+    }
+    document["toolchain"]["channel"] = toml_edit::value(toolchain.as_str());
+    std::fs::write(path, document.to_string()).context("Failed to write rust-toolchain.toml")?;
+
+    if json_output {
+        output_json(&json!({
+            "written_to": path,
+            "toolchain": toolchain,
+            "was_existing": was_existing
+        }));
+    } else {
+        output_text(&format!(
+            "📌 Pinned toolchain {} in {}",
+            toolchain,
+            path.display()
+        ));
+        if let Some(existing) = existing.filter(|e| *e != toolchain) {
+            output_text(&format!("   (was {})", existing));
+        }
+    }
+
+    Ok(())
+}
+
+async fn check_toolchain_file(json_output: bool) -> Result<()> {
+    // rustup uses the closest toolchain file in this directory or its parents
+    let cwd = std::env::current_dir()?;
+    let Some(path) = cwd
+        .ancestors()
+        .flat_map(|dir| [dir.join("rust-toolchain.toml"), dir.join("rust-toolchain")])
+        .find(|path| path.is_file())
+    else {
+        if json_output {
+            output_json(&json!({
+                "error": "No rust-toolchain.toml found",
+                "suggestion": "Pin the active toolchain with: oxy toolchain pin"
+            }));
+        } else {
+            output_text("❌ No rust-toolchain.toml found");
+            output_text("💡 Pin the active toolchain with: oxy toolchain pin");
+        }
+        return Err(anyhow!("No rust-toolchain.toml found"));
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let Some((channel, components)) = parse_toolchain_file(&content) else {
+        let error = format!("{} doesn't set a toolchain channel", path.display());
+        if json_output {
+            output_json(&json!({ "error": error }));
+        } else {
+            output_text(&format!("❌ {}", error));
+        }
+        return Err(anyhow!(error));
+    };
+
+    info!("Checking that {} is installed...", channel);
+    // Since rustup 1.28, resolving the active toolchain installs it, which is what this
+    // check is meant to tell the user about first. For the same reason, the host triple
+    // doesn't come from `rustc -vV` here
+    let no_install = [("RUSTUP_AUTO_INSTALL", OsStr::new("0"))];
+    let list = run_command_in("rustup", &["toolchain", "list"], Path::new("."), &no_install)?;
+    let list = String::from_utf8_lossy(&list.stdout);
+    let installed = installed_toolchain(&list, &channel, None);
+
+    let missing_components = match &installed {
+        Some(toolchain) => {
+            let output = run_command_in(
+                "rustup",
+                &["component", "list", "--installed", "--toolchain", toolchain],
+                Path::new("."),
+                &no_install,
+            )?;
+            missing_components(&String::from_utf8_lossy(&output.stdout), &components)
+        }
+        // Installing the toolchain from the file brings its components along
+        None => components.clone(),
+    };
+    let install_command = installed
+        .is_none()
+        .then(|| format!("rustup toolchain install {}", channel));
+    let component_command = (installed.is_some() && !missing_components.is_empty()).then(|| {
+        format!(
+            "rustup component add --toolchain {} {}",
+            channel,
+            missing_components.join(" ")
+        )
+    });
+    let ok = installed.is_some() && missing_components.is_empty();
+
+    if json_output {
+        output_json(&json!({
+            "toolchain_file": path,
+            "channel": channel,
+            "installed": installed.is_some(),
+            "installed_as": installed,
+            "components": components,
+            "missing_components": missing_components,
+            "install_command": install_command.as_ref().or(component_command.as_ref())
+        }));
+    } else {
+        output_text(&format!("📌 Toolchain File: {}", path.display()));
+        output_text("==========================================");
+        match &installed {
+            Some(name) => output_text(&format!("✅ {} is installed ({})", channel, name)),
+            None => output_text(&format!("❌ {} is not installed", channel)),
+        }
+        for component in &components {
+            let icon = if missing_components.contains(component) {
+                "❌"
+            } else {
+                "✅"
+            };
+            output_text(&format!("  {} {}", icon, component));
+        }
+        if let Some(command) = install_command.as_ref().or(component_command.as_ref()) {
+            output_text(&format!("💡 Install with: {}", command));
+        }
+    }
+
+    if !ok {
+        return Err(anyhow!("The toolchain in {} is not fully installed", path.display()));
+    }
+    Ok(())
+}
+
+/// The channel and components of a `rust-toolchain.toml`, or of a legacy `rust-toolchain`
+/// file holding only the channel name.
+pub fn parse_toolchain_file(content: &str) -> Option<(String, Vec<String>)> {
+    let Ok(document) = content.parse::<toml::Value>() else {
+        let channel = content.trim();
+        return (!channel.is_empty() && !channel.contains(char::is_whitespace))
+            .then(|| (channel.to_string(), Vec::new()));
+    };
+    let toolchain = document.get("toolchain")?;
+    let channel = toolchain.get("channel")?.as_str()?.to_string();
+    let components = toolchain
+        .get("components")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.as_str().map(String::from))
+        .collect();
+    Some((channel, components))
+}
+
+/// The name in `rustup toolchain list` output of the toolchain `channel` refers to, which
+/// may or may not include the host triple.
+pub fn installed_toolchain(list: &str, channel: &str, host: Option<&str>) -> Option<String> {
+    list.lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .find(|name| *name == channel || parse_channel(name, host) == channel)
+        .map(String::from)
+}
+
+/// Components from `components` missing in `rustup component list --installed` output,
+/// whose names carry the target triple (`clippy-x86_64-unknown-linux-gnu`) except for
+/// target-independent ones like `rust-src`.
+pub fn missing_components(installed: &str, components: &[String]) -> Vec<String> {
+    components
+        .iter()
+        .filter(|component| {
+            // rustup still accepts the old `-preview` names, e.g. `clippy-preview`
+            let component = component.trim_end_matches("-preview");
+            !installed.lines().map(str::trim).any(|line| {
+                line.strip_prefix(component).is_some_and(|rest| {
+                    rest.is_empty()
+                        || rest
+                            .strip_prefix('-')
+                            .and_then(|triple| triple.split('-').next())
+                            .is_some_and(|arch| TRIPLE_ARCHES.contains(&arch))
+                })
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+async fn cross_check(targets: Vec<String>, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let targets = if targets.is_empty() {
+        let output = run_command("rustup", &["target", "list", "--installed"])?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    } else {
+        targets
+    };
+    if targets.is_empty() {
+        if json_output {
+            output_json(&json!({
+                "error": "No installed targets found",
+                "suggestion": "Install one with: oxy target add <triple>"
+            }));
+        } else {
+            output_text("❌ No installed targets found");
+            output_text("💡 Install one with: oxy target add <triple>");
+        }
+        return Err(anyhow!("No installed targets found"));
+    }
+
+    let mut results = Vec::new();
+    for target in &targets {
+        info!("Checking target {}...", target);
+        let output = run_command(
+            "cargo",
+            &["check", "--target", target, "--message-format=json"],
+        )?;
+        let error_count = cargo_messages(&output, "compiler-message")
+            .iter()
+            .filter(|message| message["message"]["level"] == "error")
+            .count();
+        // Failures before compiling (e.g. a target without its std) have no JSON message
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = if output.status.success() {
+            None
+        } else {
+            stderr.lines().find(|line| line.starts_with("error"))
+        };
+        results.push(json!({
+            "target": target,
+            "success": output.status.success(),
+            "error_count": error_count,
+            "error": error
+        }));
+    }
+    let all_passed = results.iter().all(|result| result["success"] == true);
+
+    if json_output {
+        output_json(&json!({
+            "results": results,
+            "all_passed": all_passed
+        }));
+    } else {
+        output_text("🎯 Cross-Target Check");
+        output_text("=====================");
+        let width = targets.iter().map(|t| t.len()).max().unwrap_or(0);
+        for result in &results {
+            let target = result["target"].as_str().unwrap_or("");
+            let error_count = result["error_count"].as_u64().unwrap_or(0);
+            let status = if result["success"] == true {
+                "✅ pass".to_string()
+            } else if error_count > 0 {
+                format!("❌ fail ({} error(s))", error_count)
+            } else {
+                let error = result["error"].as_str().unwrap_or("cargo check failed");
+                format!("❌ fail: {}", error)
+            };
+            output_text(&format!("  {:<width$}  {}", target, status, width = width));
+        }
+        output_text("");
+        let passed = results
+            .iter()
+            .filter(|result| result["success"] == true)
+            .count();
+        output_text(&format!("📊 {}/{} targets compile", passed, results.len()));
+    }
+
+    if !all_passed {
+        return Err(anyhow!("cargo check failed for some targets"));
+    }
+
+    Ok(())
+}
+
+/// Channel part of a `rustup show active-toolchain` line, without the host triple.
+pub fn parse_channel(active_toolchain: &str, host: Option<&str>) -> String {
+    let name = active_toolchain.split_whitespace().next().unwrap_or("");
+
+    if let Some(channel) = host.and_then(|host| name.strip_suffix(&format!("-{}", host))) {
+        return channel.to_string();
+    }
+
+    // Fall back to cutting at the first component that looks like the start of a triple
+    let parts: Vec<&str> = name.split('-').collect();
+    match parts
+        .iter()
+        .position(|part| TRIPLE_ARCHES.contains(part))
+    {
+        Some(index) if index > 0 => parts[..index].join("-"),
+        _ => name.to_string(),
+    }
+}
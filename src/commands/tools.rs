@@ -1,9 +1,15 @@
-use crate::utils::{output_json, output_text, run_command};
-use anyhow::Result;
-use serde_json::json;
-use tracing::info;
+use crate::config::Config;
+use crate::utils::{
+    format_duration, output_json, output_text, run_command, run_command_with_timing,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::process::Output;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tracing::{info, warn};
 
-pub async fn run(json_output: bool) -> Result<()> {
+pub async fn run(install_missing: bool, json_output: bool) -> Result<()> {
     info!("Scanning for Rust development tools...");
 
     let tools = [
@@ -33,7 +39,8 @@ pub async fn run(json_output: bool) -> Result<()> {
     for (name, cmd) in &tools {
         let parts: Vec<&str> = cmd.split_whitespace().collect();
         match run_command(parts[0], &parts[1..]) {
-            Ok(output) => {
+            // Missing cargo subcommands still spawn cargo, so check the exit status too
+            Ok(output) if output.status.success() => {
                 let version_info = String::from_utf8_lossy(&output.stdout)
                     .lines()
                     .next()
@@ -47,7 +54,7 @@ pub async fn run(json_output: bool) -> Result<()> {
                     "status": "available"
                 }));
             }
-            Err(_) => {
+            _ => {
                 missing_tools.push(json!({
                     "name": name,
                     "status": "not_found"
@@ -56,15 +63,35 @@ pub async fn run(json_output: bool) -> Result<()> {
         }
     }
 
+    let to_install: Vec<String> = if install_missing {
+        missing_tools
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .filter(|name| install_command(name).is_some())
+            .map(String::from)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     if json_output {
-        output_json(&json!({
+        let mut report = json!({
             "found_tools": found_tools,
             "missing_tools": missing_tools,
             "summary": {
                 "total_found": found_tools.len(),
                 "total_missing": missing_tools.len()
             }
-        }));
+        });
+        if install_missing {
+            let started = Instant::now();
+            let results = install_tools(&to_install, json_output).await;
+            report["installed"] = json!(results);
+            report["install_elapsed_ms"] = json!(started.elapsed().as_millis() as u64);
+            output_json(&report);
+            return install_outcome(&results);
+        }
+        output_json(&report);
     } else {
         output_text("🔧 Rust Development Tools");
         output_text("==========================");
@@ -92,6 +119,24 @@ pub async fn run(json_output: bool) -> Result<()> {
                 output_text(&format!("  {}", name));
             }
 
+            if install_missing {
+                output_text("");
+                output_text(&format!(
+                    "📥 Installing {} missing tools...",
+                    to_install.len()
+                ));
+                let started = Instant::now();
+                let results = install_tools(&to_install, json_output).await;
+                let failed = results.iter().filter(|r| r["success"] == false).count();
+                output_text(&format!(
+                    "Installed {} of {} tools in {}",
+                    results.len() - failed,
+                    results.len(),
+                    format_duration(started.elapsed())
+                ));
+                return install_outcome(&results);
+            }
+
             output_text("");
             output_text("💡 Installation suggestions:");
             output_text("  • cargo install cargo-watch cargo-edit cargo-audit cargo-outdated");
@@ -104,3 +149,155 @@ pub async fn run(json_output: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// The command that installs a tool, for tools oxygen knows how to install.
+fn install_command(name: &str) -> Option<(&'static str, Vec<&'static str>)> {
+    let command = match name {
+        "rustfmt" => ("rustup", vec!["component", "add", "rustfmt"]),
+        "clippy" => ("rustup", vec!["component", "add", "clippy"]),
+        "rust-analyzer" => ("rustup", vec!["component", "add", "rust-analyzer"]),
+        "cargo-watch" => ("cargo", vec!["install", "cargo-watch"]),
+        "cargo-edit" => ("cargo", vec!["install", "cargo-edit"]),
+        "cargo-audit" => ("cargo", vec!["install", "cargo-audit"]),
+        "cargo-outdated" => ("cargo", vec!["install", "cargo-outdated"]),
+        "cargo-expand" => ("cargo", vec!["install", "cargo-expand"]),
+        "cargo-flamegraph" => ("cargo", vec!["install", "flamegraph"]),
+        "cargo-criterion" => ("cargo", vec!["install", "cargo-criterion"]),
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Install `tools`: rustup components in one sequential `rustup component add`, as parallel
+/// rustup calls race on the toolchain's state, then the rest concurrently, at most
+/// `[tools] install_parallelism` at a time. Every install runs to completion so all failures
+/// are reported together.
+async fn install_tools(tools: &[String], json_output: bool) -> Vec<Value> {
+    let parallelism = Config::load()
+        .unwrap_or_else(|e| {
+            warn!("Ignoring invalid oxygen config: {:#}", e);
+            Config::default()
+        })
+        .tools
+        .install_parallelism
+        .max(1);
+
+    let (components, others): (Vec<String>, Vec<String>) = tools
+        .iter()
+        .cloned()
+        .partition(|name| matches!(install_command(name), Some(("rustup", _))));
+    let mut results = Vec::new();
+
+    if !components.is_empty() {
+        if !json_output {
+            output_text(&format!("  ⏳ Installing {}...", components.join(", ")));
+        }
+        let mut args = vec!["component", "add"];
+        args.extend(components.iter().map(String::as_str));
+        let outcome = run_command_with_timing("rustup", &args);
+        for name in &components {
+            let result = install_result(name, &outcome);
+            report_install(&result, json_output);
+            results.push(result);
+        }
+    }
+
+    let mut pending = others.into_iter();
+    let mut running = JoinSet::new();
+
+    loop {
+        while running.len() < parallelism {
+            let Some(name) = pending.next() else {
+                break;
+            };
+            if !json_output {
+                output_text(&format!("  ⏳ Installing {}...", name));
+            }
+            running.spawn_blocking(move || install_tool(&name));
+        }
+
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let result = joined.unwrap_or_else(|e| {
+            json!({
+                "name": "unknown",
+                "success": false,
+                "error": format!("Install task failed: {}", e)
+            })
+        });
+        report_install(&result, json_output);
+        results.push(result);
+    }
+
+    results
+}
+
+fn report_install(result: &Value, json_output: bool) {
+    if json_output {
+        return;
+    }
+    let name = result["name"].as_str().unwrap_or("unknown");
+    let duration_ms = result["install_duration_ms"].as_u64().unwrap_or(0);
+    if result["success"] == true {
+        output_text(&format!("  ✅ Installed {} ({}ms)", name, duration_ms));
+    } else {
+        output_text(&format!(
+            "  ❌ Failed to install {}: {}",
+            name,
+            result["error"].as_str().unwrap_or("unknown error")
+        ));
+    }
+}
+
+fn install_tool(name: &str) -> Value {
+    let Some((program, args)) = install_command(name) else {
+        return json!({
+            "name": name,
+            "success": false,
+            "error": "No known install command"
+        });
+    };
+
+    install_result(name, &run_command_with_timing(program, &args))
+}
+
+fn install_result(name: &str, outcome: &Result<(Output, Duration)>) -> Value {
+    match outcome {
+        Ok((output, duration)) if output.status.success() => json!({
+            "name": name,
+            "success": true,
+            "install_duration_ms": duration.as_millis() as u64
+        }),
+        Ok((output, duration)) => json!({
+            "name": name,
+            "success": false,
+            "install_duration_ms": duration.as_millis() as u64,
+            "error": String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("install failed")
+                .trim()
+        }),
+        Err(e) => json!({
+            "name": name,
+            "success": false,
+            "error": e.to_string()
+        }),
+    }
+}
+
+fn install_outcome(results: &[Value]) -> Result<()> {
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| r["success"] == false)
+        .filter_map(|r| r["name"].as_str())
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Failed to install: {}", failed.join(", ")))
+    }
+}
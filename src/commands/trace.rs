@@ -0,0 +1,136 @@
+use crate::commands::explain::strip_ansi;
+use crate::utils::{output_json, output_text, run_command_in};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::path::Path;
+use tracing::info;
+
+/// Levels tracing and `log` print, loudest first.
+const LEVELS: [&str; 5] = ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
+pub async fn run(
+    log_level: &str,
+    backtrace: bool,
+    command: Vec<String>,
+    json_output: bool,
+) -> Result<()> {
+    let args: Vec<&str> = command.iter().map(String::as_str).collect();
+    info!(
+        "Running cargo {} with RUST_LOG={}",
+        args.join(" "),
+        log_level
+    );
+
+    let mut envs: Vec<(&str, &std::ffi::OsStr)> = vec![("RUST_LOG", log_level.as_ref())];
+    if backtrace {
+        envs.push(("RUST_BACKTRACE", "full".as_ref()));
+    }
+    let output = run_command_in("cargo", &args, Path::new("."), &envs)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let events: Vec<Value> = stderr
+        .lines()
+        .map(|line| (line, "stderr"))
+        .chain(stdout.lines().map(|line| (line, "stdout")))
+        .filter(|(line, _)| !line.trim().is_empty())
+        .map(|(line, stream)| {
+            let mut event = parse_log_line(line);
+            event["stream"] = json!(stream);
+            event
+        })
+        .collect();
+
+    let count = |level: &str| {
+        events
+            .iter()
+            .filter(|event| event["level"] == level)
+            .count()
+    };
+    let (errors, warnings) = (count("ERROR"), count("WARN"));
+
+    if json_output {
+        output_json(&json!({
+            "command": format!("cargo {}", args.join(" ")),
+            "log_level": log_level,
+            "success": output.status.success(),
+            "exit_code": output.status.code(),
+            "error_count": errors,
+            "warning_count": warnings,
+            "events": events
+        }));
+    } else {
+        output_text(&format!(
+            "🔍 cargo {} (RUST_LOG={})",
+            args.join(" "),
+            log_level
+        ));
+        output_text("==========================================");
+        for event in &events {
+            let line = match event.get("raw").and_then(|raw| raw.as_str()) {
+                Some(raw) => raw.to_string(),
+                None => format_json_event(event),
+            };
+            match event["level"].as_str() {
+                Some("ERROR") => output_text(&console::style(line).red().bold().to_string()),
+                Some("WARN") => output_text(&console::style(line).yellow().to_string()),
+                _ => output_text(&line),
+            }
+        }
+        output_text("");
+        output_text(&format!(
+            "📊 {} error(s), {} warning(s) in {} line(s)",
+            errors,
+            warnings,
+            events.len()
+        ));
+    }
+
+    if !output.status.success() {
+        return Err(anyhow!("cargo {} failed", args.join(" ")));
+    }
+
+    Ok(())
+}
+
+/// One line of output as a log event. JSON lines from `tracing-subscriber`'s JSON format
+/// are kept as they are; other lines become `{level, raw}`, with the level taken from
+/// the usual `<timestamp> LEVEL target: message` layout if present.
+pub fn parse_log_line(line: &str) -> Value {
+    if let Ok(mut event @ Value::Object(_)) = serde_json::from_str::<Value>(line)
+        && event.get("level").is_some()
+    {
+        if let Some(level) = event["level"].as_str() {
+            event["level"] = json!(level.to_uppercase());
+        }
+        return event;
+    }
+
+    let plain = strip_ansi(line);
+    let level = plain
+        .split_whitespace()
+        .take(3)
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphabetic()))
+        .find(|word| LEVELS.contains(word));
+    json!({
+        "level": level,
+        "raw": plain
+    })
+}
+
+/// Render a JSON event in the layout of tracing's default formatter.
+fn format_json_event(event: &Value) -> String {
+    let message = event["fields"]["message"]
+        .as_str()
+        .map(String::from)
+        .unwrap_or_else(|| event["fields"].to_string());
+    format!(
+        "{} {:>5} {}: {}",
+        event["timestamp"].as_str().unwrap_or(""),
+        event["level"].as_str().unwrap_or(""),
+        event["target"].as_str().unwrap_or(""),
+        message
+    )
+    .trim_start()
+    .to_string()
+}
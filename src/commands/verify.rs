@@ -0,0 +1,284 @@
+use crate::utils::{
+    cargo_messages, create_worktree, is_rust_project, output_json, output_text, read_manifest,
+    remove_worktree, run_command, run_command_in, sha256_file,
+};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Bytes of context shown on each side of the first difference between two binaries.
+const DIFF_CONTEXT: usize = 16;
+
+pub async fn run(compare_to: Option<String>, bin: Option<String>, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let compare_to = match compare_to {
+        Some(git_ref) => git_ref,
+        None => {
+            let output = run_command("git", &["describe", "--tags", "--abbrev=0"])?;
+            let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !output.status.success() || tag.is_empty() {
+                if json_output {
+                    output_json(&json!({
+                        "error": "No tags found to compare against",
+                        "suggestion": "Pass a git ref with --compare-to"
+                    }));
+                } else {
+                    output_text("❌ No tags found to compare against");
+                    output_text("💡 Pass a git ref with --compare-to");
+                }
+                return Err(anyhow!("No tags found to compare against"));
+            }
+            tag
+        }
+    };
+
+    let prefix = run_command("git", &["rev-parse", "--show-prefix"])
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    // Both sides are built from clean checkouts with their own target directories, so
+    // neither build can reuse artifacts from the other or from the developer's target/
+    info!("Building HEAD in an isolated checkout...");
+    let current = build_isolated("HEAD", &prefix, bin.as_deref());
+    info!("Building {} in an isolated checkout...", compare_to);
+    let baseline = build_isolated(&compare_to, &prefix, bin.as_deref());
+
+    let (current, baseline) = match (current, baseline) {
+        (Ok(current), Ok(baseline)) => (current, baseline),
+        (Err(e), _) | (_, Err(e)) => {
+            if json_output {
+                output_json(&json!({
+                    "error": "Isolated build failed",
+                    "details": e.to_string()
+                }));
+            } else {
+                output_text("❌ Isolated build failed");
+                output_text(&format!("Error: {}", e));
+            }
+            return Err(e);
+        }
+    };
+
+    let reproducible = current.sha256 == baseline.sha256;
+    let difference = if reproducible {
+        None
+    } else {
+        first_difference(&current.bytes, &baseline.bytes)
+    };
+
+    if json_output {
+        output_json(&json!({
+            "compare_to": compare_to,
+            "reproducible": reproducible,
+            "current": current.to_json("HEAD"),
+            "baseline": baseline.to_json(&compare_to),
+            "first_difference": difference.map(|offset| json!({
+                "offset": offset,
+                "current_bytes": hex_window(&current.bytes, offset),
+                "baseline_bytes": hex_window(&baseline.bytes, offset)
+            }))
+        }));
+    } else {
+        output_text(&format!(
+            "🔁 Reproducible Build Check: HEAD vs {}",
+            compare_to
+        ));
+        output_text("==========================================");
+        output_text(&format!(
+            "  HEAD:  {}  {} bytes",
+            current.sha256,
+            current.bytes.len()
+        ));
+        output_text(&format!(
+            "  {}:  {}  {} bytes",
+            compare_to,
+            baseline.sha256,
+            baseline.bytes.len()
+        ));
+        output_text("");
+
+        if reproducible {
+            output_text("✅ Binaries are identical - the build is reproducible");
+        } else {
+            output_text("❌ Binaries differ");
+            if let Some(offset) = difference {
+                output_text(&format!("First difference at offset {:#x}:", offset));
+                output_text(&format!("  HEAD:  {}", hex_window(&current.bytes, offset)));
+                output_text(&format!(
+                    "  {}:  {}",
+                    compare_to,
+                    hex_window(&baseline.bytes, offset)
+                ));
+            }
+            output_text(
+                "💡 Differences are expected if the code changed; for the same source, check for embedded timestamps, paths or build-script output",
+            );
+        }
+    }
+
+    if !reproducible {
+        return Err(anyhow!("Builds are not reproducible"));
+    }
+
+    Ok(())
+}
+
+struct IsolatedBuild {
+    binary: PathBuf,
+    sha256: String,
+    bytes: Vec<u8>,
+}
+
+impl IsolatedBuild {
+    fn to_json(&self, git_ref: &str) -> Value {
+        json!({
+            "ref": git_ref,
+            "binary": self.binary.file_name().map(|n| n.to_string_lossy().to_string()),
+            "sha256": self.sha256,
+            "size_bytes": self.bytes.len()
+        })
+    }
+}
+
+/// Build `git_ref` in a temporary worktree and hash its release binary.
+fn build_isolated(git_ref: &str, prefix: &str, bin: Option<&str>) -> Result<IsolatedBuild> {
+    let worktree = create_worktree(git_ref, "verify")?;
+    let result = build_in(&worktree, prefix, bin);
+    remove_worktree(&worktree);
+    result
+}
+
+fn build_in(worktree: &Path, prefix: &str, bin: Option<&str>) -> Result<IsolatedBuild> {
+    let project_dir = worktree.join(prefix);
+    // Without --bin, `cargo run` would pick default-run, so compare that binary
+    let bin = bin
+        .map(String::from)
+        .or_else(|| default_run(&project_dir.join("Cargo.toml")));
+    // Keep the target dir inside the worktree so it is thrown away with it
+    let target_dir = worktree.join("target");
+
+    // Remap the checkout location so the temp path doesn't leak into the binary
+    let mut rustflags = OsString::from(format!("--remap-path-prefix={}=.", worktree.display()));
+    if let Some(existing) = std::env::var_os("RUSTFLAGS") {
+        rustflags.push(" ");
+        rustflags.push(existing);
+    }
+
+    // Without a committed lockfile, resolve both sides to the same versions as the local one
+    let lockfile = project_dir.join("Cargo.lock");
+    if !lockfile.exists() && Path::new("Cargo.lock").exists() {
+        std::fs::copy("Cargo.lock", &lockfile)?;
+    }
+
+    let mut args = vec!["build", "--release", "--message-format=json"];
+    if let Some(name) = &bin {
+        args.extend(["--bin", name]);
+    }
+    let output = run_command_in(
+        "cargo",
+        &args,
+        &project_dir,
+        &[
+            ("CARGO_TARGET_DIR", target_dir.as_os_str()),
+            ("RUSTFLAGS", rustflags.as_os_str()),
+        ],
+    )?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "cargo build failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let binary = select_binary(
+        &cargo_messages(&output, "compiler-artifact"),
+        bin.as_deref(),
+    )?;
+    let sha256 = sha256_file(&binary)?;
+    let bytes = std::fs::read(&binary)?;
+
+    Ok(IsolatedBuild {
+        binary,
+        sha256,
+        bytes,
+    })
+}
+
+/// The `default-run` binary of the package at `manifest`, if it sets one.
+pub fn default_run(manifest: &Path) -> Option<String> {
+    read_manifest(manifest)
+        .ok()?
+        .get("package")?
+        .get("default-run")?
+        .as_str()
+        .map(String::from)
+}
+
+/// The executable to compare among the built artifacts: the one named `bin`, else the only
+/// one. Picking one of several arbitrarily could compare different programs across builds.
+pub fn select_binary(artifacts: &[Value], bin: Option<&str>) -> Result<PathBuf> {
+    let executables: Vec<(&str, &str)> = artifacts
+        .iter()
+        .filter(|artifact| {
+            artifact["target"]["kind"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|kind| kind == "bin"))
+        })
+        .filter_map(|artifact| {
+            Some((
+                artifact["target"]["name"].as_str()?,
+                artifact["executable"].as_str()?,
+            ))
+        })
+        .collect();
+
+    match (bin, executables.as_slice()) {
+        (Some(name), _) => executables
+            .iter()
+            .find(|(target, _)| *target == name)
+            .map(|(_, path)| PathBuf::from(path))
+            .ok_or_else(|| anyhow!("No binary named '{}' was produced", name)),
+        (None, [(_, path)]) => Ok(PathBuf::from(path)),
+        (None, []) => Err(anyhow!("No binary was produced")),
+        (None, _) => {
+            let names: Vec<&str> = executables.iter().map(|(name, _)| *name).collect();
+            Err(anyhow!(
+                "Several binaries were built ({}); pick one with --bin",
+                names.join(", ")
+            ))
+        }
+    }
+}
+
+/// Offset of the first byte where the two buffers differ, counting a length mismatch.
+pub fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(x, y)| x != y)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+fn hex_window(bytes: &[u8], offset: usize) -> String {
+    let start = offset.saturating_sub(DIFF_CONTEXT);
+    let end = (offset + DIFF_CONTEXT).min(bytes.len());
+    bytes
+        .get(start..end)
+        .unwrap_or_default()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
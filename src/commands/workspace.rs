@@ -0,0 +1,361 @@
+use crate::WorkspaceAction;
+use crate::commands::build::{BuildOptions, BuildTarget};
+use crate::commands::check::CheckOptions;
+use crate::utils::{DEPENDENCY_TABLES, is_rust_project, output_json, output_text, read_manifest};
+use anyhow::{Result, anyhow};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// A package listed by the root manifest's `[workspace]` table.
+pub struct WorkspaceMember {
+    pub name: String,
+    /// Directory of the member, relative to the workspace root
+    pub path: PathBuf,
+    pub version: Option<String>,
+    /// `lib`, `bin`, `lib+bin` or `proc-macro`
+    pub kind: &'static str,
+    pub manifest: toml::Value,
+}
+
+/// A dependency of one member on another.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MemberEdge {
+    pub from: String,
+    pub to: String,
+    /// `normal`, `dev` or `build`, like the `--edges` kinds of `cargo tree`
+    pub kind: &'static str,
+}
+
+pub async fn run(action: WorkspaceAction, json_output: bool) -> Result<()> {
+    if !is_rust_project() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not in a Rust project directory",
+                "is_rust_project": false
+            }));
+        } else {
+            output_text("❌ Not in a Rust project (no Cargo.toml found)");
+        }
+        return Ok(());
+    }
+
+    let root = read_manifest(Path::new("Cargo.toml"))?;
+    if root.get("workspace").is_none() {
+        if json_output {
+            output_json(&json!({
+                "error": "Not a workspace root (no [workspace] table in Cargo.toml)"
+            }));
+        } else {
+            output_text("❌ Not a workspace root (no [workspace] table in Cargo.toml)");
+        }
+        return Err(anyhow!("Not a workspace root"));
+    }
+
+    match action {
+        WorkspaceAction::List => list_members(&root, json_output).await,
+        WorkspaceAction::Check => {
+            let options = CheckOptions {
+                workspace: true,
+                ..Default::default()
+            };
+            crate::commands::check::run(options, json_output).await
+        }
+        WorkspaceAction::Build => {
+            let options = BuildOptions {
+                target: BuildTarget::Workspace,
+                ..Default::default()
+            };
+            crate::commands::build::run(options, json_output).await
+        }
+        WorkspaceAction::Graph => show_graph(&root, json_output).await,
+    }
+}
+
+async fn list_members(root: &toml::Value, json_output: bool) -> Result<()> {
+    let members = workspace_members(root);
+
+    if json_output {
+        let members: Vec<_> = members
+            .iter()
+            .map(|member| {
+                json!({
+                    "name": member.name,
+                    "path": member.path,
+                    "version": member.version,
+                    "type": member.kind
+                })
+            })
+            .collect();
+        output_json(&json!({ "members": members }));
+        return Ok(());
+    }
+
+    output_text("📦 Workspace Members");
+    output_text("====================");
+    if members.is_empty() {
+        output_text("No members found");
+        return Ok(());
+    }
+
+    let width = members.iter().map(|m| m.name.len()).max().unwrap_or(0);
+    for member in &members {
+        output_text(&format!(
+            "  {:<width$}  {:<10}  {:<10}  {}",
+            member.name,
+            member.version.as_deref().unwrap_or("-"),
+            member.kind,
+            member.path.display(),
+            width = width
+        ));
+    }
+    output_text("");
+    output_text(&format!("📊 {} member(s)", members.len()));
+
+    Ok(())
+}
+
+async fn show_graph(root: &toml::Value, json_output: bool) -> Result<()> {
+    let members = workspace_members(root);
+    let edges = member_edges(&members);
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    let dot = graph_dot(&names, &edges);
+
+    if json_output {
+        let edges: Vec<_> = edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "from": edge.from,
+                    "to": edge.to,
+                    "kind": edge.kind
+                })
+            })
+            .collect();
+        output_json(&json!({
+            "members": names,
+            "edges": edges,
+            "dot": dot
+        }));
+    } else {
+        // Plain DOT, so the output can be piped straight into `dot -Tsvg`
+        output_text(dot.trim_end());
+    }
+
+    Ok(())
+}
+
+/// Members of the workspace rooted at the current directory, with the root package first.
+pub fn workspace_members(root: &toml::Value) -> Vec<WorkspaceMember> {
+    let string_list = |key: &str| -> Vec<String> {
+        root.get("workspace")
+            .and_then(|w| w.get(key))
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    };
+    let patterns = string_list("members");
+    let exclude = string_list("exclude");
+    let workspace_version = root
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str());
+
+    let mut paths = Vec::new();
+    if root.get("package").is_some() {
+        paths.push(PathBuf::from("."));
+    }
+    for path in expand_member_patterns(Path::new("."), &patterns, &exclude) {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    info!("Reading {} workspace member manifest(s)...", paths.len());
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let manifest = match read_manifest(&path.join("Cargo.toml")) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Skipping member {}: {:#}", path.display(), e);
+                    return None;
+                }
+            };
+            let package = manifest.get("package")?;
+            let name = package.get("name")?.as_str()?.to_string();
+            let version = match package.get("version") {
+                Some(toml::Value::String(version)) => Some(version.clone()),
+                // `version.workspace = true`
+                Some(toml::Value::Table(_)) => workspace_version.map(String::from),
+                _ => None,
+            };
+            let kind = crate_kind(&path, &manifest);
+            Some(WorkspaceMember {
+                name,
+                path,
+                version,
+                kind,
+                manifest,
+            })
+        })
+        .collect()
+}
+
+/// Directories under `root` matching the `members` patterns, minus `exclude`. Patterns
+/// may use `*` and `?` within a path component; only directories with a Cargo.toml count.
+pub fn expand_member_patterns(
+    root: &Path,
+    patterns: &[String],
+    exclude: &[String],
+) -> Vec<PathBuf> {
+    let normalize = |path: &str| PathBuf::from(path.trim_start_matches("./").trim_end_matches('/'));
+    let excluded: Vec<PathBuf> = exclude.iter().map(|e| normalize(e)).collect();
+
+    let mut members = Vec::new();
+    for pattern in patterns {
+        let mut candidates = vec![PathBuf::new()];
+        for component in normalize(pattern).iter() {
+            let component = component.to_string_lossy();
+            if !component.contains(['*', '?']) {
+                candidates
+                    .iter_mut()
+                    .for_each(|c| c.push(component.as_ref()));
+                continue;
+            }
+            let mut matched = Vec::new();
+            for candidate in &candidates {
+                let Ok(entries) = std::fs::read_dir(root.join(candidate)) else {
+                    continue;
+                };
+                let mut names: Vec<String> = entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| glob_match(&component, name))
+                    .collect();
+                names.sort();
+                matched.extend(names.into_iter().map(|name| candidate.join(name)));
+            }
+            candidates = matched;
+        }
+
+        for candidate in candidates {
+            let is_excluded = excluded.iter().any(|e| candidate.starts_with(e));
+            if !is_excluded
+                && root.join(&candidate).join("Cargo.toml").is_file()
+                && !members.contains(&candidate)
+            {
+                members.push(candidate);
+            }
+        }
+    }
+    members
+}
+
+/// Whether `name` matches a glob component where `*` is any run and `?` any one character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+/// The kind of crate a member builds, from its manifest and cargo's auto-discovered targets.
+fn crate_kind(dir: &Path, manifest: &toml::Value) -> &'static str {
+    let lib = manifest.get("lib");
+    if lib
+        .and_then(|l| l.get("proc-macro"))
+        .and_then(|p| p.as_bool())
+        == Some(true)
+    {
+        return "proc-macro";
+    }
+    let has_lib = lib.is_some() || dir.join("src/lib.rs").is_file();
+    let has_bin = manifest.get("bin").is_some()
+        || dir.join("src/main.rs").is_file()
+        || dir.join("src/bin").is_dir();
+    match (has_lib, has_bin) {
+        (true, true) => "lib+bin",
+        (false, true) => "bin",
+        _ => "lib",
+    }
+}
+
+/// Dependencies between members declared as `path` or `workspace = true` dependencies,
+/// including target-specific ones.
+pub fn member_edges(members: &[WorkspaceMember]) -> Vec<MemberEdge> {
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    let mut edges = Vec::new();
+
+    for member in members {
+        let targets = member
+            .manifest
+            .get("target")
+            .and_then(|t| t.as_table())
+            .into_iter()
+            .flat_map(|targets| targets.values());
+        for scope in std::iter::once(&member.manifest).chain(targets) {
+            for table_name in DEPENDENCY_TABLES {
+                let Some(table) = scope.get(*table_name).and_then(|t| t.as_table()) else {
+                    continue;
+                };
+                let kind = match *table_name {
+                    "dev-dependencies" => "dev",
+                    "build-dependencies" => "build",
+                    _ => "normal",
+                };
+                for (key, spec) in table {
+                    let local = spec.get("path").is_some() || spec.get("workspace").is_some();
+                    // `package = "..."` renames the dependency
+                    let name = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+                    let edge = MemberEdge {
+                        from: member.name.clone(),
+                        to: name.to_string(),
+                        kind,
+                    };
+                    if local
+                        && name != member.name
+                        && names.contains(&name)
+                        && !edges.contains(&edge)
+                    {
+                        edges.push(edge);
+                    }
+                }
+            }
+        }
+    }
+    edges
+}
+
+/// A Graphviz digraph of the members, with dev dependencies dashed and build dependencies
+/// dotted.
+pub fn graph_dot(members: &[&str], edges: &[MemberEdge]) -> String {
+    let mut dot = String::from("digraph workspace {\n    rankdir=LR;\n    node [shape=box];\n");
+    for member in members {
+        dot.push_str(&format!("    \"{}\";\n", member));
+    }
+    for edge in edges {
+        let style = match edge.kind {
+            "dev" => " [style=dashed, label=\"dev\"]",
+            "build" => " [style=dotted, label=\"build\"]",
+            _ => "",
+        };
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\"{};\n",
+            edge.from, edge.to, style
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
@@ -1,21 +1,37 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct Config {
     pub tools: ToolsConfig,
     pub build: BuildConfig,
     pub output: OutputConfig,
+    pub check: CheckConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ToolsConfig {
     pub custom_tools: Vec<String>,
     pub check_paths: Vec<PathBuf>,
+    /// How many tools `oxy tools --install-missing` installs at once
+    pub install_parallelism: usize,
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            custom_tools: Vec::new(),
+            check_paths: Vec::new(),
+            install_parallelism: 4,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct BuildConfig {
     pub release_by_default: bool,
     pub show_warnings: bool,
@@ -23,28 +39,82 @@ pub struct BuildConfig {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct OutputConfig {
     pub json_by_default: bool,
     pub color: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckConfig {
+    pub no_fmt: bool,
+    pub no_clippy: bool,
+    pub no_check: bool,
+    /// Run `cargo machete` for unused dependencies when it is installed
+    pub run_machete: bool,
+    pub scoring: ScoringConfig,
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            no_fmt: false,
+            no_clippy: false,
+            no_check: false,
+            run_machete: true,
+            scoring: ScoringConfig::default(),
+        }
+    }
+}
+
+/// Points each step contributes to the `oxy check` quality score.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringConfig {
+    pub fmt_weight: u32,
+    pub clippy_weight: u32,
+    pub check_weight: u32,
+    /// Points taken off the clippy weight for each warning
+    pub clippy_warning_penalty: u32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            fmt_weight: 30,
+            clippy_weight: 40,
+            check_weight: 30,
+            clippy_warning_penalty: 2,
+        }
+    }
+}
+
+/// Per-project overrides, merged on top of the global config.
+pub const PROJECT_CONFIG_FILE: &str = ".oxygen.toml";
+
 impl Config {
-    #[allow(dead_code)]
     pub fn load() -> Result<Self> {
+        let mut merged = toml::Value::Table(Default::default());
+
         let config_path = Self::config_path()?;
+        for path in [config_path.as_path(), Path::new(PROJECT_CONFIG_FILE)] {
+            if !path.exists() {
+                continue;
+            }
 
-        if !config_path.exists() {
-            return Ok(Config::default());
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {:?}", path))?;
+            let value: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+            merge_toml(&mut merged, value);
         }
 
-        let content = std::fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
-
-        toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {:?}", config_path))
+        merged
+            .try_into()
+            .context("Failed to load oxygen configuration")
     }
 
-    #[allow(dead_code)]
     pub fn config_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir().context("Failed to get config directory")?;
         Ok(config_dir.join("oxygen").join("config.toml"))
@@ -67,3 +137,51 @@ impl Config {
         Ok(())
     }
 }
+
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Dotted paths of the keys in a config file that oxygen doesn't read, found by comparing
+/// the file with what is left of it after a round trip through `Config`.
+pub fn unknown_keys(value: &toml::Value) -> Result<Vec<String>> {
+    let config: Config = value.clone().try_into()?;
+    let known = toml::Value::try_from(&config).context("Failed to serialize config")?;
+    let mut unknown = Vec::new();
+    collect_unknown_keys(value, &known, "", &mut unknown);
+    Ok(unknown)
+}
+
+fn collect_unknown_keys(
+    value: &toml::Value,
+    known: &toml::Value,
+    prefix: &str,
+    unknown: &mut Vec<String>,
+) {
+    let (Some(table), Some(known)) = (value.as_table(), known.as_table()) else {
+        return;
+    };
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match known.get(key) {
+            Some(known) => collect_unknown_keys(value, known, &path, unknown),
+            None => unknown.push(path),
+        }
+    }
+}
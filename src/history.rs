@@ -0,0 +1,199 @@
+use crate::utils::run_command;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Per-project directory for run history, next to `.oxygen.toml`.
+pub const HISTORY_DIR: &str = ".oxygen";
+
+/// Oldest runs are dropped once a history grows past this many entries.
+const MAX_RUNS: usize = 500;
+
+/// One `oxy check` run. Steps that were skipped are recorded as `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub git_hash: Option<String>,
+    pub fmt_passed: Option<bool>,
+    /// Whether clippy exited successfully; a crate that fails to compile reports no warnings
+    #[serde(default)]
+    pub clippy_passed: Option<bool>,
+    pub clippy_warnings: Option<usize>,
+    pub check_passed: Option<bool>,
+    pub total_duration_ms: u64,
+}
+
+impl CheckRun {
+    pub fn passed(&self) -> bool {
+        self.fmt_passed != Some(false)
+            && self.clippy_passed != Some(false)
+            && self.clippy_warnings.unwrap_or(0) == 0
+            && self.check_passed != Some(false)
+    }
+}
+
+/// Past `oxy check` results, stored in `.oxygen/check_history.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CheckHistory {
+    pub runs: Vec<CheckRun>,
+}
+
+impl CheckHistory {
+    pub fn path() -> PathBuf {
+        Path::new(HISTORY_DIR).join("check_history.json")
+    }
+
+    /// Load the history, treating a missing file as empty.
+    pub fn load() -> Result<Self> {
+        load_history(&Self::path())
+    }
+
+    /// Add a run and write the history back to disk.
+    pub fn append(run: CheckRun) -> Result<()> {
+        let mut history = Self::load()?;
+        push_capped(&mut history.runs, run);
+        save_history(&Self::path(), &history)
+    }
+}
+
+/// One successful `oxy build`, with the size of its main artifact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildRun {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub git_hash: Option<String>,
+    /// Effective release LTO setting: `thin`, `fat` or `off`
+    pub lto: String,
+    pub stripped: bool,
+    pub binary: String,
+    pub size_bytes: u64,
+}
+
+/// Past `oxy build` results, stored in `.oxygen/build_history.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BuildHistory {
+    pub runs: Vec<BuildRun>,
+}
+
+impl BuildHistory {
+    /// The history file of the project in `root`.
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(HISTORY_DIR).join("build_history.json")
+    }
+
+    /// Load the history, treating a missing file as empty.
+    pub fn load(root: &Path) -> Result<Self> {
+        load_history(&Self::path(root))
+    }
+
+    /// Add a run and write the history back to disk.
+    pub fn append(root: &Path, run: BuildRun) -> Result<()> {
+        let mut history = Self::load(root)?;
+        push_capped(&mut history.runs, run);
+        save_history(&Self::path(root), &history)
+    }
+
+    /// The most recent run that built `binary` with the given LTO and strip settings.
+    pub fn latest(&self, binary: &str, lto: &str, stripped: bool) -> Option<&BuildRun> {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| run.binary == binary && run.lto == lto && run.stripped == stripped)
+    }
+}
+
+/// Benchmark results saved by `oxy bench baseline save`, stored in
+/// `.oxygen/baselines/<name>.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BenchBaseline {
+    pub name: String,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub git_hash: Option<String>,
+    /// Nanoseconds per iteration, by benchmark name
+    pub benchmarks: BTreeMap<String, f64>,
+}
+
+impl BenchBaseline {
+    pub fn path(name: &str) -> PathBuf {
+        Path::new(HISTORY_DIR)
+            .join("baselines")
+            .join(format!("{}.json", name))
+    }
+
+    /// Load the baseline saved as `name`, if there is one.
+    pub fn load(name: &str) -> Result<Option<Self>> {
+        let path = Self::path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        load_history(&path).map(Some)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_history(&Self::path(&self.name), self)
+    }
+}
+
+fn load_history<T: Default + DeserializeOwned>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn save_history<T: Serialize>(path: &Path, history: &T) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new(HISTORY_DIR));
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    std::fs::write(path, serde_json::to_string_pretty(history)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Append `run`, dropping the oldest entries past `MAX_RUNS`.
+fn push_capped<T>(runs: &mut Vec<T>, run: T) {
+    runs.push(run);
+    if runs.len() > MAX_RUNS {
+        let excess = runs.len() - MAX_RUNS;
+        runs.drain(..excess);
+    }
+}
+
+/// Short hash of the checked-out commit, if in a git repository.
+pub fn git_short_hash() -> Option<String> {
+    run_command("git", &["rev-parse", "--short", "HEAD"])
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+}
+
+/// Seconds since the Unix epoch, for history timestamps.
+pub fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Render values as a row of block characters scaled between their min and max.
+pub fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = max - min;
+    values
+        .iter()
+        .map(|value| {
+            let level = ((value - min) * (BARS.len() as u64 - 1))
+                .checked_div(range)
+                .unwrap_or(0);
+            BARS[level as usize]
+        })
+        .collect()
+}
@@ -1,13 +1,19 @@
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 pub mod commands;
 pub mod config;
+pub mod history;
 pub mod utils;
 
 #[derive(Subcommand)]
 pub enum ToolchainAction {
     /// List installed toolchains
-    List,
+    List {
+        /// Show disk usage for each toolchain
+        #[arg(long)]
+        with_sizes: bool,
+    },
     /// Install a toolchain
     Install {
         /// Toolchain to install (stable, beta, nightly, or specific version)
@@ -25,20 +31,239 @@ pub enum ToolchainAction {
         /// Toolchain to remove
         toolchain: String,
     },
+    /// Pin the active toolchain in rust-toolchain.toml
+    Pin {
+        /// Overwrite an existing pin without asking
+        #[arg(long)]
+        force: bool,
+    },
+    /// Run `cargo check` for several targets and show which ones compile
+    CrossCheck {
+        /// Target triples to check (default: all installed targets)
+        targets: Vec<String>,
+    },
+    /// Show which toolchain provides a binary such as rustfmt, and every toolchain that has it
+    Which {
+        /// Name of the binary, e.g. rustfmt or cargo-clippy
+        binary: String,
+    },
+    /// Check that the toolchain and components rust-toolchain.toml asks for are installed
+    Check,
+    /// Uninstall every toolchain except the default, the active one and directory overrides
+    Purge {
+        /// Only list the toolchains that would be removed
+        #[arg(long)]
+        dry_run: bool,
+        /// Toolchain to keep, e.g. nightly or 1.70 (repeatable)
+        #[arg(long, value_name = "TOOLCHAIN")]
+        keep: Vec<String>,
+        /// Remove without asking for confirmation
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TargetAction {
+    /// List available compilation targets
+    List {
+        /// Only show installed targets
+        #[arg(long)]
+        installed_only: bool,
+    },
+    /// Install a compilation target
+    Add {
+        /// Target triple, e.g. aarch64-unknown-linux-gnu
+        triple: String,
+        /// Also make it the default build target in .cargo/config.toml
+        #[arg(long)]
+        set_default: bool,
+    },
+    /// Remove an installed compilation target
+    Remove {
+        /// Target triple to remove
+        triple: String,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum DepsAction {
     /// Show dependency tree with vulnerabilities
-    Tree,
+    Tree {
+        /// Exit with an error if any yanked crate versions are used
+        #[arg(long)]
+        fail_on_yanked: bool,
+        /// Only follow normal dependencies, leaving out dev and build dependencies
+        #[arg(long, conflicts_with = "edges")]
+        no_dev: bool,
+        /// Dependency kinds to follow, passed to `cargo tree --edges` (e.g. normal,build)
+        #[arg(long, value_name = "KINDS")]
+        edges: Option<String>,
+        /// Only show these kinds of dependencies; `proc-macro` keeps proc-macro crates and
+        /// what they depend on (comma-separated: normal, dev, build, proc-macro)
+        #[arg(
+            long,
+            value_name = "TYPES",
+            value_delimiter = ',',
+            value_parser = ["normal", "dev", "build", "proc-macro"],
+            conflicts_with_all = ["edges", "no_dev"]
+        )]
+        crate_types: Vec<String>,
+    },
     /// Check for outdated dependencies
-    Outdated,
+    Outdated {
+        /// Check every workspace member and group the results per member
+        #[arg(long)]
+        workspace: bool,
+    },
     /// Audit dependencies for security issues
-    Audit,
+    Audit {
+        /// Upgrade vulnerable dependencies with `cargo audit fix` (needs its `fix` feature)
+        #[arg(long)]
+        fix: bool,
+        /// With --fix, only show which packages would be upgraded
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+    },
     /// Show dependency licenses
     Licenses,
     /// Analyze dependency sizes
     Size,
+    /// Pin every dependency in Cargo.toml to the exact version in Cargo.lock
+    PinExact,
+    /// Download all dependencies without building
+    Fetch {
+        /// Set `net.offline = true` in .cargo/config.toml once the fetch succeeds
+        #[arg(long)]
+        offline_after: bool,
+    },
+    /// Download the dependencies of every feature set ahead of feature-matrix builds
+    FetchAllFeatures {
+        /// Comma-separated feature list to fetch for, instead of each feature (repeatable)
+        #[arg(long = "features", value_name = "LIST")]
+        feature_sets: Vec<String>,
+    },
+    /// Run cargo check for every combination of the crate's features
+    FeatureMatrix {
+        /// Stop after this many combinations, smallest combinations first
+        #[arg(long, default_value_t = 16, value_name = "N")]
+        max_combinations: usize,
+    },
+    /// Show which dependencies use `unsafe`, using cargo-geiger
+    #[command(alias = "import-map")]
+    UnsafeMap,
+    /// Check that the project and its dependencies build with the minimum supported Rust version
+    CompatCheck {
+        /// Toolchain to check with (defaults to package.rust-version)
+        #[arg(long)]
+        msrv: Option<String>,
+    },
+    /// Manage `[patch.crates-io]` overrides in Cargo.toml
+    Patch {
+        #[command(subcommand)]
+        action: PatchAction,
+    },
+    /// Show who publishes the dependencies, using cargo-supply-chain (all views by default)
+    SupplyChain {
+        /// List each crate with its publishers
+        #[arg(long)]
+        crates: bool,
+        /// List each publisher (user or team) with the crates they publish
+        #[arg(long)]
+        publishers: bool,
+        /// Like --publishers, but only individual users
+        #[arg(long)]
+        users: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PatchAction {
+    /// Patch a crates.io dependency with a local checkout, checking that it applies
+    Add {
+        /// Crate to patch
+        crate_name: String,
+        /// Directory of the local checkout
+        path: PathBuf,
+    },
+    /// Remove a crate's patch
+    Remove {
+        /// Crate whose patch to remove
+        crate_name: String,
+    },
+    /// Show every `[patch]` entry in Cargo.toml
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum EnvAction {
+    /// Remove cached registry and git sources not used by any local Cargo.lock
+    Clean {
+        /// Actually delete stale entries (otherwise only report them)
+        #[arg(short = 'y', long)]
+        confirm: bool,
+    },
+    /// Write a self-contained HTML report of `oxy env`, `doctor`, `info` and `tools` to
+    /// attach to bug reports
+    Report {
+        /// File to write the report to
+        #[arg(short, long, default_value = "oxygen_report.html")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CompletionsAction {
+    /// Write the completion script to the shell's completion directory
+    Install {
+        /// Shell to install for (detected from $SHELL by default)
+        #[arg(long)]
+        shell: Option<clap_complete::Shell>,
+    },
+    /// Check that the installed completion script matches this version of oxy
+    Check {
+        /// Shell to check (detected from $SHELL by default)
+        #[arg(long)]
+        shell: Option<clap_complete::Shell>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ManifestAction {
+    /// Print a normalized, pretty-printed Cargo.toml
+    Show,
+    /// Check Cargo.toml for missing fields, invalid values and duplicate dependencies
+    Validate,
+    /// Reorder Cargo.toml sections into the conventional order
+    Format {
+        /// Rewrite Cargo.toml instead of printing the result
+        #[arg(long)]
+        in_place: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum FmtAction {
+    /// Print a unified diff of what `cargo fmt` would change, without applying it
+    ShowDiff,
+}
+
+#[derive(Subcommand)]
+pub enum GhAction {
+    /// Create a GitHub release with the signed release binary and checksums
+    Release {
+        /// Tag to create the release for
+        tag: String,
+        /// Create the release as a draft
+        #[arg(long)]
+        draft: bool,
+        /// Release notes (defaults to notes generated by GitHub)
+        #[arg(long)]
+        notes: Option<String>,
+        /// Binary to release (defaults to the package's only binary or its default-run)
+        #[arg(long)]
+        bin: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -55,6 +280,189 @@ pub enum GpgAction {
     },
     /// Setup GPG for Rust development
     Setup,
+    /// Sign or verify an artifact with Sigstore cosign
+    Cosign {
+        /// Artifact to sign, writing a bundle to <artifact>.bundle
+        #[arg(long, value_name = "ARTIFACT", required_unless_present = "verify")]
+        sign: Option<String>,
+        /// Artifact to verify against its <artifact>.bundle
+        #[arg(long, value_name = "ARTIFACT", conflicts_with = "sign")]
+        verify: Option<String>,
+        /// Use the OIDC-based keyless workflow instead of cosign.key / cosign.pub
+        #[arg(long)]
+        keyless: bool,
+        /// Signer identity a keyless signature must carry, e.g. an email or workflow URL
+        #[arg(long, value_name = "IDENTITY", requires = "verify")]
+        certificate_identity: Option<String>,
+        /// OIDC issuer that must have vouched for the identity, e.g. https://github.com/login/oauth
+        #[arg(long, value_name = "URL", requires = "verify")]
+        certificate_oidc_issuer: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VetAction {
+    /// Check dependencies against recorded audits
+    Check,
+    /// Certify that you have audited a crate version
+    Certify {
+        /// Name of the crate that was audited
+        crate_name: String,
+        /// Version of the crate that was audited
+        version: String,
+    },
+    /// Initialize cargo-vet for this project
+    Init,
+}
+
+#[derive(Subcommand)]
+pub enum CiAction {
+    /// Write a GitHub Actions workflow to .github/workflows/oxy.yml that runs oxy
+    Generate {
+        /// Operating systems to run the check and test jobs on
+        #[arg(long, value_enum, default_value = "linux")]
+        platform: CiPlatform,
+        /// Also run `cargo bench` every week
+        #[arg(long)]
+        bench: bool,
+        /// Overwrite an existing workflow without asking
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Runner platforms for `oxy ci generate`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CiPlatform {
+    Linux,
+    Macos,
+    Windows,
+    All,
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceAction {
+    /// List workspace members with their path, version and crate type
+    List,
+    /// Run fmt, clippy and check across every member
+    Check,
+    /// Build every member in release mode
+    Build,
+    /// Print the dependency graph between members in DOT format
+    Graph,
+}
+
+#[derive(Subcommand)]
+pub enum LintAction {
+    /// List clippy's lints with their default level and group
+    List {
+        /// Only show lints whose name contains this text
+        #[arg(long, value_name = "PATTERN")]
+        filter: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TestAction {
+    /// Run the tests with coverage and fail if line coverage is below a threshold
+    CoverageGate {
+        /// Minimum line coverage in percent
+        #[arg(long)]
+        threshold: f64,
+        /// Also write a coverage report in this format to target/coverage
+        #[arg(long)]
+        format: Option<CoverageFormat>,
+    },
+}
+
+/// Report format written by `oxy test coverage-gate --format`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CoverageFormat {
+    /// lcov.info, read by most coverage services
+    Lcov,
+    /// Cobertura XML, as used by GitLab and Jenkins
+    Cobertura,
+    /// Browsable HTML report
+    Html,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Check the global and project config files for errors, unknown keys and missing paths
+    Validate,
+}
+
+#[derive(Subcommand)]
+pub enum BenchAction {
+    /// Manage saved benchmark results in .oxygen/baselines
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+    /// Run the benchmarks and compare them with a saved baseline
+    Compare {
+        /// Name the baseline was saved under
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BaselineAction {
+    /// Run the benchmarks and save the results as a baseline
+    Save {
+        /// Name to save the baseline under, e.g. main
+        name: String,
+    },
+}
+
+/// Release profile LTO setting forced by `oxy build --lto`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LtoMode {
+    Thin,
+    Fat,
+    Off,
+}
+
+/// Intermediate compiler output requested with `oxy build --emit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EmitFormat {
+    /// Assembly (.s)
+    Asm,
+    /// LLVM IR (.ll)
+    #[value(name = "llvm-ir")]
+    LlvmIr,
+    /// LLVM bitcode (.bc)
+    #[value(name = "llvm-bc")]
+    LlvmBc,
+    /// Rust MIR (.mir)
+    Mir,
+}
+
+/// Document format written by `oxy sbom`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SbomFormat {
+    /// SPDX 2.3 tag-value
+    Spdx,
+    /// CycloneDX 1.4 JSON
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+}
+
+#[derive(Subcommand)]
+pub enum GenerateKind {
+    /// Write a Markdown summary of Cargo.lock
+    LockfileSummary {
+        /// File to write the summary to (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Scaffold an FFI crate under crates/ that runs bindgen on a C header
+    Bindgen {
+        /// C header to generate bindings for
+        header: PathBuf,
+        /// Name of the generated crate
+        crate_name: String,
+    },
 }
 
 #[cfg(test)]
@@ -80,4 +488,1281 @@ mod tests {
         // This should be true since we're in a Rust project
         assert!(utils::is_rust_project());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_perf_parse_unit_data() {
+        let html = r#"<script>
+const UNIT_DATA = [
+  {"i": 0, "name": "serde", "version": "1.0.0", "target": "", "duration": 1.5,
+   "sections": [["frontend", {"start": 0.0, "end": 1.0}], ["codegen", {"start": 1.0, "end": 1.5}]]},
+  {"i": 1, "name": "syn", "version": "2.0.0", "target": " build-script", "duration": 0.25, "rmeta_time": 0.05}
+];
+const CONCURRENCY_DATA = [];
+</script>"#;
+        let units = commands::perf::parse_unit_data(html);
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0]["total_ms"], 1500);
+        assert_eq!(units[0]["codegen_ms"], 500);
+        assert_eq!(units[1]["name"], "syn (build-script)");
+        assert_eq!(units[1]["codegen_ms"], 200);
+    }
+
+    #[test]
+    fn test_check_parse_machete_output() {
+        let stdout = "Analyzing dependencies of crates in this directory...
+cargo-machete found the following unused dependencies in this directory:
+demo -- ./Cargo.toml:
+\tanyhow
+\tlog
+
+If you believe cargo-machete has detected an unused dependency incorrectly,
+Done!
+";
+        let unused = commands::check::parse_machete_output(stdout);
+        assert_eq!(unused.len(), 2);
+        assert_eq!(unused[0]["crate"], "demo");
+        assert_eq!(unused[0]["manifest"], "./Cargo.toml");
+        assert_eq!(unused[1]["dependency"], "log");
+    }
+
+    #[test]
+    fn test_utils_unified_diff() {
+        let original = "fn main() {\nlet x=1;\n    println!(\"{}\", x);\n}\n";
+        let formatted = "fn main() {\n    let x = 1;\n    println!(\"{}\", x);\n}\n";
+        let diff = utils::unified_diff(original, formatted, "src/main.rs");
+        assert!(diff.starts_with("--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,4 +1,4 @@\n"));
+        assert!(diff.contains("\n-let x=1;\n+    let x = 1;\n"));
+        assert!(utils::unified_diff(original, original, "src/main.rs").is_empty());
+    }
+
+    #[test]
+    fn test_init_is_safe_archive_path() {
+        assert!(commands::init::is_safe_archive_path("template/src/main.rs"));
+        assert!(commands::init::is_safe_archive_path("./Cargo.toml"));
+        assert!(!commands::init::is_safe_archive_path("../evil.rs"));
+        assert!(!commands::init::is_safe_archive_path("template/../../evil.rs"));
+        assert!(!commands::init::is_safe_archive_path("/etc/passwd"));
+        assert!(!commands::init::is_safe_archive_path("C:\\evil.rs"));
+    }
+
+    #[test]
+    fn test_deps_annotate_advisory_urls() {
+        let mut report = serde_json::json!({
+            "vulnerabilities": {
+                "found": true,
+                "count": 2,
+                "list": [
+                    {"advisory": {"id": "RUSTSEC-2023-0071", "title": "Marvin Attack"}},
+                    {"advisory": {"id": "GHSA-xxxx", "title": "Not RustSec"}}
+                ]
+            }
+        });
+        commands::deps::annotate_advisory_urls(&mut report);
+        let list = &report["vulnerabilities"]["list"];
+        assert_eq!(
+            list[0]["url"],
+            "https://rustsec.org/advisories/RUSTSEC-2023-0071.html"
+        );
+        assert!(list[1].get("url").is_none());
+    }
+
+    #[test]
+    fn test_manifest_validate() {
+        let valid = "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n";
+        assert!(commands::manifest::validate_manifest(valid).is_empty());
+
+        let invalid = r#"
+[package]
+name = "1demo"
+version = "1.0"
+edition = "2020"
+
+[dependencies]
+foo-bar = "1.0"
+foo_bar = "1.0"
+"#;
+        let errors = commands::manifest::validate_manifest(invalid);
+        let fields: Vec<_> = errors.iter().map(|e| e["field"].as_str().unwrap()).collect();
+        assert_eq!(
+            fields,
+            [
+                "package.name",
+                "package.version",
+                "package.edition",
+                "dependencies.foo_bar"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_info_scan_build_script() {
+        let source = r#"
+            extern crate pkg_config;
+            use std::env;
+
+            fn main() {
+                // Built with gcc via the cc crate
+                cc::Build::new().file("src/native.c").compile("native");
+                pkg_config::probe_library("zlib").unwrap();
+            }
+        "#;
+        let usage = commands::info::scan_build_script(source);
+        assert_eq!(usage["uses_cc"], true);
+        assert_eq!(usage["uses_pkg_config"], true);
+        assert_eq!(usage["uses_cmake"], false);
+        assert_eq!(usage["uses_bindgen"], false);
+    }
+
+    #[test]
+    fn test_toolchain_parse_channel() {
+        let host = Some("x86_64-unknown-linux-gnu");
+        assert_eq!(
+            commands::toolchain::parse_channel("stable-x86_64-unknown-linux-gnu (default)", host),
+            "stable"
+        );
+        assert_eq!(
+            commands::toolchain::parse_channel(
+                "nightly-2024-05-01-x86_64-unknown-linux-gnu (overridden by '/p/rust-toolchain.toml')",
+                host
+            ),
+            "nightly-2024-05-01"
+        );
+        assert_eq!(
+            commands::toolchain::parse_channel("1.78.0-aarch64-apple-darwin", None),
+            "1.78.0"
+        );
+        assert_eq!(commands::toolchain::parse_channel("my-custom-toolchain", host), "my-custom-toolchain");
+    }
+
+    #[test]
+    fn test_utils_count_code_lines() {
+        let source = "//! Crate docs\n\nfn main() {\n    /* inline */\n    /*\n     * block\n     */\n    println!(\"hi\"); // trailing\n}\n";
+        assert_eq!(utils::count_code_lines(source), 3);
+    }
+
+    #[test]
+    fn test_verify_first_difference() {
+        assert_eq!(commands::verify::first_difference(b"abcdef", b"abcdef"), None);
+        assert_eq!(commands::verify::first_difference(b"abcdef", b"abXdef"), Some(2));
+        assert_eq!(commands::verify::first_difference(b"abc", b"abcdef"), Some(3));
+    }
+
+    #[test]
+    fn test_info_count_debt_markers() {
+        let source = r#"
+            // FIXME: handle errors properly
+            fn parse(input: &str) -> u32 {
+                let value = input.parse().unwrap();
+                let other = input.parse::<u32>().unwrap_or(0);
+                std::env::var("HOME").expect("HOME is set");
+                todo!()
+            }
+        "#;
+        // todo, fixme, unimplemented, unwrap, expect
+        assert_eq!(commands::info::count_debt_markers(source), vec![1, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_check_count_clippy_warnings() {
+        let warned = "    Checking demo v0.1.0\nwarning: unused variable: `x`\nwarning: `demo` (bin \"demo\") generated 2 warnings (run `cargo clippy --fix` to apply 1 suggestion)\n";
+        assert_eq!(commands::check::count_clippy_warnings(warned), 2);
+
+        let denied = "error: unused variable: `x`\nerror: could not compile `demo` (bin \"demo\") due to 3 previous errors\n";
+        assert_eq!(commands::check::count_clippy_warnings(denied), 3);
+        assert_eq!(commands::check::count_clippy_warnings("    Finished dev\n"), 0);
+    }
+
+    #[test]
+    fn test_history_sparkline() {
+        assert_eq!(history::sparkline(&[0, 7, 14]), "▁▄█");
+        assert_eq!(history::sparkline(&[3, 3]), "▁▁");
+        assert_eq!(history::sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_history_check_passed() {
+        let run = |clippy_passed, clippy_warnings| history::CheckRun {
+            timestamp: 0,
+            git_hash: None,
+            fmt_passed: Some(true),
+            clippy_passed,
+            clippy_warnings,
+            check_passed: Some(true),
+            total_duration_ms: 0,
+        };
+        assert!(run(Some(true), Some(0)).passed());
+        assert!(run(None, None).passed());
+        assert!(!run(Some(true), Some(2)).passed());
+        // clippy failed to compile the crate, so it found no lints to count
+        assert!(!run(Some(false), Some(0)).passed());
+    }
+
+    #[test]
+    fn test_deps_group_outdated_by_member() {
+        let stdout = concat!(
+            r#"{"crate_name":"app","dependencies":[{"name":"serde","project":"1.0.0","compat":"1.0.9","latest":"1.0.9","kind":"Normal","platform":null}]}"#,
+            "\n",
+            r#"{"crate_name":"core","dependencies":[]}"#,
+            "\n"
+        );
+        let paths = std::collections::HashMap::from([
+            ("app".to_string(), "crates/app".to_string()),
+            ("core".to_string(), "crates/core".to_string()),
+        ]);
+
+        let members = commands::deps::group_outdated_by_member(stdout, &paths);
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0]["name"], "app");
+        assert_eq!(members[0]["path"], "crates/app");
+        assert_eq!(members[0]["outdated_deps"][0]["name"], "serde");
+        assert_eq!(members[1]["outdated_deps"].as_array().map(|d| d.len()), Some(0));
+    }
+
+    #[test]
+    fn test_explain_normalize_error_code() {
+        assert_eq!(commands::explain::normalize_error_code("E0502").as_deref(), Some("E0502"));
+        assert_eq!(commands::explain::normalize_error_code("e0382").as_deref(), Some("E0382"));
+        assert_eq!(commands::explain::normalize_error_code("502").as_deref(), Some("E0502"));
+        assert_eq!(commands::explain::normalize_error_code("E05020"), None);
+        assert_eq!(commands::explain::normalize_error_code("borrow"), None);
+    }
+
+    #[test]
+    fn test_explain_strip_ansi() {
+        let text = "\x1b[1mError\x1b[0m: \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+        assert_eq!(commands::explain::strip_ansi(text), "Error: link");
+    }
+
+    #[test]
+    fn test_env_effective_rustflags() {
+        let config = vec!["-C".to_string(), "target-cpu=native".to_string()];
+
+        let (source, flags) = commands::env::effective_rustflags(None, None, &config);
+        assert_eq!(source, "config");
+        assert_eq!(flags, config);
+
+        let (source, flags) = commands::env::effective_rustflags(None, Some("-D warnings"), &config);
+        assert_eq!(source, "env");
+        assert_eq!(flags, ["-D", "warnings"]);
+
+        let encoded = "--cfg\x1ffoo=\"a b\"";
+        let (source, flags) =
+            commands::env::effective_rustflags(Some(encoded), Some("-D warnings"), &config);
+        assert_eq!(source, "encoded");
+        assert_eq!(flags, ["--cfg", "foo=\"a b\""]);
+    }
+
+    #[test]
+    fn test_deps_parse_geiger_report() {
+        let count = |unsafe_| serde_json::json!({"safe": 1, "unsafe_": unsafe_});
+        let package = |name: &str, deps: serde_json::Value, unsafe_: u64, forbids: bool| {
+            serde_json::json!({
+                "package": {
+                    "id": {"name": name, "version": "1.0.0"},
+                    "dependencies": deps
+                },
+                "unsafety": {
+                    "used": {
+                        "functions": count(0),
+                        "exprs": count(unsafe_),
+                        "item_impls": count(0),
+                        "item_traits": count(0),
+                        "methods": count(0)
+                    },
+                    "forbids_unsafe": forbids
+                }
+            })
+        };
+        let report = serde_json::json!({
+            "packages": [
+                package("app", serde_json::json!([{"name": "libc", "version": "1.0.0"}]), 0, true),
+                package("libc", serde_json::json!([]), 12, false)
+            ]
+        });
+
+        let crates = commands::deps::parse_geiger_report(&report);
+        assert_eq!(crates[0]["name"], "libc");
+        assert_eq!(crates[0]["unsafe_calls"], 12);
+        assert_eq!(crates[1]["name"], "app");
+        assert_eq!(crates[1]["forbids_unsafe"], true);
+        assert_eq!(crates[1]["transitive_unsafe_calls"], 12);
+    }
+
+    #[test]
+    fn test_hooks_hook_script() {
+        let script = commands::hooks::hook_script("oxy check", None);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.ends_with("exec oxy check\n"));
+
+        let chained = commands::hooks::hook_script("oxy check", Some("pre-push.pre-oxy"));
+        assert!(chained.contains("chained=\"$(dirname \"$0\")/pre-push.pre-oxy\"\n"));
+        assert!(chained.contains("    \"$chained\" \"$@\" || exit $?\n"));
+    }
+
+    #[test]
+    fn test_build_slowest_units() {
+        let artifacts = vec![
+            serde_json::json!({
+                "package_id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.200",
+                "target": {"name": "serde"},
+                "fresh": false,
+                "duration_ms": 900
+            }),
+            serde_json::json!({
+                "package_id": "path+file:///work/app#0.1.0",
+                "target": {"name": "app"},
+                "fresh": false,
+                "duration_ms": 1500
+            }),
+            serde_json::json!({
+                "package_id": "registry+https://github.com/rust-lang/crates.io-index#libc@0.2.150",
+                "target": {"name": "libc"},
+                "fresh": true,
+                "duration_ms": 5000
+            }),
+        ];
+        let units = commands::build::slowest_units(&artifacts, &[]);
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0]["name"], "app");
+        assert_eq!(units[0]["version"], "0.1.0");
+        assert_eq!(units[1]["name"], "serde");
+
+        // Without durations in the messages, fall back to the timing report
+        let timing = vec![serde_json::json!({"name": "syn", "version": "2.0.0", "total_ms": 700})];
+        let units = commands::build::slowest_units(&[serde_json::json!({"fresh": false})], &timing);
+        assert_eq!(units[0]["name"], "syn");
+        assert_eq!(units[0]["duration_ms"], 700);
+    }
+
+    #[test]
+    fn test_info_find_deprecated_items() {
+        let source = r#"
+#[deprecated(since = "0.3.0", note = "use `connect` instead")]
+pub(crate) async fn open() {}
+
+#[deprecated = "renamed to Config"]
+#[derive(Debug)]
+pub struct Settings;
+
+pub enum Mode {
+    #[deprecated(
+        since = "0.2.0"
+    )]
+    Legacy,
+}
+"#;
+        let items = commands::info::find_deprecated_items(source);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["line"], 2);
+        assert_eq!(items[0]["item_name"], "open");
+        assert_eq!(items[0]["since"], "0.3.0");
+        assert_eq!(items[0]["note"], "use `connect` instead");
+        assert_eq!(items[1]["item_name"], "Settings");
+        assert_eq!(items[1]["note"], "renamed to Config");
+        assert_eq!(items[2]["item_name"], "Legacy");
+        assert_eq!(items[2]["since"], "0.2.0");
+        assert!(items[2]["note"].is_null());
+    }
+
+    #[test]
+    fn test_deps_msrv_failures() {
+        let old_cargo = "error: package `clap_lex v0.7.4` cannot be built because it requires rustc 1.74 or newer, while the currently active rustc version is 1.70.0\n";
+        let failures = commands::deps::msrv_failures(old_cargo);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0]["name"], "clap_lex");
+        assert_eq!(failures[0]["error_message"], "requires rustc 1.74 or newer");
+
+        let new_cargo = concat!(
+            "error: rustc 1.78.0 is not supported by the following packages:\n",
+            "  clap@4.5.20 requires rustc 1.80\n",
+            "  clap_builder@4.5.20 requires rustc 1.80\n",
+            "Either upgrade rustc or select compatible dependency versions with\n"
+        );
+        let failures = commands::deps::msrv_failures(new_cargo);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[1]["name"], "clap_builder");
+        assert_eq!(failures[1]["error_message"], "requires rustc 1.80");
+    }
+
+    #[test]
+    fn test_perf_hint_find_perf_hints() {
+        let source = r#"
+fn render(items: &[Item], name: String) -> String {
+    let label = name.clone();
+    for item in items {
+        let copy = item.clone();
+        let line = format!("{} {}", label, "}");
+    }
+    let done = label.clone();
+    done
+}
+"#;
+        let hints = commands::perf_hint::find_perf_hints(source);
+        let kinds: Vec<(&str, u64)> = hints
+            .iter()
+            .map(|hint| (hint["kind"].as_str().unwrap(), hint["line"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [("string_param", 2), ("clone_in_loop", 5), ("format_in_loop", 6)]
+        );
+    }
+
+    #[test]
+    fn test_sbom_spdx_tag_value() {
+        use commands::sbom::SbomPackage;
+
+        let packages = vec![
+            SbomPackage {
+                name: "demo".to_string(),
+                version: "0.1.0".to_string(),
+                license: Some("MIT".to_string()),
+                from_crates_io: false,
+                is_local: true,
+            },
+            SbomPackage {
+                name: "serde_json".to_string(),
+                version: "1.0.0+build".to_string(),
+                license: None,
+                from_crates_io: true,
+                is_local: false,
+            },
+        ];
+        let document = commands::sbom::spdx_tag_value("demo", &packages, "2024-01-01T00:00:00Z");
+
+        assert!(document.starts_with("SPDXVersion: SPDX-2.3\n"));
+        assert!(document.contains("Created: 2024-01-01T00:00:00Z\n"));
+        assert!(document.contains("SPDXID: SPDXRef-Package-serde-json-1.0.0-build\n"));
+        assert!(document.contains("ExternalRef: PACKAGE-MANAGER purl pkg:cargo/serde_json@1.0.0+build\n"));
+        assert!(document.contains("PackageLicenseDeclared: MIT\n"));
+        assert!(document.contains("PackageLicenseDeclared: NOASSERTION\n"));
+        assert!(document.contains(
+            "PackageDownloadLocation: https://crates.io/api/v1/crates/serde_json/1.0.0+build/download\n"
+        ));
+        assert!(document.contains("Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-Package-demo-0.1.0\n"));
+        assert!(!document.contains("DESCRIBES SPDXRef-Package-serde"));
+    }
+
+    #[test]
+    fn test_trace_parse_log_line() {
+        let event = commands::trace::parse_log_line(
+            "\x1b[2m2024-01-01T00:00:00Z\x1b[0m \x1b[33m WARN\x1b[0m app: slow request",
+        );
+        assert_eq!(event["level"], "WARN");
+        assert_eq!(event["raw"], "2024-01-01T00:00:00Z  WARN app: slow request");
+
+        let event = commands::trace::parse_log_line(
+            r#"{"timestamp":"2024-01-01T00:00:00Z","level":"error","fields":{"message":"boom"}}"#,
+        );
+        assert_eq!(event["level"], "ERROR");
+        assert_eq!(event["fields"]["message"], "boom");
+
+        let event = commands::trace::parse_log_line("   Compiling app v0.1.0");
+        assert!(event["level"].is_null());
+    }
+
+    #[test]
+    fn test_build_linker_commands() {
+        let output = r#"       Fresh cc v1.2.0
+[demo 0.1.0] running: "cc" "-O3" "-c" "hello.c"
+[demo 0.1.0] running: "ar" "cq" "libhello.a"
+[demo 0.1.0] running: "/usr/bin/aarch64-linux-gnu-gcc" "-c" "hello.c"
+     Running `/home/me/.rustup/toolchains/stable/bin/rustc --crate-name demo`
+LC_ALL="C" PATH="/opt/rust/bin:/usr/bin" VSLANG="1033" "cc" "-m64" "-o" "target/release/demo"
+{"reason":"build-finished","success":true}"#;
+
+        assert_eq!(
+            commands::build::linker_commands(output),
+            vec![
+                r#""cc" "-O3" "-c" "hello.c""#,
+                r#""/usr/bin/aarch64-linux-gnu-gcc" "-c" "hello.c""#,
+                r#""cc" "-m64" "-o" "target/release/demo""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_completions_up_to_date() {
+        use commands::completions::{completion_script, completions_up_to_date};
+        let oxy = |subcommands: &[&'static str]| {
+            let mut cmd = clap::Command::new("oxy")
+                .subcommands(subcommands.iter().map(|name| clap::Command::new(*name)));
+            completion_script(clap_complete::Shell::Zsh, &mut cmd)
+        };
+        let installed = oxy(&["build"]);
+        assert!(completions_up_to_date(Some(&installed), &oxy(&["build"])));
+        assert!(!completions_up_to_date(Some(&installed), &oxy(&["build", "bench"])));
+        assert!(!completions_up_to_date(None, &oxy(&["build"])));
+    }
+
+    #[test]
+    fn test_check_parse_targets_file() {
+        let content = "# crates\ncore\n\ncli  # the binary\n--target wasm32-unknown-unknown\n--target=aarch64-apple-darwin\n";
+        let (packages, targets) = commands::check::parse_targets_file(content);
+        assert_eq!(packages, vec!["core", "cli"]);
+        assert_eq!(
+            targets,
+            vec!["wasm32-unknown-unknown", "aarch64-apple-darwin"]
+        );
+    }
+
+    #[test]
+    fn test_ci_workflow() {
+        let workflow = commands::ci::ci_workflow(&["ubuntu-latest", "windows-latest"], false);
+        assert!(workflow.contains("os: [ubuntu-latest, windows-latest]"));
+        assert!(workflow.contains("run: oxy check\n"));
+        assert!(workflow.contains("run: oxy deps audit\n"));
+        assert!(workflow.contains("~/.cargo/registry/cache/"));
+        assert!(!workflow.contains("schedule"));
+        assert!(!workflow.contains("cargo bench"));
+
+        let workflow = commands::ci::ci_workflow(&["ubuntu-latest"], true);
+        assert!(workflow.contains("  schedule:\n"));
+        assert!(workflow.contains("if: github.event_name != 'schedule'"));
+        assert!(workflow.contains("run: cargo bench --workspace"));
+    }
+
+    #[test]
+    fn test_deps_included_edge_kinds() {
+        use commands::deps::included_edge_kinds;
+
+        assert_eq!(included_edge_kinds(None), vec!["normal", "build", "dev"]);
+        assert_eq!(included_edge_kinds(Some("normal")), vec!["normal"]);
+        assert_eq!(included_edge_kinds(Some("no-dev")), vec!["normal", "build"]);
+        assert_eq!(
+            included_edge_kinds(Some("all,no-build,features")),
+            vec!["normal", "dev", "features"]
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_edges_and_graph_dot() {
+        use commands::workspace::{WorkspaceMember, graph_dot, member_edges};
+
+        let member = |name: &str, manifest: &str| WorkspaceMember {
+            name: name.to_string(),
+            path: std::path::PathBuf::from(name),
+            version: None,
+            kind: "lib",
+            manifest: toml::from_str(manifest).unwrap(),
+        };
+        let members = vec![
+            member(
+                "app",
+                "[dependencies]\ncore = { workspace = true }\nserde = \"1\"\n\n[dev-dependencies]\nhelpers = { path = \"../test-utils\", package = \"test-utils\" }\n",
+            ),
+            member("core", "[target.'cfg(unix)'.build-dependencies]\nbuild-utils = { path = \"../build-utils\" }\n"),
+            member("test-utils", "[dependencies]\ncore = { path = \"../core\" }\n"),
+            member("build-utils", ""),
+        ];
+
+        let edges = member_edges(&members);
+        let summary: Vec<(&str, &str, &str)> = edges
+            .iter()
+            .map(|edge| (edge.from.as_str(), edge.to.as_str(), edge.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("app", "core", "normal"),
+                ("app", "test-utils", "dev"),
+                ("core", "build-utils", "build"),
+                ("test-utils", "core", "normal"),
+            ]
+        );
+
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        let dot = graph_dot(&names, &edges);
+        assert!(dot.starts_with("digraph workspace {\n"));
+        assert!(dot.contains("    \"build-utils\";\n"));
+        assert!(dot.contains("    \"app\" -> \"core\";\n"));
+        assert!(dot.contains("    \"app\" -> \"test-utils\" [style=dashed, label=\"dev\"];\n"));
+        assert!(dot.contains("    \"core\" -> \"build-utils\" [style=dotted, label=\"build\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_info_count_public_items() {
+        let source = "pub struct Config;\npub(crate) struct Internal;\npub enum Mode { A }\npub unsafe trait Marker {}\npub async fn run() {}\npub const fn limit() -> usize { 1 }\npub extern \"C\" fn callback() {}\nfn private() {}\npub type Result<T> = std::result::Result<T, Error>;\npub const MAX: usize = 3;\npub static NAME: &str = \"x\";\npub mod config;\npub(crate) mod utils;\nmod history;\nmod tests {\n";
+
+        // structs, enums, traits, functions, types, consts
+        assert_eq!(commands::info::count_public_items(source), vec![1, 1, 1, 3, 1, 1]);
+        assert_eq!(
+            commands::info::module_declarations(source),
+            vec!["config", "utils", "history"]
+        );
+    }
+
+    #[test]
+    fn test_deps_feature_sets_to_fetch() {
+        let manifest: toml::Value = toml::from_str(
+            "[features]\ndefault = [\"json\"]\njson = [\"dep:serde_json\"]\ntls = [\"reqwest?/rustls\"]\n\n[dependencies]\nserde_json = { version = \"1\", optional = true }\nreqwest = { version = \"0.12\", optional = true }\nanyhow = \"1\"\n",
+        )
+        .unwrap();
+
+        let labels: Vec<String> = commands::deps::feature_sets_to_fetch(&manifest, &[])
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["default features", "json", "reqwest", "tls", "all features"]
+        );
+
+        let requested = commands::deps::feature_sets_to_fetch(&manifest, &["json,tls".to_string()]);
+        assert_eq!(
+            requested,
+            vec![(
+                "json,tls".to_string(),
+                vec!["--features".to_string(), "json,tls".to_string()]
+            )]
+        );
+
+        let tree = "demo v0.1.0 (/tmp/demo)\nanyhow v1.0.86\nserde_json v1.0.120\nanyhow v1.0.86\nitoa v1.0.11 (*)\n";
+        assert_eq!(
+            commands::deps::tree_packages(tree),
+            vec!["demo@0.1.0", "anyhow@1.0.86", "serde_json@1.0.120", "itoa@1.0.11"]
+        );
+    }
+
+    #[test]
+    fn test_history_build_latest() {
+        let run = |lto: &str, stripped: bool, size_bytes: u64| history::BuildRun {
+            timestamp: size_bytes,
+            git_hash: None,
+            lto: lto.to_string(),
+            stripped,
+            binary: "target/release/app".to_string(),
+            size_bytes,
+        };
+        let history = history::BuildHistory {
+            runs: vec![
+                run("off", false, 100),
+                run("off", true, 60),
+                run("off", false, 90),
+                run("fat", false, 70),
+            ],
+        };
+
+        let latest = |lto, stripped| {
+            history
+                .latest("target/release/app", lto, stripped)
+                .map(|run| run.size_bytes)
+        };
+        assert_eq!(latest("off", false), Some(90));
+        assert_eq!(latest("off", true), Some(60));
+        assert_eq!(latest("thin", false), None);
+        assert!(history.latest("target/release/other", "off", false).is_none());
+    }
+
+    #[test]
+    fn test_doctor_denies_unsafe_code() {
+        use commands::doctor::denies_unsafe_code;
+
+        assert!(denies_unsafe_code("//! Derive macros\n#![deny(unsafe_code)]\n"));
+        assert!(denies_unsafe_code("#![forbid(missing_docs, unsafe_code)]\n"));
+        assert!(!denies_unsafe_code("#![deny(missing_docs)]\nuse proc_macro::TokenStream;\n"));
+        assert!(!denies_unsafe_code("#![allow(unsafe_code)]\n"));
+        assert!(!denies_unsafe_code("// #![deny(unsafe_code)] would be nice\n"));
+    }
+
+    #[test]
+    fn test_lint_parse_clippy_lints() {
+        let help = "Lint checks provided by rustc:\n\n    name  default  meaning\n    ----  -------  -------\n    dead-code  warn     detects unused items\n\nLint checks loaded by this crate:\n\n                 name  default  meaning\n                 ----  -------  -------\n    clippy::absolute-paths  allow    checks for usage of an item without a `use` statement\n    clippy::needless-borrow  warn     taking a reference that is going to be automatically dereferenced\n\nLint groups loaded by this crate:\n\n    name  sub-lints\n    ----  ---------\n    clippy::all  clippy::needless-borrow\n    clippy::restriction  clippy::absolute-paths\n    clippy::style  clippy::needless-borrow\n";
+
+        let lints = commands::lint::parse_clippy_lints(help);
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints[0]["name"], "clippy::absolute_paths");
+        assert_eq!(lints[0]["level"], "allow");
+        assert_eq!(lints[0]["group"], "restriction");
+        assert_eq!(
+            lints[0]["description"],
+            "checks for usage of an item without a `use` statement"
+        );
+        assert_eq!(lints[1]["name"], "clippy::needless_borrow");
+        assert_eq!(lints[1]["level"], "warn");
+        assert_eq!(lints[1]["group"], "style");
+    }
+
+    #[test]
+    fn test_toolchain_from_path() {
+        use commands::toolchain::toolchain_from_path;
+        use std::path::Path;
+
+        assert_eq!(
+            toolchain_from_path(Path::new(
+                "/home/me/.rustup/toolchains/nightly-x86_64-unknown-linux-gnu/bin/rustfmt"
+            )),
+            Some("nightly-x86_64-unknown-linux-gnu".to_string())
+        );
+        assert_eq!(toolchain_from_path(Path::new("/usr/bin/rustfmt")), None);
+    }
+
+    #[test]
+    fn test_metrics_doc_coverage_percent() {
+        let report = concat!(
+            r#"{"src/lib.rs":{"total":3,"with_docs":2,"total_examples":3,"with_examples":0},"#,
+            r#""src/util.rs":{"total":1,"with_docs":1,"total_examples":1,"with_examples":0}}"#
+        );
+        assert_eq!(commands::metrics::doc_coverage_percent(report), Some(75.0));
+        assert_eq!(commands::metrics::doc_coverage_percent("{}"), None);
+        assert_eq!(commands::metrics::doc_coverage_percent("error"), None);
+    }
+
+    #[test]
+    fn test_init_set_manifest_license() {
+        let manifest = "[package]\nname = \"demo\"\nedition = \"2024\"\n\n[dependencies]\n";
+        assert_eq!(
+            commands::init::set_manifest_license(manifest, "MIT"),
+            "[package]\nname = \"demo\"\nedition = \"2024\"\nlicense = \"MIT\"\n\n[dependencies]\n"
+        );
+
+        let workspace = "[workspace.package]\nlicense = \"MIT OR Apache-2.0\"\n";
+        assert_eq!(
+            commands::init::set_manifest_license(workspace, "MPL-2.0"),
+            "[workspace.package]\nlicense = \"MPL-2.0\"\n"
+        );
+    }
+
+    #[test]
+    fn test_outdated_toolchains_parse_rustup_check() {
+        let output = concat!(
+            "stable-x86_64-unknown-linux-gnu - Update available : ",
+            "1.70.0 (90c541806 2023-05-31) -> 1.71.0 (8ede3aae2 2023-07-12)\n",
+            "nightly-x86_64-unknown-linux-gnu - Up to date : 1.75.0-nightly (2f1bd0729 2023-10-27)\n",
+            "rustup - Up to date : 1.26.0\n"
+        );
+        let toolchains = commands::outdated_toolchains::parse_rustup_check(output);
+        assert_eq!(toolchains.len(), 2);
+        assert_eq!(toolchains[0]["name"], "stable-x86_64-unknown-linux-gnu");
+        assert_eq!(toolchains[0]["up_to_date"], false);
+        assert_eq!(toolchains[0]["installed_version"], "1.70.0");
+        assert_eq!(toolchains[0]["installed_date"], "2023-05-31");
+        assert_eq!(toolchains[0]["latest_version"], "1.71.0");
+        assert_eq!(toolchains[1]["up_to_date"], true);
+        assert_eq!(toolchains[1]["latest_version"], "1.75.0-nightly");
+    }
+
+    #[test]
+    fn test_check_find_workspace_inconsistencies() {
+        let root: toml::Value = toml::from_str(
+            "[workspace.dependencies]\nserde = \"1\"\ntokio = { version = \"1.38\" }\n\
+             rand = \"0.8\"\n",
+        )
+        .unwrap();
+        let manifest: toml::Value = toml::from_str(
+            "[dependencies]\nserde = { version = \"^1.0.0\", features = [\"derive\"] }\n\
+             tokio = \"1.20\"\nanyhow = \"1\"\nrand = \"0.8.5\"\n\
+             [dev-dependencies]\ntokio = { workspace = true }\n",
+        )
+        .unwrap();
+        let members = vec![commands::workspace::WorkspaceMember {
+            name: "core".to_string(),
+            path: std::path::PathBuf::from("crates/core"),
+            version: None,
+            kind: "lib",
+            manifest,
+        }];
+
+        let inconsistencies = commands::check::find_workspace_inconsistencies(&root, &members);
+        assert_eq!(
+            inconsistencies,
+            vec![
+                serde_json::json!({
+                    "member": "core",
+                    "dep": "rand",
+                    "workspace_version": "0.8",
+                    "member_version": "0.8.5"
+                }),
+                serde_json::json!({
+                    "member": "core",
+                    "dep": "tokio",
+                    "workspace_version": "1.38",
+                    "member_version": "1.20"
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_cargo_config_settings() {
+        let config: toml::Value = toml::from_str(
+            "[build]\nrustflags = [\"-C\", \"target-cpu=native\"]\njobs = 4\n\
+             [net]\nretry = 3\n[source.crates-io]\nreplace-with = \"vendored\"\n",
+        )
+        .unwrap();
+        let settings = commands::env::cargo_config_settings(&config);
+        let setting = |key: &str| settings.iter().find(|s| s["key"] == key).unwrap().clone();
+
+        assert_eq!(settings.len(), 4);
+        assert_eq!(setting("build.rustflags")["value"], r#"["-C", "target-cpu=native"]"#);
+        assert!(setting("build.rustflags")["warning"].is_string());
+        assert!(setting("build.jobs")["warning"].is_null());
+        assert_eq!(setting("net.retry")["is_default"], true);
+        assert!(setting("net.retry")["warning"].is_null());
+        assert!(setting("source.crates-io.replace-with")["warning"].is_string());
+    }
+
+    #[test]
+    fn test_test_line_coverage_percent() {
+        let report = r#"{"data":[{"totals":{"lines":{"count":200,"covered":150,"percent":75.0}}}],
+            "type":"llvm.coverage.json.export","version":"2.0.1"}"#;
+        assert_eq!(commands::test::line_coverage_percent(report), Some(75.0));
+
+        let empty = r#"{"data":[{"totals":{"lines":{"count":0,"covered":0,"percent":0}}}]}"#;
+        assert_eq!(commands::test::line_coverage_percent(empty), Some(100.0));
+        assert_eq!(commands::test::line_coverage_percent("not json"), None);
+    }
+
+    #[test]
+    fn test_doctor_mount_for_path() {
+        let mounts = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+                      server:/home /home nfs4 rw,vers=4.2 0 0\n\
+                      /dev/sdb1 /home/me/local\\040disk ext4 rw 0 0\n";
+        let mount = |path: &str| {
+            commands::doctor::mount_for_path(mounts, std::path::Path::new(path))
+                .map(|(_, fs_type)| fs_type)
+        };
+        assert_eq!(mount("/home/me/.cargo/registry").as_deref(), Some("nfs4"));
+        assert_eq!(mount("/home/me/local disk/.cargo").as_deref(), Some("ext4"));
+        assert_eq!(mount("/opt/cargo").as_deref(), Some("ext4"));
+
+        assert!(commands::doctor::is_network_filesystem("nfs4"));
+        assert!(commands::doctor::is_network_filesystem("fuse.sshfs"));
+        assert!(!commands::doctor::is_network_filesystem("ext4"));
+    }
+
+    #[test]
+    fn test_publish_check_helpers() {
+        let files = ["Cargo.toml", "src/lib.rs", ".env", ".env.example", "certs/server.pem",
+            "keys/signing.key", "src/keyboard.rs"];
+        assert_eq!(
+            commands::publish_check::secret_files(files.into_iter()),
+            vec![".env", "certs/server.pem", "keys/signing.key"]
+        );
+
+        assert!(commands::publish_check::is_docs_rs_url("https://docs.rs/serde", "serde"));
+        assert!(commands::publish_check::is_docs_rs_url(
+            "https://docs.rs/serde/latest/serde/",
+            "serde"
+        ));
+        assert!(!commands::publish_check::is_docs_rs_url("https://docs.rs/tokio", "serde"));
+        assert!(!commands::publish_check::is_docs_rs_url("https://serde.rs", "serde"));
+    }
+
+    #[test]
+    fn test_deps_feature_combinations() {
+        let features: Vec<String> = ["a", "b", "c"].iter().map(|f| f.to_string()).collect();
+        let all = commands::deps::feature_combinations(&features, 16);
+        assert_eq!(all.len(), 8);
+        assert!(all[0].is_empty());
+        assert_eq!(all[1], vec!["a"]);
+        assert_eq!(all[4], vec!["a", "b"]);
+        assert_eq!(all[7], vec!["a", "b", "c"]);
+
+        let capped = commands::deps::feature_combinations(&features, 5);
+        assert_eq!(capped.len(), 5);
+        assert_eq!(capped[4], vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_check_quality_score() {
+        let weights = config::ScoringConfig::default();
+        let score = commands::check::quality_score;
+        assert_eq!(score(Some(true), Some((true, 0)), Some(true), &weights), Some(100));
+        // 40 - 2 * 4 warnings = 32 of 40 for clippy
+        assert_eq!(score(Some(true), Some((false, 4)), Some(true), &weights), Some(92));
+        assert_eq!(score(Some(false), Some((false, 0)), Some(true), &weights), Some(30));
+        // Skipped steps don't count: 30 of 60
+        assert_eq!(score(Some(false), None, Some(true), &weights), Some(50));
+        assert_eq!(score(None, None, None, &weights), None);
+    }
+
+    #[test]
+    fn test_toolchain_purge_candidates() {
+        let list = "stable-x86_64-unknown-linux-gnu (default)\n\
+                    beta-x86_64-unknown-linux-gnu\n\
+                    nightly-x86_64-unknown-linux-gnu (active)\n\
+                    1.70-x86_64-unknown-linux-gnu\n\
+                    1.75-x86_64-unknown-linux-gnu\n\
+                    my-local\n";
+        let overrides = vec!["my-local".to_string()];
+        let keep = vec!["1.70".to_string()];
+        assert_eq!(
+            commands::toolchain::purge_candidates(list, &overrides, &keep),
+            vec!["beta-x86_64-unknown-linux-gnu", "1.75-x86_64-unknown-linux-gnu"]
+        );
+    }
+
+    #[test]
+    fn test_deps_proc_macro_subtrees() {
+        let tree = "app v0.1.0 (/app)\n\
+                    ├── anyhow v1.0.0 default\n\
+                    ├── clap v4.0.0 default\n\
+                    │   └── clap_derive v4.0.0 (proc-macro) default\n\
+                    │       └── syn v2.0.0 full\n\
+                    └── serde v1.0.0 default\n";
+        assert_eq!(
+            commands::deps::proc_macro_subtrees(tree),
+            "app v0.1.0 (/app)\n\
+             ├── clap v4.0.0 default\n\
+             │   └── clap_derive v4.0.0 (proc-macro) default\n\
+             │       └── syn v2.0.0 full\n"
+        );
+        let types = vec!["proc-macro".to_string(), "normal".to_string()];
+        assert_eq!(commands::deps::crate_type_edges(&types).as_deref(), Some("normal"));
+        assert_eq!(commands::deps::crate_type_edges(&types[..1]), None);
+    }
+
+    #[test]
+    fn test_config_unknown_keys() {
+        let value: toml::Value = toml::from_str(
+            "[build]\nrelease_by_defualt = true\ntarget_dir = \"out\"\n\
+             [check.scoring]\nfmt_weight = 10\nlint_weight = 5\n[colour]\nenabled = true\n",
+        )
+        .unwrap();
+        let mut unknown = config::unknown_keys(&value).unwrap();
+        unknown.sort();
+        assert_eq!(
+            unknown,
+            vec!["build.release_by_defualt", "check.scoring.lint_weight", "colour"]
+        );
+
+        let wrong_type: toml::Value = toml::from_str("[check]\nno_fmt = \"yes\"\n").unwrap();
+        assert!(config::unknown_keys(&wrong_type).is_err());
+    }
+
+    #[test]
+    fn test_info_freshness_summary() {
+        assert_eq!(commands::sbom::iso8601_days("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            commands::sbom::iso8601_days("2024-01-15T12:34:56.789012+00:00"),
+            Some(19_737)
+        );
+        assert_eq!(commands::sbom::iso8601_days("2024-13-01"), None);
+
+        let ages = vec![
+            ("anyhow".to_string(), "1.0.0".to_string(), 10),
+            ("serde".to_string(), "1.0.0".to_string(), 312),
+            ("toml".to_string(), "0.8.0".to_string(), 30),
+        ];
+        let summary = commands::info::freshness_summary(&ages);
+        assert_eq!(summary["mean_age_days"], 117);
+        assert_eq!(summary["max_age_days"], 312);
+        assert_eq!(summary["oldest"]["name"], "serde");
+        assert!(commands::info::freshness_summary(&[]).is_null());
+    }
+
+    #[test]
+    fn test_env_report_html() {
+        let stdout = "2026-01-01T00:00:00Z  INFO oxygen: Starting Oxygen CLI\n\
+                      {\n  \"checks\": [{\"name\": \"<rustc>\", \"status\": \"ok\"}]\n}\n";
+        let doctor = commands::env::json_document(stdout).unwrap();
+        assert_eq!(doctor["checks"][0]["status"], "ok");
+        assert!(commands::env::json_document("INFO no json here\n").is_none());
+
+        let html = commands::env::report_html(
+            &[("Diagnostics", doctor), ("Tools", serde_json::Value::Null)],
+            "2026-01-01T00:00:00Z",
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<td>&lt;rustc&gt;</td><td class=\"ok\">ok</td>"));
+        assert!(html.contains("<h2>Tools</h2>\n<p class=\"missing\">Not available</p>"));
+    }
+
+    #[test]
+    fn test_check_clippy_lint_warnings() {
+        let message = |code: &str, line: u64| {
+            serde_json::json!({
+                "reason": "compiler-message",
+                "message": {
+                    "message": "unneeded `return` statement",
+                    "level": "error",
+                    "code": { "code": code },
+                    "spans": [
+                        { "file_name": "src/lib.rs", "line_start": 1, "is_primary": false },
+                        { "file_name": "src/main.rs", "line_start": line, "is_primary": true }
+                    ]
+                }
+            })
+        };
+        let messages = vec![
+            message("clippy::needless_return", 42),
+            // The same module compiled into both the lib and the bin
+            message("clippy::needless_return", 42),
+            message("E0308", 7),
+            serde_json::json!({ "reason": "compiler-message", "message": { "code": null } }),
+        ];
+        let warnings = commands::check::clippy_lint_warnings(&messages);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0]["lint"], "clippy::needless_return");
+        assert_eq!(warnings[0]["file"], "src/main.rs");
+        assert_eq!(warnings[0]["line"], 42);
+    }
+
+    #[test]
+    fn test_deps_updated_packages() {
+        let package = |name: &str, version: &str| utils::LockedPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+            dependencies: Vec::new(),
+        };
+        let before = vec![
+            package("time", "0.1.43"),
+            package("syn", "1.0.109"),
+            package("syn", "2.0.0"),
+            package("old", "1.0.0"),
+        ];
+        let after = vec![
+            package("time", "0.3.36"),
+            package("syn", "1.0.109"),
+            package("syn", "2.0.1"),
+            package("new", "1.0.0"),
+        ];
+        let updated = commands::deps::updated_packages(&before, &after);
+        let summary: Vec<_> = updated
+            .iter()
+            .map(|p| (&p["name"], &p["from_version"], &p["to_version"]))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (&"time".into(), &"0.1.43".into(), &"0.3.36".into()),
+                (&"syn".into(), &"2.0.0".into(), &"2.0.1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_sccache_build_stats() {
+        let snapshot = |hits: u64, misses: u64| {
+            serde_json::json!({
+                "stats": {
+                    "cache_hits": { "counts": { "Rust": hits, "C/C++": 0 } },
+                    "cache_misses": { "counts": { "Rust": misses } }
+                },
+                "cache_size": 1_048_576
+            })
+        };
+        let stats =
+            commands::build::sccache_build_stats(Some(&snapshot(10, 5)), &snapshot(138, 50))
+                .unwrap();
+        assert_eq!(stats["hits"], 128);
+        assert_eq!(stats["misses"], 45);
+        assert_eq!(stats["hit_rate_pct"], 74.0);
+        assert_eq!(stats["cache_size_bytes"], 1_048_576);
+
+        // Older sccache versions report plain totals, and a restarted server resets them
+        let old = serde_json::json!({ "stats": { "cache_hits": 3, "cache_misses": 1 } });
+        let stats = commands::build::sccache_build_stats(Some(&snapshot(10, 5)), &old).unwrap();
+        assert_eq!((stats["hits"].as_u64(), stats["misses"].as_u64()), (Some(3), Some(1)));
+        assert!(commands::build::sccache_build_stats(None, &serde_json::json!({})).is_none());
+    }
+
+    #[test]
+    fn test_doctor_extern_crate_declarations() {
+        let source = "#![no_std]\n\
+                      extern crate alloc;\n\
+                      #[macro_use]\n\
+                      extern crate serde_derive;\n\
+                      pub extern crate rand as random;\n\
+                      // extern crate commented;\n\
+                      extern crate self as my_crate;\n\
+                      #[cfg(test)]\n\
+                      extern crate quickcheck;\n\
+                      #[cfg(test)]\n\
+                      mod tests {\n\
+                          extern crate proptest;\n\
+                      }\n\
+                      extern crate log;\n";
+        let lines: Vec<usize> = commands::doctor::extern_crate_declarations(source)
+            .into_iter()
+            .map(|(line, _)| line)
+            .collect();
+        assert_eq!(lines, vec![4, 5, 14]);
+    }
+
+    #[test]
+    fn test_bench_parse_bench_output() {
+        let stdout = "running 2 tests\n\
+test parse::small ... bench:       1,234 ns/iter (+/- 56)\n\
+test parse::large ... bench:   2,000,000 ns/iter (+/- 1,000)\n\
+\n\
+fib 20                  time:   [25.1 µs 25.3 µs 25.6 µs]\n\
+a/very/long/criterion/benchmark/name\n\
+                        time:   [1.5000 ms 2.0000 ms 2.5000 ms]\n\
+                        change: [-1.2% +0.3% +1.9%] (p = 0.71 > 0.05)\n";
+        let benchmarks = commands::bench::parse_bench_output(stdout);
+        assert_eq!(benchmarks.len(), 4);
+        assert_eq!(benchmarks["parse::small"], 1234.0);
+        assert_eq!(benchmarks["parse::large"], 2_000_000.0);
+        assert!((benchmarks["fib 20"] - 25_300.0).abs() < 1e-6);
+        assert_eq!(benchmarks["a/very/long/criterion/benchmark/name"], 2_000_000.0);
+
+        let baseline = std::collections::BTreeMap::from([("fib 20".to_string(), 20_000.0)]);
+        let comparisons = commands::bench::compare_benchmarks(&baseline, &benchmarks);
+        let fib = comparisons.iter().find(|c| c["name"] == "fib 20").unwrap();
+        assert_eq!(fib["delta_pct"], 26.5);
+        let new = comparisons.iter().find(|c| c["name"] == "parse::small").unwrap();
+        assert!(new["delta_pct"].is_null());
+    }
+
+    #[test]
+    fn test_info_cargo_targets() {
+        let manifest: toml::Value = r#"
+            [package]
+            name = "multi"
+
+            [[bin]]
+            name = "server"
+            path = "src/server.rs"
+            required-features = ["net"]
+
+            [[bin]]
+            name = "worker"
+
+            [[example]]
+            name = "demo"
+        "#
+        .parse()
+        .unwrap();
+        let root = std::path::Path::new("/nonexistent-oxygen-project");
+
+        let bins = commands::info::cargo_targets(&manifest, "bin", root);
+        assert_eq!(bins.len(), 2);
+        assert_eq!(bins[0]["path"], "src/server.rs");
+        assert_eq!(bins[0]["required_features"], serde_json::json!(["net"]));
+        assert_eq!(bins[1]["path"], "src/bin/worker.rs");
+
+        let examples = commands::info::cargo_targets(&manifest, "example", root);
+        assert_eq!(examples[0]["name"], "demo");
+        assert_eq!(examples[0]["path"], "examples/demo.rs");
+    }
+
+    #[test]
+    fn test_deps_supply_chain_publishers() {
+        let report = serde_json::json!({
+            "not_audited": { "no_published_versions": [], "not_on_crates_io": ["app"] },
+            "crates_publishers": {
+                "serde": {
+                    "users": [{ "id": 1, "login": "dtolnay" }],
+                    "teams": [{ "id": 2, "login": "github:serde-rs:publish" }]
+                },
+                "itoa": [{ "id": 1, "login": "dtolnay", "kind": "user" }]
+            }
+        });
+        let publishers = commands::deps::supply_chain_publishers(&report);
+        assert_eq!(publishers.len(), 2);
+        assert_eq!(publishers["serde"].len(), 2);
+        assert_eq!(publishers["serde"][1]["kind"], "team");
+        assert_eq!(publishers["itoa"].len(), 1);
+    }
+
+    #[test]
+    fn test_check_use_statements() {
+        let source = "use std::fs;\npub(crate) use crate::utils::{\n    output_json,\n    output_text,\n};\n\nfn main() {\n    let used = 1;\n}\n";
+        assert_eq!(
+            commands::check::use_statements(source),
+            vec!["usestd::fs;", "pub(crate)usecrate::utils::{output_json,output_text,};"]
+        );
+        let rewrapped = "use std::fs;\npub(crate) use crate::utils::{output_json, output_text,};\n";
+        assert_eq!(
+            commands::check::use_statements(rewrapped),
+            commands::check::use_statements(source)
+        );
+    }
+
+    #[test]
+    fn test_check_panic_strategy_warning() {
+        let config: toml::Value = "[build]\ntarget = \"thumbv7em-none-eabihf\"\n".parse().unwrap();
+        let explicit: toml::Value = "[profile.release]\npanic = \"unwind\"\n".parse().unwrap();
+        let warning =
+            commands::check::panic_strategy_warning(&explicit, std::slice::from_ref(&config))
+                .unwrap();
+        assert_eq!(warning["status"], "warning");
+        assert_eq!(warning["panic"], "unwind");
+        assert_eq!(warning["targets"], serde_json::json!(["thumbv7em-none-eabihf"]));
+        assert!(warning["message"].as_str().unwrap().contains("fail to link"));
+
+        let unset: toml::Value = "[package]\nname = \"fw\"\n".parse().unwrap();
+        assert!(
+            commands::check::panic_strategy_warning(&unset, std::slice::from_ref(&config))
+                .is_none()
+        );
+        let abort: toml::Value = "[profile.release]\npanic = \"abort\"\n".parse().unwrap();
+        assert!(commands::check::panic_strategy_warning(&abort, &[config]).is_none());
+
+        // A [target.<triple>] table doesn't make that triple the one being built
+        let table: toml::Value =
+            "[target.thumbv7em-none-eabihf]\nrunner = \"probe-rs run\"\n".parse().unwrap();
+        assert!(commands::check::panic_strategy_warning(&explicit, &[table]).is_none());
+        let hosted: toml::Value =
+            "[build]\ntarget = \"x86_64-unknown-linux-gnu\"\n".parse().unwrap();
+        assert!(commands::check::panic_strategy_warning(&explicit, &[hosted]).is_none());
+    }
+
+    #[test]
+    fn test_deps_set_patch() {
+        let mut manifest: toml_edit::DocumentMut =
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\n".parse().unwrap();
+        commands::deps::set_patch(&mut manifest, "serde", "../serde");
+        let written = manifest.to_string();
+        assert!(written.contains("[patch.crates-io]\nserde = { path = \"../serde\" }"));
+        assert!(!written.contains("[patch]\n"));
+
+        let parsed: toml::Value = written.parse().unwrap();
+        let patches = commands::deps::manifest_patches(&parsed);
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0]["crate_name"], "serde");
+        assert_eq!(patches[0]["source"], "../serde");
+
+        assert!(commands::deps::unset_patch(&mut manifest, "serde"));
+        assert!(!commands::deps::unset_patch(&mut manifest, "serde"));
+        assert!(!manifest.to_string().contains("patch"));
+    }
+
+    #[test]
+    fn test_flamegraph_diff_frame_deltas() {
+        let baseline = "main;parse 10\nmain;parse;alloc 5\nmain;render 20\n";
+        let current = "main;parse 30\nmain;parse;alloc 5\nmain;render 12\nmain;log 3\n";
+        let deltas = commands::flamegraph_diff::frame_deltas(baseline, current);
+        assert_eq!(
+            deltas,
+            vec![
+                ("parse".to_string(), 20),
+                ("log".to_string(), 3),
+                ("render".to_string(), -8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toolchain_parse_toolchain_file() {
+        let file = "[toolchain]\n\
+            channel = \"nightly-2024-05-01\"\n\
+            components = [\"rustfmt\", \"rust-src\", \"miri\"]\n";
+        let (channel, components) = commands::toolchain::parse_toolchain_file(file).unwrap();
+        assert_eq!(channel, "nightly-2024-05-01");
+        assert_eq!(
+            commands::toolchain::parse_toolchain_file("1.75.0\n"),
+            Some(("1.75.0".to_string(), vec![]))
+        );
+
+        let list = "stable-x86_64-unknown-linux-gnu (default)\n\
+            nightly-2024-05-01-x86_64-unknown-linux-gnu\n";
+        assert_eq!(
+            commands::toolchain::installed_toolchain(list, &channel, None).as_deref(),
+            Some("nightly-2024-05-01-x86_64-unknown-linux-gnu")
+        );
+        assert!(commands::toolchain::installed_toolchain(list, "beta", None).is_none());
+
+        let installed =
+            "cargo-x86_64-unknown-linux-gnu\nrust-src\nrustfmt-x86_64-unknown-linux-gnu\n";
+        assert_eq!(
+            commands::toolchain::missing_components(installed, &components),
+            vec!["miri".to_string()]
+        );
+    }
+}
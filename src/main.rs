@@ -1,11 +1,13 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use clap::{CommandFactory, Parser, Subcommand};
 use tracing::{Level, info};
 use tracing_subscriber::fmt;
-use oxygen::{ToolchainAction, DepsAction, GpgAction};
+use oxygen::{ToolchainAction, TargetAction, CompletionsAction, DepsAction, EnvAction, FmtAction, GhAction, GpgAction, ManifestAction, VetAction, GenerateKind, SbomFormat, CiAction, CiPlatform, WorkspaceAction, LtoMode, LintAction, TestAction, CoverageFormat, EmitFormat, ConfigAction, BenchAction, BaselineAction, PatchAction};
 
 mod commands;
 mod config;
+mod history;
 mod utils;
 
 #[derive(Parser)]
@@ -26,17 +28,177 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run clippy, fmt, and check in sequence
-    Check,
+    Check {
+        /// Skip cargo fmt --check
+        #[arg(long)]
+        no_fmt: bool,
+        /// Skip cargo clippy
+        #[arg(long)]
+        no_clippy: bool,
+        /// Skip cargo check
+        #[arg(long)]
+        no_check: bool,
+        /// Group and merge use declarations with rustfmt before checking (needs nightly rustfmt)
+        #[arg(long)]
+        fix_imports: bool,
+        /// Also run `cargo +nightly check` to catch upcoming breakage (doesn't affect the result)
+        #[arg(long)]
+        check_nightly: bool,
+        /// Install the nightly toolchain without prompting if it's missing
+        #[arg(short = 'y', long, requires = "check_nightly")]
+        yes: bool,
+        /// Don't warn about uncommitted changes in the working tree
+        #[arg(long)]
+        allow_dirty: bool,
+        /// Check each package and `--target <triple>` listed in a file, one per line
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["fix_imports", "check_nightly"])]
+        targets_file: Option<PathBuf>,
+        /// Check every workspace member, not just the default members
+        #[arg(long, conflicts_with = "targets_file")]
+        workspace: bool,
+    },
+    /// Show the pass/fail and warning trend of past `oxy check` runs
+    CheckHistory {
+        /// Only show the most recent N runs
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Save benchmark baselines and compare against them
+    Bench {
+        #[command(subcommand)]
+        action: BenchAction,
+    },
     /// Build with enhanced timing and size summaries
-    Build,
+    Build {
+        /// Build only the named binary
+        #[arg(long, group = "build_target")]
+        bin: Option<String>,
+        /// Build only the named example
+        #[arg(long, group = "build_target")]
+        example: Option<String>,
+        /// Build only the library
+        #[arg(long, group = "build_target")]
+        lib: bool,
+        /// Build all binaries
+        #[arg(long, group = "build_target")]
+        bins: bool,
+        /// Build all examples
+        #[arg(long, group = "build_target")]
+        examples: bool,
+        /// Build every workspace member
+        #[arg(long, group = "build_target")]
+        workspace: bool,
+        /// Strip debug symbols from the built binaries
+        #[arg(long)]
+        strip: bool,
+        /// Override the release profile's LTO setting and compare the size to a non-LTO build
+        #[arg(long, value_enum, value_name = "MODE")]
+        lto: Option<LtoMode>,
+        /// Also write assembly, LLVM IR/bitcode or MIR for the project's crates
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        emit: Option<EmitFormat>,
+        /// Write cargo's HTML timing report and rank the slowest units from it
+        #[arg(long)]
+        timings: bool,
+        /// Build the package at this Cargo.toml instead of the current directory's
+        #[arg(long, value_name = "PATH")]
+        manifest_path: Option<PathBuf>,
+    },
+    /// Generate CI configuration that runs oxy
+    Ci {
+        #[command(subcommand)]
+        action: CiAction,
+    },
+    /// Count the code generated by build scripts and macros
+    Codegen {
+        /// Write the macro-expanded crate source to target/expanded.rs (needs cargo-expand)
+        #[arg(long)]
+        expand: bool,
+    },
+    /// Print or install shell completion scripts
+    #[command(args_conflicts_with_subcommands = true)]
+    Completions {
+        #[command(subcommand)]
+        action: Option<CompletionsAction>,
+        /// Shell to print completions for
+        shell: Option<clap_complete::Shell>,
+    },
+    /// Validate oxygen's configuration files
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Compare code quality metrics between a git ref and the working tree
+    Diff {
+        /// Git ref to compare against
+        base_ref: String,
+        /// Metrics to compare: loc, deps, warnings, binary_size, test_count
+        #[arg(long, value_delimiter = ',')]
+        metrics: Vec<String>,
+    },
     /// Diagnose environment and tool issues
     Doctor,
     /// Show current Rust environment information
-    Env,
+    Env {
+        #[command(subcommand)]
+        action: Option<EnvAction>,
+        /// Show the RUSTFLAGS rustc actually receives, and where each source sets them
+        #[arg(long)]
+        effective_rustflags: bool,
+    },
+    /// Explain a rustc error code, e.g. E0502
+    Explain {
+        /// Error code (E0502, e0502 or 502)
+        code: String,
+    },
+    /// Compare two folded-stack profiles as a differential flamegraph (needs inferno)
+    FlamegraphDiff {
+        /// Folded stacks of the baseline run
+        baseline: PathBuf,
+        /// Folded stacks of the run to compare
+        current: PathBuf,
+        /// Where to write the differential SVG (defaults to flamegraph-diff.svg)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Install git hooks that run oxy check before commits and pushes
+    InstallHooks {
+        /// Install the pre-commit hook (oxy check --no-clippy)
+        #[arg(long)]
+        pre_commit: bool,
+        /// Install the pre-push hook (oxy check)
+        #[arg(long)]
+        pre_push: bool,
+        /// Overwrite existing hooks instead of chaining them
+        #[arg(long)]
+        force: bool,
+    },
     /// Show project metadata and git status
-    Info,
+    Info {
+        /// Show what changed in Cargo.toml since the given git ref
+        #[arg(long, value_name = "GIT_REF", conflicts_with_all = ["debt_scan", "deprecated_scan"])]
+        diff: Option<String>,
+        /// Count todo!(), FIXME, unimplemented!(), unwrap() and expect() across the sources
+        #[arg(long, conflicts_with = "deprecated_scan")]
+        debt_scan: bool,
+        /// List #[deprecated] items with their `since` and `note` fields
+        #[arg(long)]
+        deprecated_scan: bool,
+        /// Count the public structs, enums, traits, functions, types and consts of the library
+        #[arg(long, conflicts_with_all = ["diff", "debt_scan", "deprecated_scan"])]
+        api_surface: bool,
+    },
     /// List installed Rust development tools
-    Tools,
+    Tools {
+        /// Install any missing tools that can be installed with cargo or rustup
+        #[arg(long)]
+        install_missing: bool,
+    },
+    /// Manage rustup compilation targets
+    Target {
+        #[command(subcommand)]
+        action: TargetAction,
+    },
     /// Manage Rust toolchains and versions
     Toolchain {
         #[command(subcommand)]
@@ -52,17 +214,134 @@ pub enum Commands {
         /// List available templates
         #[arg(long)]
         list_templates: bool,
+        /// Create a workspace with per-crate GitHub Actions CI (same as --template monorepo)
+        #[arg(long, conflicts_with_all = ["template", "from_template_url"])]
+        monorepo: bool,
+        /// Rust edition for the generated Cargo.toml (2018, 2021 or 2024)
+        #[arg(long)]
+        edition: Option<String>,
+        /// Download a template archive (.tar.gz or .zip) from a URL
+        #[arg(long, value_name = "URL", conflicts_with = "template")]
+        from_template_url: Option<String>,
+        /// Username for downloading the template archive. The password is read from
+        /// OXYGEN_TEMPLATE_PASSWORD, or else prompted for (or read from piped stdin)
+        #[arg(long, requires = "from_template_url")]
+        user: Option<String>,
+        /// Write a LICENSE file and set it in Cargo.toml (MIT, Apache-2.0, GPL-3.0-only,
+        /// GPL-3.0-or-later, MPL-2.0, BSD-2-Clause or BSD-3-Clause)
+        #[arg(long, value_name = "SPDX")]
+        license: Option<String>,
     },
     /// Analyze and manage dependencies
     Deps {
         #[command(subcommand)]
         action: DepsAction,
     },
+    /// Formatting helpers built on rustfmt
+    Fmt {
+        #[command(subcommand)]
+        action: FmtAction,
+    },
+    /// GitHub operations using the gh CLI
+    Gh {
+        #[command(subcommand)]
+        action: GhAction,
+    },
     /// GPG signing and verification
     Gpg {
         #[command(subcommand)]
         action: GpgAction,
     },
+    /// Generate reports and files for the project
+    #[command(visible_alias = "gen")]
+    Generate {
+        #[command(subcommand)]
+        what: GenerateKind,
+    },
+    /// Explore clippy lints
+    Lint {
+        #[command(subcommand)]
+        action: LintAction,
+    },
+    /// Read, validate and format Cargo.toml
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestAction,
+    },
+    /// Show lines of code, dependencies, tests, warnings, doc coverage and binary size
+    Metrics,
+    /// Show which installed toolchains have updates available (from rustup check)
+    OutdatedToolchains {
+        /// Update the outdated toolchains right away
+        #[arg(long)]
+        update: bool,
+    },
+    /// Profile compile times with cargo build --timings
+    Perf {
+        /// Open the HTML timing report in a browser
+        #[arg(long)]
+        open: bool,
+    },
+    /// Scan the sources for common performance anti-patterns
+    PerfHint,
+    /// Check the crate is ready for crates.io without publishing it
+    PublishCheck,
+    /// Generate a Software Bill of Materials for the dependency tree
+    Sbom {
+        /// SBOM document format
+        #[arg(long, value_enum, default_value = "spdx")]
+        format: SbomFormat,
+        /// File to write the SBOM to
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Build, sign and checksum a release binary into a release directory
+    SignRelease {
+        /// Release version (defaults to package.version)
+        version: Option<String>,
+        /// Directory to collect release artifacts in [default: release]
+        #[arg(long)]
+        output_dir: Option<PathBuf>,
+        /// Create a GPG-signed v<version> git tag
+        #[arg(long)]
+        tag: bool,
+    },
+    /// Test helpers for CI
+    Test {
+        #[command(subcommand)]
+        action: TestAction,
+    },
+    /// Run a cargo command with RUST_LOG set and highlight its log output
+    Trace {
+        /// Value for RUST_LOG, e.g. debug or my_crate=trace
+        #[arg(long, default_value = "debug")]
+        log_level: String,
+        /// Also set RUST_BACKTRACE=full
+        #[arg(long)]
+        backtrace: bool,
+        /// Cargo command and arguments, e.g. `run -- --port 8080`
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Check that the release build is reproducible against another git ref
+    Verify {
+        /// Git ref to compare against (defaults to the latest tag)
+        #[arg(long, value_name = "GIT_REF")]
+        compare_to: Option<String>,
+        /// Binary to compare (defaults to the package's only binary or its default-run)
+        #[arg(long, value_name = "NAME")]
+        bin: Option<String>,
+    },
+    /// Supply chain audits with cargo-vet
+    Vet {
+        #[command(subcommand)]
+        action: VetAction,
+    },
+    /// Run commands across all members of a Cargo workspace
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
 }
 
 
@@ -81,18 +360,146 @@ async fn main() -> Result<()> {
     info!("Starting Oxygen CLI");
 
     match cli.command {
-        Commands::Check => commands::check::run(cli.json).await?,
-        Commands::Build => commands::build::run(cli.json).await?,
+        Commands::Check {
+            no_fmt,
+            no_clippy,
+            no_check,
+            fix_imports,
+            check_nightly,
+            yes,
+            allow_dirty,
+            targets_file,
+            workspace,
+        } => {
+            let options = commands::check::CheckOptions {
+                no_fmt,
+                no_clippy,
+                no_check,
+                fix_imports,
+                check_nightly,
+                yes,
+                allow_dirty,
+                targets_file,
+                workspace,
+            };
+            commands::check::run(options, cli.json).await?
+        }
+        Commands::CheckHistory { limit } => commands::check::show_history(limit, cli.json).await?,
+        Commands::Bench { action } => commands::bench::run(action, cli.json).await?,
+        Commands::Build {
+            bin,
+            example,
+            lib,
+            bins,
+            examples,
+            workspace,
+            strip,
+            lto,
+            emit,
+            timings,
+            manifest_path,
+        } => {
+            let options = commands::build::BuildOptions {
+                target: commands::build::BuildTarget::from_flags(
+                    bin, example, lib, bins, examples, workspace,
+                ),
+                strip,
+                lto,
+                emit,
+                timings,
+                verbose: cli.verbose,
+                manifest_path,
+            };
+            commands::build::run(options, cli.json).await?
+        }
+        Commands::Ci { action } => commands::ci::run(action, cli.json).await?,
+        Commands::Codegen { expand } => commands::codegen::run(expand, cli.json).await?,
+        Commands::Completions { action, shell } => {
+            commands::completions::run(action, shell, Cli::command(), cli.json).await?
+        }
+        Commands::Config { action } => commands::config::run(action, cli.json).await?,
+        Commands::Diff { base_ref, metrics } => {
+            commands::diff::run(&base_ref, metrics, cli.json).await?
+        }
         Commands::Doctor => commands::doctor::run(cli.json).await?,
-        Commands::Env => commands::env::run(cli.json).await?,
-        Commands::Info => commands::info::run(cli.json).await?,
-        Commands::Tools => commands::tools::run(cli.json).await?,
+        Commands::Env {
+            action,
+            effective_rustflags,
+        } => commands::env::run(action, effective_rustflags, cli.json).await?,
+        Commands::Explain { code } => commands::explain::run(&code, cli.json).await?,
+        Commands::FlamegraphDiff {
+            baseline,
+            current,
+            output,
+        } => commands::flamegraph_diff::run(&baseline, &current, output, cli.json).await?,
+        Commands::InstallHooks {
+            pre_commit,
+            pre_push,
+            force,
+        } => commands::hooks::run(pre_commit, pre_push, force, cli.json).await?,
+        Commands::Info {
+            diff,
+            debt_scan,
+            deprecated_scan,
+            api_surface,
+        } => {
+            commands::info::run(diff, debt_scan, deprecated_scan, api_surface, cli.json).await?
+        }
+        Commands::Tools { install_missing } => commands::tools::run(install_missing, cli.json).await?,
+        Commands::Target { action } => commands::target::run(action, cli.json).await?,
         Commands::Toolchain { action } => commands::toolchain::run(action, cli.json).await?,
-        Commands::Init { name, template, list_templates } => {
-            commands::init::run(name, template, list_templates, cli.json).await?
-        },
+        Commands::Init {
+            name,
+            template,
+            list_templates,
+            monorepo,
+            edition,
+            from_template_url,
+            user,
+            license,
+        } => {
+            let options = commands::init::InitOptions {
+                name,
+                template: template.or_else(|| monorepo.then(|| "monorepo".to_string())),
+                list_templates,
+                edition,
+                template_url: from_template_url,
+                user,
+                license,
+            };
+            commands::init::run(options, cli.json).await?
+        }
         Commands::Deps { action } => commands::deps::run(action, cli.json).await?,
+        Commands::Fmt { action } => commands::fmt::run(action, cli.json).await?,
+        Commands::Gh { action } => commands::gh::run(action, cli.json).await?,
         Commands::Gpg { action } => commands::gpg::run(action, cli.json).await?,
+        Commands::Generate { what } => commands::generate::run(what, cli.json).await?,
+        Commands::Lint { action } => commands::lint::run(action, cli.json).await?,
+        Commands::Manifest { action } => commands::manifest::run(action, cli.json).await?,
+        Commands::Metrics => commands::metrics::run(cli.json).await?,
+        Commands::OutdatedToolchains { update } => {
+            commands::outdated_toolchains::run(update, cli.json).await?
+        }
+        Commands::Perf { open } => commands::perf::run(open, cli.json).await?,
+        Commands::PerfHint => commands::perf_hint::run(cli.json).await?,
+        Commands::PublishCheck => commands::publish_check::run(cli.json).await?,
+        Commands::Sbom { format, output } => commands::sbom::run(format, output, cli.json).await?,
+        Commands::SignRelease {
+            version,
+            output_dir,
+            tag,
+        } => commands::sign_release::run(version, output_dir, tag, cli.json).await?,
+        Commands::Test { action } => commands::test::run(action, cli.json).await?,
+        Commands::Trace {
+            log_level,
+            backtrace,
+            command,
+        } => commands::trace::run(&log_level, backtrace, command, cli.json).await?,
+        Commands::Verify { compare_to, bin } => {
+            commands::verify::run(compare_to, bin, cli.json).await?
+        }
+        Commands::Vet { action } => commands::vet::run(action, cli.json).await?,
+        Commands::Workspace { action } => commands::workspace::run(action, cli.json).await?,
     }
 
     Ok(())
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use serde_json::Value;
+use std::path::PathBuf;
 use std::process::Command;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::info;
 
 pub fn run_command(cmd: &str, args: &[&str]) -> Result<std::process::Output> {
@@ -13,6 +15,79 @@ pub fn run_command(cmd: &str, args: &[&str]) -> Result<std::process::Output> {
         .with_context(|| format!("Failed to execute command: {} {}", cmd, args.join(" ")))
 }
 
+/// Run a command with inherited stdio so the user can interact with it.
+pub fn run_command_interactive(cmd: &str, args: &[&str]) -> Result<std::process::ExitStatus> {
+    info!("Running interactive command: {} {}", cmd, args.join(" "));
+
+    Command::new(cmd)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to execute command: {} {}", cmd, args.join(" ")))
+}
+
+/// Run a command in `dir` with extra environment variables.
+pub fn run_command_in(
+    cmd: &str,
+    args: &[&str],
+    dir: &std::path::Path,
+    envs: &[(&str, &std::ffi::OsStr)],
+) -> Result<std::process::Output> {
+    info!(
+        "Running command in {}: {} {}",
+        dir.display(),
+        cmd,
+        args.join(" ")
+    );
+
+    Command::new(cmd)
+        .args(args)
+        .current_dir(dir)
+        .envs(envs.iter().copied())
+        .output()
+        .with_context(|| format!("Failed to execute command: {} {}", cmd, args.join(" ")))
+}
+
+/// Messages with the given `reason` from cargo's `--message-format=json` output.
+pub fn cargo_messages(output: &std::process::Output, reason: &str) -> Vec<Value> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["reason"] == reason)
+        .collect()
+}
+
+/// Check out `git_ref` into a fresh detached worktree under the temp directory.
+pub fn create_worktree(git_ref: &str, label: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("oxy-{}-{}", label, std::process::id()));
+    let dir_str = dir.to_string_lossy();
+    let output = run_command("git", &["worktree", "add", "--detach", &dir_str, git_ref])?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to check out {}: {}",
+            git_ref,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(dir)
+}
+
+pub fn remove_worktree(dir: &std::path::Path) {
+    let removed = run_command(
+        "git",
+        &["worktree", "remove", "--force", &dir.to_string_lossy()],
+    );
+    if !removed.is_ok_and(|o| o.status.success()) {
+        tracing::warn!("Failed to remove worktree {}", dir.display());
+    }
+}
+
+/// Check whether a cargo subcommand (e.g. `vet` for cargo-vet) is installed.
+pub fn cargo_subcommand_installed(subcommand: &str) -> bool {
+    run_command("cargo", &[subcommand, "--version"])
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
 pub fn run_command_with_timing(
     cmd: &str,
     args: &[&str],
@@ -23,6 +98,63 @@ pub fn run_command_with_timing(
     Ok((output, duration))
 }
 
+const CRATES_IO_API: &str = "https://crates.io/api/v1/crates";
+const CRATES_IO_USER_AGENT: &str = "oxygen (https://github.com/ghostkellz/oxygen)";
+const CRATES_IO_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Fetch the crates.io metadata for a single crate version, cached on disk for a day.
+pub fn crates_io_version(crate_name: &str, version: &str) -> Result<Value> {
+    let cache_file = dirs::cache_dir().map(|dir| {
+        dir.join("oxygen")
+            .join("crates-io")
+            .join(format!("{}-{}.json", crate_name, version))
+    });
+
+    if let Some(path) = &cache_file
+        && let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified())
+        && modified.elapsed().is_ok_and(|age| age < CRATES_IO_CACHE_TTL)
+        && let Ok(content) = std::fs::read_to_string(path)
+        && let Ok(cached) = serde_json::from_str::<Value>(&content)
+    {
+        return Ok(cached);
+    }
+
+    let url = format!("{}/{}/{}", CRATES_IO_API, crate_name, version);
+    let output = run_command(
+        "curl",
+        &["-sSfL", "--max-time", "10", "-A", CRATES_IO_USER_AGENT, &url],
+    )?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "crates.io request for {}@{} failed: {}",
+            crate_name,
+            version,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let response: Value =
+        serde_json::from_slice(&output.stdout).context("Invalid response from crates.io")?;
+    let version_info = response
+        .get("version")
+        .cloned()
+        .context("Unexpected response from crates.io")?;
+
+    if let Some(path) = &cache_file
+        && let Some(parent) = path.parent()
+        && std::fs::create_dir_all(parent).is_ok()
+    {
+        let _ = std::fs::write(path, version_info.to_string());
+    }
+
+    Ok(version_info)
+}
+
+pub fn check_yanked(crate_name: &str, version: &str) -> Result<bool> {
+    let info = crates_io_version(crate_name, version)?;
+    Ok(info.get("yanked").and_then(|y| y.as_bool()).unwrap_or(false))
+}
+
 pub fn output_json(data: &Value) {
     println!("{}", serde_json::to_string_pretty(data).unwrap());
 }
@@ -31,10 +163,135 @@ pub fn output_text(message: &str) {
     println!("{}", message);
 }
 
+/// Ask a yes/no question on the terminal; anything but `y`/`yes` (or no terminal) is a no.
+pub fn confirm(prompt: &str) -> bool {
+    use std::io::{IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{} [y/N] ", prompt);
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Whether the terminal is likely to render OSC 8 hyperlinks.
+pub fn supports_hyperlinks() -> bool {
+    use std::io::IsTerminal;
+
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    if std::env::var("COLORTERM").is_ok_and(|v| !v.is_empty()) {
+        return true;
+    }
+    std::env::var("TERM").is_ok_and(|term| {
+        ["xterm-kitty", "wezterm", "alacritty", "foot", "xterm-ghostty"].contains(&term.as_str())
+            || term.starts_with("vte")
+    })
+}
+
+/// `text` linked to `url` when the terminal supports it, otherwise the plain URL.
+pub fn hyperlink(url: &str, text: &str) -> String {
+    if supports_hyperlinks() {
+        format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+    } else {
+        url.to_string()
+    }
+}
+
 pub fn is_rust_project() -> bool {
     std::path::Path::new("Cargo.toml").exists()
 }
 
+/// Dependency tables that can appear at the top level or under `[target.'cfg(..)']`.
+pub const DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// SHA-256 of a file as lowercase hex, using the platform's checksum tool.
+pub fn sha256_file(path: &std::path::Path) -> Result<String> {
+    checksum_file(path, "256")
+}
+
+/// SHA-512 of a file as lowercase hex, using the platform's checksum tool.
+pub fn sha512_file(path: &std::path::Path) -> Result<String> {
+    checksum_file(path, "512")
+}
+
+fn checksum_file(path: &std::path::Path, bits: &str) -> Result<String> {
+    let path_str = path.to_string_lossy();
+    let output = if cfg!(target_os = "macos") {
+        run_command("shasum", &["-a", bits, &path_str])?
+    } else {
+        run_command(&format!("sha{}sum", bits), &[&path_str])?
+    };
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to checksum {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(String::from)
+        .with_context(|| format!("No checksum output for {}", path.display()))
+}
+
+pub fn read_manifest(path: &std::path::Path) -> Result<toml::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .parse::<toml::Value>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// A single `[[package]]` entry from `Cargo.lock`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    /// Entries are `name`, or `name version` when several versions of it are locked
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    /// Human-readable origin of the package: crates.io, another registry, git, or a local path.
+    pub fn source_kind(&self) -> &'static str {
+        match self.source.as_deref() {
+            Some(s) if s.starts_with("registry+https://github.com/rust-lang/crates.io-index") => {
+                "crates.io"
+            }
+            Some("sparse+https://index.crates.io/") => "crates.io",
+            Some(s) if s.starts_with("git+") => "git",
+            Some(_) => "registry",
+            None => "path",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+pub fn read_lockfile(path: &std::path::Path) -> Result<Vec<LockedPackage>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let lockfile: Lockfile =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(lockfile.package)
+}
+
 pub fn format_duration(duration: std::time::Duration) -> String {
     let secs = duration.as_secs_f64();
     if secs < 1.0 {
@@ -50,6 +307,274 @@ pub fn get_binary_size(path: &str) -> Result<u64> {
     Ok(metadata.len())
 }
 
+pub fn get_dir_size(path: &std::path::Path) -> Result<u64> {
+    let mut total = 0;
+
+    for entry in std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read directory {}", path.display()))?
+    {
+        let entry = entry?;
+        // Don't follow symlinks so linked toolchains aren't counted twice
+        let metadata = entry.path().symlink_metadata()?;
+        if metadata.is_dir() {
+            total += get_dir_size(&entry.path())?;
+        } else {
+            total += metadata.len();
+        }
+    }
+
+    Ok(total)
+}
+
+/// Recursively collect files under `root` accepted by `filter`, skipping `target/` and hidden directories.
+pub fn find_files(root: &std::path::Path, filter: &dyn Fn(&std::path::Path) -> bool) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return found;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name != "target" && !name.starts_with('.') {
+                found.extend(find_files(&path, filter));
+            }
+        } else if file_type.is_file() && filter(&path) {
+            found.push(path);
+        }
+    }
+
+    found.sort();
+    found
+}
+
+/// Lines of Rust code under `root`, ignoring blank lines and comments.
+pub fn count_rust_loc(root: &std::path::Path) -> usize {
+    find_files(root, &|path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    })
+    .iter()
+    .filter_map(|path| std::fs::read_to_string(path).ok())
+    .map(|source| count_code_lines(&source))
+    .sum()
+}
+
+/// Non-blank lines that aren't line comments or inside a block comment.
+pub fn count_code_lines(source: &str) -> usize {
+    let mut in_block_comment = false;
+    let mut count = 0;
+    for line in source.lines() {
+        let line = line.trim();
+        if in_block_comment {
+            if let Some(end) = line.find("*/") {
+                in_block_comment = false;
+                if !line[end + 2..].trim().is_empty() {
+                    count += 1;
+                }
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("/*") {
+            if !rest.contains("*/") {
+                in_block_comment = true;
+            }
+            continue;
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Line-based unified diff between two versions of `file`, with three lines of context.
+/// Returns an empty string when the contents are identical.
+pub fn unified_diff(original: &str, formatted: &str, file: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let old: Vec<&str> = original.lines().collect();
+    let new: Vec<&str> = formatted.lines().collect();
+
+    // Each op is tagged ' ' (unchanged), '-' (removed) or '+' (added)
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    diff_lines(&old, &new, &mut ops);
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let count = |ops: &[(char, &str)], skip: char| ops.iter().filter(|(t, _)| *t != skip).count();
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", file, file);
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT);
+        let mut last = changes[k];
+        // Merge changes whose context windows touch into one hunk
+        while k < changes.len() && changes[k] <= last + 2 * CONTEXT + 1 {
+            last = changes[k];
+            k += 1;
+        }
+        let end = (last + CONTEXT + 1).min(ops.len());
+
+        let hunk = &ops[start..end];
+        let (old_len, new_len) = (count(hunk, '+'), count(hunk, '-'));
+        let old_start = count(&ops[..start], '+') + usize::from(old_len > 0);
+        let new_start = count(&ops[..start], '-') + usize::from(new_len > 0);
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_len, new_start, new_len
+        ));
+        for (tag, line) in hunk {
+            diff.push_str(&format!("{}{}\n", tag, line));
+        }
+    }
+
+    diff
+}
+
+/// Append the edit script turning `old` into `new` to `ops`. Uses Myers' linear-space
+/// divide and conquer, so memory stays proportional to the input even for large files.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str], ops: &mut Vec<(char, &'a str)>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    ops.extend(old[..prefix].iter().map(|line| (' ', *line)));
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    if old_mid.is_empty() {
+        ops.extend(new_mid.iter().map(|line| ('+', *line)));
+    } else if new_mid.is_empty() {
+        ops.extend(old_mid.iter().map(|line| ('-', *line)));
+    } else {
+        let (x, y, u, v) = middle_snake(old_mid, new_mid);
+        diff_lines(&old_mid[..x], &new_mid[..y], ops);
+        ops.extend(old_mid[x..u].iter().map(|line| (' ', *line)));
+        diff_lines(&old_mid[u..], &new_mid[v..], ops);
+    }
+
+    ops.extend(old[old.len() - suffix..].iter().map(|line| (' ', *line)));
+}
+
+/// The middle snake of a shortest edit script between `a` and `b` as `(x, y, u, v)`: lines
+/// `a[x..u]` equal `b[y..v]`, and the edits before and after it can be found independently.
+fn middle_snake(a: &[&str], b: &[&str]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    // Furthest x reached on each diagonal k = x - y, forwards and backwards from the end
+    let offset = max + 1;
+    let mut forward = vec![0isize; (2 * offset + 1) as usize];
+    let mut backward = vec![0isize; (2 * offset + 1) as usize];
+    let at = |k: isize| (k + offset) as usize;
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let (x0, y0) = (x, x - k);
+            let mut y = y0;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[at(k)] = x;
+            if odd && (k - delta).abs() < d && x + backward[at(delta - k)] >= n {
+                return (x0 as usize, y0 as usize, x as usize, y as usize);
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+            let (x0, y0) = (x, x - k);
+            let mut y = y0;
+            while x < n && y < m && a[(n - x - 1) as usize] == b[(m - y - 1) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[at(k)] = x;
+            if !odd && (delta - k).abs() <= d && x + forward[at(delta - k)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                );
+            }
+        }
+    }
+    unreachable!("an edit script of at most n + m steps always exists")
+}
+
+pub fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+}
+
+/// Extracted sources of a registry package under `$CARGO_HOME/registry/src`, if downloaded.
+pub fn registry_source_dir(name: &str, version: &str) -> Option<PathBuf> {
+    let registry_src = cargo_home()?.join("registry").join("src");
+    std::fs::read_dir(registry_src)
+        .ok()?
+        .flatten()
+        .map(|index| index.path().join(format!("{}-{}", name, version)))
+        .find(|dir| dir.join("Cargo.toml").is_file())
+}
+
+pub fn rustup_home() -> Option<PathBuf> {
+    std::env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".rustup")))
+}
+
+/// Cargo config files lowest precedence first: `$CARGO_HOME/config.toml`, then each
+/// `.cargo/config.toml` from the filesystem root down to the current directory.
+pub fn cargo_config_files() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::current_dir()
+        .map(|cwd| cwd.ancestors().map(|dir| dir.join(".cargo")).collect())
+        .unwrap_or_default();
+    if let Some(cargo_home) = cargo_home()
+        && !dirs.contains(&cargo_home)
+    {
+        dirs.push(cargo_home);
+    }
+
+    dirs.iter()
+        .rev()
+        .filter_map(|dir| {
+            // Cargo prefers the legacy extensionless file when both exist
+            [dir.join("config"), dir.join("config.toml")]
+                .into_iter()
+                .find(|path| path.is_file())
+        })
+        .collect()
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
     let mut size = bytes as f64;